@@ -1,5 +1,11 @@
 use std::env;
 
+use crate::services::encryption::KeyRing;
+
+/// Key shipped in earlier versions of this file; never acceptable in production
+const DEFAULT_ENCRYPTION_KEY: &str = "default-32-char-encryption-key!";
+const MIN_KEY_LEN: usize = 32;
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub host: String,
@@ -7,11 +13,21 @@ pub struct Config {
     pub contract_id: String,
     pub network: String,
     pub rpc_url: String,
-    pub encryption_key: String,
+    pub network_passphrase: String,
+    /// Account used as the source of read-only simulations
+    pub source_account: String,
+    /// Bid encryption master keys, current plus retired versions
+    pub encryption_keys: KeyRing,
+    /// SQLite file holding the event index
+    pub index_db_path: String,
+    /// Ledger to start indexing from when no cursor is stored yet
+    pub indexer_start_ledger: Option<u32>,
+    pub indexer_poll_secs: u64,
 }
 
 impl Config {
     pub fn from_env() -> Self {
+        let network = env::var("NETWORK").unwrap_or_else(|_| "testnet".to_string());
         Self {
             host: env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
             port: env::var("PORT")
@@ -20,12 +36,124 @@ impl Config {
                 .expect("PORT must be a number"),
             contract_id: env::var("CONTRACT_ID")
                 .expect("CONTRACT_ID must be set"),
-            network: env::var("NETWORK")
-                .unwrap_or_else(|_| "testnet".to_string()),
             rpc_url: env::var("RPC_URL")
                 .unwrap_or_else(|_| "https://soroban-testnet.stellar.org".to_string()),
-            encryption_key: env::var("ENCRYPTION_KEY")
-                .unwrap_or_else(|_| "default-32-char-encryption-key!".to_string()),
+            network_passphrase: env::var("NETWORK_PASSPHRASE")
+                .unwrap_or_else(|_| default_passphrase(&network).to_string()),
+            source_account: env::var("SOURCE_ACCOUNT")
+                .unwrap_or_else(|_| "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M".to_string()),
+            network,
+            encryption_keys: key_ring(
+                &env::var("APP_ENV").unwrap_or_else(|_| "production".to_string()),
+                env::var("ENCRYPTION_KEY").ok().as_deref(),
+                env::var("ENCRYPTION_KEY_ID").ok().as_deref(),
+                env::var("ENCRYPTION_KEYS_PREVIOUS").ok().as_deref(),
+            )
+            .unwrap_or_else(|e| panic!("{}", e)),
+            index_db_path: env::var("INDEX_DB_PATH")
+                .unwrap_or_else(|_| "opentender-index.db".to_string()),
+            indexer_start_ledger: env::var("INDEXER_START_LEDGER")
+                .ok()
+                .map(|v| v.parse().expect("INDEXER_START_LEDGER must be a number")),
+            indexer_poll_secs: env::var("INDEXER_POLL_SECS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .expect("INDEXER_POLL_SECS must be a number"),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn for_tests(rpc_url: &str) -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            contract_id: "CD5JXQDVQJK2ZHYX7U76I45N2DKKFMVCA6WJ6ZQHO2EL6WJV2VAD6GP4".to_string(),
+            network: "testnet".to_string(),
+            rpc_url: rpc_url.to_string(),
+            network_passphrase: default_passphrase("testnet").to_string(),
+            source_account: "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M".to_string(),
+            encryption_keys: KeyRing::new(1, "test-32-char-encryption-key!!!!!"),
+            index_db_path: ":memory:".to_string(),
+            indexer_start_ledger: Some(100),
+            indexer_poll_secs: 1,
         }
     }
-}
\ No newline at end of file
+}
+
+fn default_passphrase(network: &str) -> &'static str {
+    match network {
+        "mainnet" | "public" => "Public Global Stellar Network ; September 2015",
+        "futurenet" => "Test SDF Future Network ; October 2022",
+        "standalone" | "local" => "Standalone Network ; February 2017",
+        _ => "Test SDF Network ; September 2015",
+    }
+}
+
+/// Build the key ring from `ENCRYPTION_KEY` (current, id `ENCRYPTION_KEY_ID`,
+/// default 1) and `ENCRYPTION_KEYS_PREVIOUS` (`id:key,id:key`). Outside
+/// `APP_ENV=development` a missing, default or short key is refused.
+fn key_ring(
+    app_env: &str,
+    key: Option<&str>,
+    key_id: Option<&str>,
+    previous: Option<&str>,
+) -> Result<KeyRing, String> {
+    let dev = matches!(app_env, "development" | "dev");
+    let key = match key {
+        Some(key) => key,
+        None if dev => DEFAULT_ENCRYPTION_KEY,
+        None => return Err("ENCRYPTION_KEY must be set outside APP_ENV=development".to_string()),
+    };
+    if !dev && (key == DEFAULT_ENCRYPTION_KEY || key.len() < MIN_KEY_LEN) {
+        return Err(format!(
+            "ENCRYPTION_KEY must be a non-default key of at least {} bytes outside APP_ENV=development",
+            MIN_KEY_LEN
+        ));
+    }
+
+    let key_id = match key_id {
+        Some(id) => id.parse().map_err(|_| "ENCRYPTION_KEY_ID must be a number".to_string())?,
+        None => 1,
+    };
+    let mut ring = KeyRing::new(key_id, key);
+    for entry in previous.unwrap_or_default().split(',').filter(|e| !e.trim().is_empty()) {
+        let (id, old_key) = entry
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| "ENCRYPTION_KEYS_PREVIOUS entries must be id:key".to_string())?;
+        let id: u32 = id
+            .parse()
+            .map_err(|_| "ENCRYPTION_KEYS_PREVIOUS key ids must be numbers".to_string())?;
+        if id == key_id {
+            return Err(format!("key id {} is both current and previous", id));
+        }
+        ring = ring.with_previous(id, old_key);
+    }
+    Ok(ring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "a-production-key-of-32-bytes-ok!";
+
+    #[test]
+    fn test_default_key_only_in_development() {
+        assert!(key_ring("production", None, None, None).is_err());
+        assert!(key_ring("production", Some(DEFAULT_ENCRYPTION_KEY), None, None).is_err());
+        assert!(key_ring("production", Some("short"), None, None).is_err());
+        assert!(key_ring("development", None, None, None).is_ok());
+        assert_eq!(key_ring("production", Some(KEY), None, None).unwrap().current_id(), 1);
+    }
+
+    #[test]
+    fn test_previous_keys() {
+        let ring = key_ring("production", Some(KEY), Some("3"), Some("1:old-one, 2:old-two")).unwrap();
+        assert_eq!(ring.current_id(), 3);
+        assert_eq!(format!("{:?}", ring), "KeyRing { current: 3, key_ids: [1, 2, 3] }");
+
+        assert!(key_ring("production", Some(KEY), Some("2"), Some("2:clash")).is_err());
+        assert!(key_ring("production", Some(KEY), None, Some("nokey")).is_err());
+    }
+}
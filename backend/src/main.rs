@@ -7,9 +7,17 @@ use actix_cors::Cors;
 use actix_web::{middleware::Logger, web, App, HttpServer};
 use dotenv::dotenv;
 use env_logger::Env;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    // The committee key is dealt offline so no server ever holds it whole
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("deal") {
+        return deal_committee(&args[1..]);
+    }
+
     // Load environment variables
     dotenv().ok();
     
@@ -21,9 +29,18 @@ async fn main() -> std::io::Result<()> {
     log::info!("Starting OpenTender Backend on {}:{}", config.host, config.port);
     log::info!("Contract ID: {}", config.contract_id);
     log::info!("Network: {}", config.network);
+    log::info!("RPC URL: {}", config.rpc_url);
+
+    let store = Arc::new(
+        services::index::IndexStore::open(&config.index_db_path)
+            .expect("Failed to open index database"),
+    );
+    log::info!("Index database: {}", config.index_db_path);
+    actix_web::rt::spawn(services::indexer::run(config.clone(), store.clone()));
 
     // Clone config for use inside closure
     let config_data = config.clone();
+    let store_data = web::Data::from(store);
 
     HttpServer::new(move || {
         // Configure CORS
@@ -37,6 +54,7 @@ async fn main() -> std::io::Result<()> {
             .wrap(cors)
             .wrap(Logger::default())
             .app_data(web::Data::new(config_data.clone()))
+            .app_data(store_data.clone())
             // Health check
             .route("/health", web::get().to(health_check))
             // Tender routes
@@ -46,21 +64,84 @@ async fn main() -> std::io::Result<()> {
                     .route("", web::post().to(routes::tender::create_tender))
                     .route("/{id}", web::get().to(routes::tender::get_tender))
                     .route("/{id}/close", web::post().to(routes::tender::close_tender))
+                    .route("/{id}/approve", web::post().to(routes::approval::approve_publication))
+                    .route("/{id}/award/approve", web::post().to(routes::approval::approve_award))
+                    .route("/{id}/approvals", web::get().to(routes::approval::get_approvals))
+                    .route("/{id}/amend", web::post().to(routes::tender::amend_tender))
+                    .route("/{id}/amendments", web::get().to(routes::tender::get_amendments))
+                    .route("/{id}/cancel", web::post().to(routes::tender::cancel_tender))
+                    .route("/{id}/cancellation", web::get().to(routes::tender::get_cancellation))
                     .route("/{id}/winner", web::get().to(routes::tender::get_winner))
+                    .route("/{id}/award", web::post().to(routes::tender::award_tender))
+                    .route("/{id}/ties", web::get().to(routes::tender::get_tied_bidders))
+                    .route("/{id}/lots/winners", web::get().to(routes::tender::get_lot_winners))
                     .route("/{id}/bidders", web::get().to(routes::tender::get_bidders))
+                    .route("/{id}/reveal-outcomes", web::get().to(routes::tender::get_reveal_outcomes))
+                    .route("/{id}/committee", web::get().to(routes::committee::get_committee))
+                    .route("/{id}/committee", web::post().to(routes::committee::set_committee))
+                    .route("/{id}/shares", web::post().to(routes::committee::submit_key_share))
+                    .route("/{id}/open", web::post().to(routes::committee::open_bids))
+                    .route("/{id}/opening-key", web::post().to(routes::opening::publish_opening_key))
+                    .route("/{id}/open-bids", web::post().to(routes::opening::open_bids))
+                    .route("/{id}/scores", web::post().to(routes::evaluation::submit_scores))
+                    .route("/{id}/scorecards", web::get().to(routes::evaluation::get_scorecards))
+                    .route("/{id}/escrow", web::get().to(routes::escrow::get_escrow))
+                    .route("/{id}/escrow", web::post().to(routes::escrow::fund_escrow))
+                    .route("/{id}/milestones/{index}/release", web::post().to(routes::escrow::release_milestone))
+                    .route("/{id}/milestones/{index}/refund", web::post().to(routes::escrow::refund_milestone))
             )
             // Bid routes
             .service(
                 web::scope("/api/bids")
                     .route("/submit", web::post().to(routes::bid::submit_bid))
+                    .route("/replace", web::post().to(routes::bid::replace_bid))
+                    .route("/withdraw", web::post().to(routes::bid::withdraw_bid))
                     .route("/reveal", web::post().to(routes::bid::reveal_bid))
                     .route("/{tender_id}/{bidder}", web::get().to(routes::bid::get_bid))
             )
+            // Role registry
+            .service(
+                web::scope("/api/roles")
+                    .route("/grant", web::post().to(routes::role::grant_role))
+                    .route("/revoke", web::post().to(routes::role::revoke_role))
+                    .route("/{account}/{role}", web::get().to(routes::role::has_role))
+            )
+            // Bidder registry
+            .service(
+                web::scope("/api/bidders")
+                    .route("/register", web::post().to(routes::registry::register_bidder))
+                    .route("/{bidder}", web::get().to(routes::registry::get_registration))
+                    .route("/{bidder}/strikes", web::get().to(routes::registry::get_strikes))
+                    .route("/{bidder}/approve", web::post().to(routes::registry::approve_bidder))
+                    .route("/{bidder}/suspend", web::post().to(routes::registry::suspend_bidder))
+            )
+            // Debarment list
+            .service(
+                web::scope("/api/debarments")
+                    .route("", web::get().to(routes::debarment::get_debarments))
+                    .route("", web::post().to(routes::debarment::debar_bidder))
+                    .route("/{bidder}", web::get().to(routes::debarment::get_debarment))
+                    .route("/{bidder}/lift", web::post().to(routes::debarment::lift_debarment))
+            )
+            // Approval policies of procuring entities
+            .service(
+                web::scope("/api/approval-policies")
+                    .route("/{entity}", web::get().to(routes::approval::get_approval_policy))
+                    .route("/{entity}", web::put().to(routes::approval::set_approval_policy))
+            )
+            // Signed transaction hand-off
+            .service(
+                web::scope("/api/tx")
+                    .route("/submit", web::post().to(routes::tx::submit_transaction))
+            )
             // Encryption utility
             .service(
                 web::scope("/api/crypto")
-                    .route("/encrypt", web::post().to(routes::bid::encrypt_amount))
                     .route("/decrypt", web::post().to(routes::bid::decrypt_amount))
+                    .route("/reencrypt", web::post().to(routes::bid::reencrypt_amount))
+                    .route("/sealed/verify", web::post().to(routes::bid::verify_sealed))
+                    .route("/sealed/open", web::post().to(routes::bid::open_sealed))
+                    .route("/commitment", web::post().to(routes::bid::compute_commitment))
             )
     })
     .bind((config.host.as_str(), config.port))?
@@ -75,3 +156,31 @@ async fn health_check() -> actix_web::Result<actix_web::HttpResponse> {
         "version": "0.1.0"
     })))
 }
+
+/// `opentender-backend deal <threshold> <members>`: generate a tender key
+/// pair, split its secret across the committee and print the result as
+/// JSON. Run it on a trusted, offline host; nothing is kept.
+fn deal_committee(args: &[String]) -> std::io::Result<()> {
+    let usage = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "usage: opentender-backend deal <threshold> <members>",
+        )
+    };
+    let [threshold, members] = args else {
+        return Err(usage());
+    };
+    let threshold: u8 = threshold.parse().map_err(|_| usage())?;
+    let members: u8 = members.parse().map_err(|_| usage())?;
+
+    let secret = services::sealed::generate_secret();
+    let shares = services::shamir::split(&secret, threshold, members)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    let deal = models::types::CommitteeDeal {
+        encryption_key: hex::encode(services::sealed::public_key(&secret)),
+        share_hashes: shares.iter().map(|s| hex::encode(Sha256::digest(s))).collect(),
+        shares: shares.iter().map(hex::encode).collect(),
+    };
+    println!("{}", serde_json::to_string_pretty(&deal)?);
+    Ok(())
+}
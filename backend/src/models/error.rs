@@ -0,0 +1,263 @@
+use actix_web::http::StatusCode;
+use std::error::Error;
+use std::fmt;
+
+/// Mirror of the contract's `TenderError` (`#[contracterror]`).
+/// Numeric values must stay in sync with `contracts/hello-world/src/lib.rs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenderError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    TenderNotFound = 3,
+    InvalidDeadline = 4,
+    InvalidRevealDeadline = 5,
+    DeadlinePassed = 6,
+    TenderClosed = 7,
+    BidAlreadySubmitted = 8,
+    RevealNotStarted = 9,
+    RevealDeadlinePassed = 10,
+    BidNotFound = 11,
+    AlreadyRevealed = 12,
+    CommitmentMismatch = 13,
+    BelowMinimum = 14,
+    RevealNotEnded = 15,
+    Unauthorized = 16,
+    InvalidEncryptionKey = 17,
+    InvalidCommittee = 18,
+    CommitteeNotFound = 19,
+    ShareAlreadySubmitted = 20,
+    ShareMismatch = 21,
+    WrongOpeningMode = 22,
+    OpeningKeyNotPublished = 23,
+    InvalidBond = 24,
+    NoWinner = 25,
+    EscrowAlreadyFunded = 26,
+    EscrowNotFound = 27,
+    InvalidEscrow = 28,
+    MilestoneNotFound = 29,
+    MilestoneSettled = 30,
+    MilestoneNotDue = 31,
+    InvalidCriteria = 32,
+    InvalidScores = 33,
+    WrongEvaluationMethod = 34,
+    BidNotRevealed = 35,
+    NoPendingAward = 36,
+    NotTied = 37,
+    InvalidLots = 38,
+    LotNotFound = 39,
+    LotRequired = 40,
+    ReasonRequired = 41,
+    DeadlineShortened = 42,
+    RoleNotHeld = 43,
+    SelfRevoke = 44,
+    InvalidApprovalPolicy = 45,
+    AlreadyApproved = 46,
+    NotPendingApproval = 47,
+    TenderNotPublished = 48,
+    AwardNotFinal = 49,
+    BidderNotEligible = 50,
+}
+
+impl TenderError {
+    pub fn from_code(code: u32) -> Option<Self> {
+        use TenderError::*;
+        let err = match code {
+            1 => AlreadyInitialized,
+            2 => NotInitialized,
+            3 => TenderNotFound,
+            4 => InvalidDeadline,
+            5 => InvalidRevealDeadline,
+            6 => DeadlinePassed,
+            7 => TenderClosed,
+            8 => BidAlreadySubmitted,
+            9 => RevealNotStarted,
+            10 => RevealDeadlinePassed,
+            11 => BidNotFound,
+            12 => AlreadyRevealed,
+            13 => CommitmentMismatch,
+            14 => BelowMinimum,
+            15 => RevealNotEnded,
+            16 => Unauthorized,
+            17 => InvalidEncryptionKey,
+            18 => InvalidCommittee,
+            19 => CommitteeNotFound,
+            20 => ShareAlreadySubmitted,
+            21 => ShareMismatch,
+            22 => WrongOpeningMode,
+            23 => OpeningKeyNotPublished,
+            24 => InvalidBond,
+            25 => NoWinner,
+            26 => EscrowAlreadyFunded,
+            27 => EscrowNotFound,
+            28 => InvalidEscrow,
+            29 => MilestoneNotFound,
+            30 => MilestoneSettled,
+            31 => MilestoneNotDue,
+            32 => InvalidCriteria,
+            33 => InvalidScores,
+            34 => WrongEvaluationMethod,
+            35 => BidNotRevealed,
+            36 => NoPendingAward,
+            37 => NotTied,
+            38 => InvalidLots,
+            39 => LotNotFound,
+            40 => LotRequired,
+            41 => ReasonRequired,
+            42 => DeadlineShortened,
+            43 => RoleNotHeld,
+            44 => SelfRevoke,
+            45 => InvalidApprovalPolicy,
+            46 => AlreadyApproved,
+            47 => NotPendingApproval,
+            48 => TenderNotPublished,
+            49 => AwardNotFinal,
+            50 => BidderNotEligible,
+            _ => return None,
+        };
+        Some(err)
+    }
+
+    /// Machine-readable code returned in `ApiResponse::error_code`
+    pub fn code(&self) -> &'static str {
+        use TenderError::*;
+        match self {
+            AlreadyInitialized => "ALREADY_INITIALIZED",
+            NotInitialized => "NOT_INITIALIZED",
+            TenderNotFound => "TENDER_NOT_FOUND",
+            InvalidDeadline => "INVALID_DEADLINE",
+            InvalidRevealDeadline => "INVALID_REVEAL_DEADLINE",
+            DeadlinePassed => "DEADLINE_PASSED",
+            TenderClosed => "TENDER_CLOSED",
+            BidAlreadySubmitted => "BID_ALREADY_SUBMITTED",
+            RevealNotStarted => "REVEAL_NOT_STARTED",
+            RevealDeadlinePassed => "REVEAL_DEADLINE_PASSED",
+            BidNotFound => "BID_NOT_FOUND",
+            AlreadyRevealed => "ALREADY_REVEALED",
+            CommitmentMismatch => "COMMITMENT_MISMATCH",
+            BelowMinimum => "BELOW_MINIMUM",
+            RevealNotEnded => "REVEAL_NOT_ENDED",
+            Unauthorized => "UNAUTHORIZED",
+            InvalidEncryptionKey => "INVALID_ENCRYPTION_KEY",
+            InvalidCommittee => "INVALID_COMMITTEE",
+            CommitteeNotFound => "COMMITTEE_NOT_FOUND",
+            ShareAlreadySubmitted => "SHARE_ALREADY_SUBMITTED",
+            ShareMismatch => "SHARE_MISMATCH",
+            WrongOpeningMode => "WRONG_OPENING_MODE",
+            OpeningKeyNotPublished => "OPENING_KEY_NOT_PUBLISHED",
+            InvalidBond => "INVALID_BOND",
+            NoWinner => "NO_WINNER",
+            EscrowAlreadyFunded => "ESCROW_ALREADY_FUNDED",
+            EscrowNotFound => "ESCROW_NOT_FOUND",
+            InvalidEscrow => "INVALID_ESCROW",
+            MilestoneNotFound => "MILESTONE_NOT_FOUND",
+            MilestoneSettled => "MILESTONE_SETTLED",
+            MilestoneNotDue => "MILESTONE_NOT_DUE",
+            InvalidCriteria => "INVALID_CRITERIA",
+            InvalidScores => "INVALID_SCORES",
+            WrongEvaluationMethod => "WRONG_EVALUATION_METHOD",
+            BidNotRevealed => "BID_NOT_REVEALED",
+            NoPendingAward => "NO_PENDING_AWARD",
+            NotTied => "NOT_TIED",
+            InvalidLots => "INVALID_LOTS",
+            LotNotFound => "LOT_NOT_FOUND",
+            LotRequired => "LOT_REQUIRED",
+            ReasonRequired => "REASON_REQUIRED",
+            DeadlineShortened => "DEADLINE_SHORTENED",
+            RoleNotHeld => "ROLE_NOT_HELD",
+            SelfRevoke => "SELF_REVOKE",
+            InvalidApprovalPolicy => "INVALID_APPROVAL_POLICY",
+            AlreadyApproved => "ALREADY_APPROVED",
+            NotPendingApproval => "NOT_PENDING_APPROVAL",
+            TenderNotPublished => "TENDER_NOT_PUBLISHED",
+            AwardNotFinal => "AWARD_NOT_FINAL",
+            BidderNotEligible => "BIDDER_NOT_ELIGIBLE",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        use TenderError::*;
+        match self {
+            TenderNotFound | BidNotFound | CommitteeNotFound | EscrowNotFound | MilestoneNotFound
+            | LotNotFound => {
+                StatusCode::NOT_FOUND
+            }
+            Unauthorized | BidderNotEligible => StatusCode::FORBIDDEN,
+            InvalidDeadline | InvalidRevealDeadline | CommitmentMismatch | BelowMinimum
+            | InvalidEncryptionKey | InvalidCommittee | ShareMismatch | InvalidBond | InvalidEscrow
+            | InvalidCriteria | InvalidScores | NotTied | InvalidLots | LotRequired
+            | ReasonRequired | DeadlineShortened | SelfRevoke | InvalidApprovalPolicy => {
+                StatusCode::BAD_REQUEST
+            }
+            AlreadyInitialized | DeadlinePassed | TenderClosed | BidAlreadySubmitted
+            | RevealNotStarted | RevealDeadlinePassed | AlreadyRevealed | RevealNotEnded
+            | ShareAlreadySubmitted | WrongOpeningMode | OpeningKeyNotPublished | NoWinner
+            | EscrowAlreadyFunded | MilestoneSettled | MilestoneNotDue | WrongEvaluationMethod
+            | BidNotRevealed | NoPendingAward | RoleNotHeld | AlreadyApproved | NotPendingApproval
+            | TenderNotPublished | AwardNotFinal => {
+                StatusCode::CONFLICT
+            }
+            NotInitialized => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl fmt::Display for TenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TenderError::*;
+        let message = match self {
+            AlreadyInitialized => "Contract already initialized",
+            NotInitialized => "Contract not initialized",
+            TenderNotFound => "Tender not found",
+            InvalidDeadline => "Deadline must be in future",
+            InvalidRevealDeadline => "Reveal deadline must be after bidding deadline",
+            DeadlinePassed => "Bidding deadline has passed",
+            TenderClosed => "Tender is closed",
+            BidAlreadySubmitted => "Bid already submitted; replace or withdraw it before the deadline",
+            RevealNotStarted => "Cannot reveal before bidding deadline",
+            RevealDeadlinePassed => "Reveal deadline has passed",
+            BidNotFound => "Bid not found",
+            AlreadyRevealed => "Bid already revealed",
+            CommitmentMismatch => "Commitment does not match revealed bid",
+            BelowMinimum => "Bid amount below minimum requirement",
+            RevealNotEnded => "Cannot close before reveal deadline and committee quorum",
+            Unauthorized => "Caller is not authorized for this action",
+            InvalidEncryptionKey => "Tender encryption key is invalid",
+            InvalidCommittee => "Committee threshold, members or share hashes are invalid",
+            CommitteeNotFound => "Tender has no key committee",
+            ShareAlreadySubmitted => "Key share already submitted",
+            ShareMismatch => "Key share does not match the registered hash",
+            WrongOpeningMode => "Not available in this tender's opening mode",
+            OpeningKeyNotPublished => "Opening key has not been published",
+            InvalidBond => "Bid bond amount must be positive",
+            NoWinner => "Tender has no winner",
+            EscrowAlreadyFunded => "Escrow already funded",
+            EscrowNotFound => "Tender has no escrow",
+            InvalidEscrow => "Escrow amount must be positive and milestone percentages must sum to 100",
+            MilestoneNotFound => "Milestone not found",
+            MilestoneSettled => "Milestone already released or refunded",
+            MilestoneNotDue => "Milestone due date has not passed",
+            InvalidCriteria => "Criteria weights must sum to 100 with price",
+            InvalidScores => "Scores must cover every criterion and be at most 100",
+            WrongEvaluationMethod => "Not available in this tender's evaluation method",
+            BidNotRevealed => "Bid has not been validly revealed",
+            NoPendingAward => "Tender is not awaiting a manual award",
+            NotTied => "Bidder is not among the tied bidders",
+            InvalidLots => "Lots cannot be combined with these tender rules",
+            LotNotFound => "Lot not found",
+            LotRequired => "Tender is split into lots; bid on a lot",
+            ReasonRequired => "A reason is required to cancel a tender",
+            DeadlineShortened => "Deadlines can only be extended",
+            RoleNotHeld => "The address does not hold that role",
+            SelfRevoke => "A SuperAdmin cannot revoke its own SuperAdmin role",
+            InvalidApprovalPolicy => "Approval threshold must be between 1 and the number of distinct approvers",
+            AlreadyApproved => "Approver has already approved",
+            NotPendingApproval => "Nothing is awaiting approval",
+            TenderNotPublished => "Tender is a draft awaiting publication approvals",
+            AwardNotFinal => "Award is provisional until approved",
+            BidderNotEligible => "Bidder is not eligible: unregistered, suspended, not invited, debarred or over the strike limit",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for TenderError {}
@@ -12,6 +12,395 @@ pub struct Tender {
     pub min_bid: i128,
     pub is_closed: bool,
     pub created_at: u64,
+    pub encryption_key: String, // hex X25519 public key bids are sealed to
+    pub opening_mode: OpeningMode,
+    pub bond: Option<BidBond>, // `BondPolicy::NotRequired` on chain is `None`
+    pub evaluation: Evaluation,
+    pub tie_break: TieBreak,
+    pub lots: Vec<Lot>, // empty for a single-lot tender
+    pub max_lots_per_bidder: u32, // 0 for no limit
+    pub eligibility: Eligibility,
+    pub max_strikes: u32,         // bidders with more strikes are turned away; 0 for no limit
+    pub is_cancelled: bool,       // cancelled tenders are also closed
+    pub is_draft: bool,           // awaiting publication approvals
+    pub award_pending: bool,      // closed with an award awaiting approvals
+}
+
+/// M-of-N approvers whose sign-off a procuring entity's tenders need to be
+/// published and awarded
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ApprovalPolicy {
+    pub approvers: Vec<String>, // empty to lift the policy
+    pub threshold: u32,
+}
+
+/// Mirror of the contract's `ApprovalStage`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStage {
+    Publication,
+    Award,
+}
+
+impl ApprovalStage {
+    /// Variant name as the contract spells it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalStage::Publication => "Publication",
+            ApprovalStage::Award => "Award",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Approval {
+    pub approver: String,
+    pub approved_at: u64,
+}
+
+/// Set an entity's approval policy; `caller` must be a SuperAdmin
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetApprovalPolicyRequest {
+    pub caller: String,
+    pub policy: ApprovalPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApproveRequest {
+    pub approver: String,
+}
+
+/// Terms of a tender as set by one amendment
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Amendment {
+    pub description: String,
+    pub ipfs_hash: String,
+    pub deadline: u64,
+    pub reveal_deadline: u64,
+    pub amended_at: u64,
+}
+
+/// Why and by whom a tender was cancelled
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Cancellation {
+    pub cancelled_by: String,
+    pub reason: String,
+    pub cancelled_at: u64,
+}
+
+/// Exclusion of a bidder from all tenders between `start` and `end`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Debarment {
+    pub bidder: String,
+    pub reason: String,
+    pub start: u64,
+    pub end: u64,
+    pub debarred_by: String,
+    pub lifted_at: Option<u64>, // set when lifted or replaced before `end`
+}
+
+/// A bidder's debarment on record, if any, and whether it applies now
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DebarmentStatus {
+    pub debarred: bool,
+    pub debarment: Option<Debarment>,
+}
+
+/// Debar a bidder; `caller` must be a SuperAdmin
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DebarBidderRequest {
+    pub caller: String,
+    pub bidder: String,
+    pub reason: String,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiftDebarmentRequest {
+    pub caller: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DebarmentListQuery {
+    pub start: Option<u32>,
+    pub limit: Option<u32>, // the contract caps a page at 50
+}
+
+/// Independently awarded part of a multi-lot tender, with its own minimum
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Lot {
+    pub title: String,
+    pub min_bid: i128,
+}
+
+/// Deposit escrowed with every bid; see the contract's `BidBond`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BidBond {
+    pub token: String, // token contract address
+    pub amount: i128,
+    pub beneficiary: String, // receives forfeited bonds
+}
+
+/// Mirror of the contract's `Evaluation`: the award rule
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Evaluation {
+    #[default]
+    LowestPrice,
+    HighestPrice, // min_bid is the reserve price
+    Vickrey,      // highest offer wins, pays the runner-up's offer
+    Weighted(WeightedCriteria),
+}
+
+/// Percent weights; `price_weight` plus the criteria weights sum to 100
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct WeightedCriteria {
+    pub price_weight: u32,
+    pub criteria: Vec<Criterion>,
+    pub evaluator: String, // scores each revealed bid on the criteria
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Criterion {
+    pub name: String,
+    pub weight: u32,
+}
+
+/// Mirror of the contract's `Role`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    SuperAdmin, // grants and revokes roles
+    ProcuringEntity,
+    Evaluator,
+    Auditor,  // read-only oversight; gate audit routes with `has_role`
+    Bidder,   // granted with an approved registration; required by registered-only tenders
+    Verifier, // approves and suspends bidder registrations
+}
+
+impl Role {
+    /// Variant name as the contract spells it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::SuperAdmin => "SuperAdmin",
+            Role::ProcuringEntity => "ProcuringEntity",
+            Role::Evaluator => "Evaluator",
+            Role::Auditor => "Auditor",
+            Role::Bidder => "Bidder",
+            Role::Verifier => "Verifier",
+        }
+    }
+}
+
+/// Grant or revoke a role; `caller` must be a SuperAdmin
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleRequest {
+    pub caller: String,
+    pub account: String,
+    pub role: Role,
+}
+
+/// Mirror of the contract's `Eligibility`: who may bid on a tender
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(tag = "mode", content = "invited", rename_all = "snake_case")]
+pub enum Eligibility {
+    #[default]
+    Open,
+    Registered, // approved, unexpired registration in the bidder registry
+    InviteOnly(Vec<String>),
+}
+
+/// A bidder's entry in the contract's bidder registry
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Registration {
+    pub credentials_hash: String, // IPFS hash of the credential documents
+    pub status: RegistrationStatus,
+    pub registered_at: u64,
+    pub verified_by: Option<String>,
+    pub expires_at: u64, // 0 until approved
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationStatus {
+    Pending,
+    Approved,
+    Suspended,
+}
+
+impl RegistrationStatus {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "Pending" => Some(RegistrationStatus::Pending),
+            "Approved" => Some(RegistrationStatus::Approved),
+            "Suspended" => Some(RegistrationStatus::Suspended),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterBidderRequest {
+    pub bidder: String,
+    pub credentials_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApproveBidderRequest {
+    pub verifier: String,
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuspendBidderRequest {
+    pub verifier: String,
+}
+
+/// Strikes a bidder collected by committing to bids it never revealed;
+/// all zero for a bidder with a clean record
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct StrikeRecord {
+    pub count: u32,
+    pub last_tender: u64,
+    pub last_struck_at: u64,
+}
+
+/// How a bidder's sealed bids stood when the tender closed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BidderOutcome {
+    pub bidder: String,
+    pub outcome: RevealOutcome,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RevealOutcome {
+    Revealed,
+    Invalid,    // revealed below the minimum bid
+    Unrevealed, // struck on bidder-reveal tenders
+}
+
+impl RevealOutcome {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "Revealed" => Some(RevealOutcome::Revealed),
+            "Invalid" => Some(RevealOutcome::Invalid),
+            "Unrevealed" => Some(RevealOutcome::Unrevealed),
+            _ => None,
+        }
+    }
+}
+
+/// Mirror of the contract's `TieBreak`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    #[default]
+    EarliestCommit,
+    Lottery, // seeded from the contract PRNG; the seed is published
+    Manual,  // creator or admin awards one of the tied bidders
+}
+
+impl TieBreak {
+    /// Variant name as the contract spells it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TieBreak::EarliestCommit => "EarliestCommit",
+            TieBreak::Lottery => "Lottery",
+            TieBreak::Manual => "Manual",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "EarliestCommit" => Some(TieBreak::EarliestCommit),
+            "Lottery" => Some(TieBreak::Lottery),
+            "Manual" => Some(TieBreak::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// Mirror of the contract's `OpeningMode`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpeningMode {
+    #[default]
+    BidderReveal, // each bidder reveals their own bid
+    TimeLock,     // the authority publishes its key and opens all bids
+}
+
+impl OpeningMode {
+    /// Variant name as the contract spells it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpeningMode::BidderReveal => "BidderReveal",
+            OpeningMode::TimeLock => "TimeLock",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "BidderReveal" => Some(OpeningMode::BidderReveal),
+            "TimeLock" => Some(OpeningMode::TimeLock),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TenderStatus {
+    Open,   // accepting bids
+    Reveal, // bidding over, not yet closed
+    Closed, // closed after the reveal phase
+    Cancelled,
+    Draft, // awaiting publication approvals
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TenderSortField {
+    #[default]
+    Id,
+    Deadline,
+    RevealDeadline,
+    CreatedAt,
+    MinBid,
+    Title,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Query string for `GET /api/tenders`
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TenderListQuery {
+    pub status: Option<TenderStatus>,
+    pub creator: Option<String>,
+    pub deadline_from: Option<u64>,
+    pub deadline_to: Option<u64>,
+    pub min_bid_from: Option<String>, // i128 as decimal string
+    pub min_bid_to: Option<String>,
+    pub q: Option<String>, // case-insensitive title search
+    #[serde(default)]
+    pub sort: TenderSortField,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub cursor: Option<String>, // next_cursor from the previous page
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TenderPage {
+    pub tenders: Vec<Tender>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,6 +412,23 @@ pub struct CreateTenderRequest {
     pub deadline: u64,
     pub reveal_deadline: u64,
     pub min_bid: i128,
+    pub encryption_key: String, // hex-encoded X25519 public key of the tender authority
+    #[serde(default)]
+    pub opening_mode: OpeningMode,
+    #[serde(default)]
+    pub bond: Option<BidBond>,
+    #[serde(default)]
+    pub evaluation: Evaluation,
+    #[serde(default)]
+    pub tie_break: TieBreak,
+    #[serde(default)]
+    pub lots: Vec<Lot>,
+    #[serde(default)]
+    pub max_lots_per_bidder: u32,
+    #[serde(default)]
+    pub eligibility: Eligibility,
+    #[serde(default)]
+    pub max_strikes: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,9 +436,11 @@ pub struct Bid {
     pub bidder: String,
     pub tender_id: u64,
     pub encrypted_amount: String,
+    pub commitment: String,
     pub revealed_amount: Option<i128>,
     pub is_valid: bool,
     pub timestamp: u64,
+    pub bond: i128, // escrowed bond; 0 once refunded or forfeited
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,6 +448,9 @@ pub struct SubmitBidRequest {
     pub tender_id: u64,
     pub bidder: String,
     pub encrypted_amount: String,
+    pub commitment: String, // hex-encoded sha256 commitment
+    #[serde(default)]
+    pub lot: Option<u32>, // required on multi-lot tenders
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -47,15 +458,20 @@ pub struct RevealBidRequest {
     pub tender_id: u64,
     pub bidder: String,
     pub actual_amount: i128,
-    pub decryption_key: String,
+    pub salt: String, // hex-encoded 32-byte salt used in the commitment
+    #[serde(default)]
+    pub lot: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Winner {
     pub tender_id: u64,
     pub bidder: String,
     pub amount: i128,
+    pub payable: i128, // price due under the award rule; below `amount` for Vickrey
     pub selected_at: u64,
+    #[serde(default)]
+    pub lot: Option<u32>, // awarded lot of a multi-lot tender
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,26 +480,326 @@ pub struct CloseTenderRequest {
     pub caller: String,
 }
 
+/// Fields left out keep their current value
 #[derive(Debug, Serialize, Deserialize)]
-pub struct EncryptRequest {
-    pub amount: i128,
+pub struct AmendTenderRequest {
+    pub creator: String,
+    pub description: Option<String>,
+    pub ipfs_hash: Option<String>,
+    pub deadline: Option<u64>, // may only move later
+    pub reveal_deadline: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelTenderRequest {
+    pub caller: String, // creator or admin
+    pub reason: String, // required, recorded on chain
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WithdrawBidRequest {
+    pub tender_id: u64,
+    pub bidder: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AwardTenderRequest {
+    pub caller: String, // creator or admin
+    pub bidder: String, // one of the tied bidders
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EncryptResponse {
     pub encrypted_amount: String,
     pub decryption_key: String,
+    /// Master key version the amount was sealed under
+    pub key_id: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DecryptRequest {
+    pub tender_id: u64,
+    pub encrypted_amount: String,
+    /// Nonce returned by encrypt; no longer needed since it travels in the ciphertext
+    #[serde(default)]
+    pub decryption_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReencryptRequest {
+    pub tender_id: u64,
     pub encrypted_amount: String,
-    pub decryption_key: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DecryptResponse {
     pub amount: i128,
+    /// Key version the ciphertext was sealed under; `None` for the legacy format
+    pub key_id: Option<u32>,
+    /// True when the ciphertext should be re-encrypted under the current key
+    pub stale: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifySealedRequest {
+    pub tender_id: u64,
+    pub sealed_amount: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifySealedResponse {
+    pub valid: bool,
+    pub reason: Option<String>,
+}
+
+/// Sent by the tender authority once bidding has closed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenSealedRequest {
+    pub tender_id: u64,
+    pub bidder: String,
+    pub authority_secret: String, // hex X25519 secret key; used once, never stored
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenSealedResponse {
+    pub amount: i128,
+    pub revealed_amount: Option<i128>,
+    /// Whether the sealed amount agrees with the on-chain reveal, if any
+    pub matches_reveal: Option<bool>,
+}
+
+/// Key holders for a tender's sealed bids; see `shamir`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Committee {
+    pub members: Vec<String>,
+    pub share_hashes: Vec<String>, // hex sha256 of each member's share, same order
+    pub threshold: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyShare {
+    pub member: String,
+    pub share: String, // hex
+    pub submitted_at: u64,
+}
+
+/// Output of the offline key ceremony (`opentender-backend deal`): publish
+/// `encryption_key` on the tender, hand `shares[i]` to member i and
+/// register only `share_hashes` through the API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitteeDeal {
+    pub encryption_key: String,
+    pub shares: Vec<String>,
+    pub share_hashes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetCommitteeRequest {
+    pub creator: String,
+    pub committee: Committee,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitKeyShareRequest {
+    pub member: String,
+    pub share: String, // hex, as dealt
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitteeStatus {
+    pub committee: Committee,
+    pub contributors: Vec<String>,
+    pub quorum: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenedBid {
+    pub bidder: String,
+    /// `None` when the sealed amount could not be opened
+    pub amount: Option<i128>,
+    pub revealed_amount: Option<i128>,
+    pub matches_reveal: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublishOpeningKeyRequest {
+    pub creator: String,
+    pub secret_key: String, // hex X25519 secret matching the tender's encryption key
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenBidsRequest {
+    /// Any account may submit the openings once the key is published
+    #[serde(alias = "creator")]
+    pub caller: String,
+}
+
+/// Opening of one bid's commitment, as submitted in an `open_bids` batch
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BidOpening {
+    pub bidder: String,
+    pub amount: i128,
+    pub salt: String, // hex
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenBidsPreparation {
+    pub transaction: PreparedTransaction,
+    pub openings: Vec<BidOpening>,
+    /// Unrevealed bids whose sealed amount did not open their commitment
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitScoresRequest {
+    pub evaluator: String,
+    pub bidder: String,
+    pub scores: Vec<u32>, // 0..=100 per criterion, in criterion order
+}
+
+/// Combined score of one bid, fixed-point with 1_000_000 as 1.0
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Scorecard {
+    pub bidder: String,
+    pub amount: i128,
+    pub price_score: i128,
+    pub criteria_scores: Vec<i128>, // unweighted, in criterion order
+    pub total: i128,
+}
+
+/// Winner's payment held by the contract; see the contract's `Escrow`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Escrow {
+    pub token: String,
+    pub amount: i128,
+    pub inspector: Option<String>, // may sign off milestones besides the creator
+    pub milestones: Vec<Milestone>,
+    pub funded_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MilestoneTerms {
+    pub description: String,
+    pub percentage: u32, // all milestones sum to 100
+    pub due_date: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Milestone {
+    pub description: String,
+    pub percentage: u32,
+    pub due_date: u64,
+    pub amount: i128,
+    pub status: MilestoneStatus,
+}
+
+/// Mirror of the contract's `MilestoneStatus`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MilestoneStatus {
+    Pending,
+    Released, // paid to the winner
+    Refunded, // returned to the creator after the due date
+}
+
+impl MilestoneStatus {
+    /// Variant name as the contract spells it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MilestoneStatus::Pending => "Pending",
+            MilestoneStatus::Released => "Released",
+            MilestoneStatus::Refunded => "Refunded",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "Pending" => Some(MilestoneStatus::Pending),
+            "Released" => Some(MilestoneStatus::Released),
+            "Refunded" => Some(MilestoneStatus::Refunded),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FundEscrowRequest {
+    pub creator: String,
+    pub token: String,
+    pub amount: i128,
+    pub inspector: Option<String>,
+    pub milestones: Vec<MilestoneTerms>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MilestoneActionRequest {
+    pub caller: String, // creator or inspector to release, creator to refund
+}
+
+/// Escrow with running totals; `overdue` lists pending milestones past
+/// their due date, which the creator may refund
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EscrowStatus {
+    pub escrow: Escrow,
+    pub released: i128,
+    pub refunded: i128,
+    pub held: i128,
+    pub overdue: Vec<u32>,
+}
+
+impl EscrowStatus {
+    pub fn new(escrow: Escrow, now: u64) -> Self {
+        let total = |status| -> i128 {
+            escrow.milestones.iter().filter(|m| m.status == status).map(|m| m.amount).sum()
+        };
+        let overdue = escrow
+            .milestones
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.status == MilestoneStatus::Pending && now > m.due_date)
+            .map(|(i, _)| i as u32)
+            .collect();
+        Self {
+            released: total(MilestoneStatus::Released),
+            refunded: total(MilestoneStatus::Refunded),
+            held: total(MilestoneStatus::Pending),
+            overdue,
+            escrow,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitmentRequest {
+    pub tender_id: u64,
+    pub bidder: String,
+    pub amount: i128,
+    pub salt: Option<String>, // generated when omitted
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitmentResponse {
+    pub commitment: String,
+    pub salt: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreparedTransaction {
+    pub xdr: String, // unsigned base64 TransactionEnvelope, simulated and ready to sign
+    pub hash: String,
+    pub network_passphrase: String,
+    pub fee: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitTransactionRequest {
+    pub xdr: String, // signed base64 TransactionEnvelope
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionResult {
+    pub hash: String,
+    pub return_value: Option<String>, // base64 ScVal returned by the contract call
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -91,6 +807,7 @@ pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    pub error_code: Option<String>, // machine-readable, e.g. "TENDER_NOT_FOUND"
 }
 
 impl<T> ApiResponse<T> {
@@ -99,6 +816,7 @@ impl<T> ApiResponse<T> {
             success: true,
             data: Some(data),
             error: None,
+            error_code: None,
         }
     }
 
@@ -107,6 +825,14 @@ impl<T> ApiResponse<T> {
             success: false,
             data: None,
             error: Some(message),
+            error_code: None,
+        }
+    }
+
+    pub fn error_with_code(message: String, code: &str) -> Self {
+        Self {
+            error_code: Some(code.to_string()),
+            ..Self::error(message)
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,107 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use serde::Serialize;
+use crate::config::Config;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::stellar;
+
+/// Prepare a set_approval_policy transaction for the SuperAdmin's wallet to sign
+pub async fn set_approval_policy(
+    config: web::Data<Config>,
+    path: web::Path<String>,
+    req: web::Json<SetApprovalPolicyRequest>,
+) -> HttpResponse {
+    let entity = path.into_inner();
+    log::info!("Setting approval policy of {}", entity);
+
+    match stellar::set_approval_policy(&config, &entity, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared set_approval_policy transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare approval policy: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_approval_policy(config: web::Data<Config>, path: web::Path<String>) -> HttpResponse {
+    let entity = path.into_inner();
+
+    match stellar::get_approval_policy(&config, &entity).await {
+        Ok(Some(policy)) => HttpResponse::Ok().json(ApiResponse::success(policy)),
+        Ok(None) => HttpResponse::NotFound().json(ApiResponse::<ApprovalPolicy>::error(
+            "No approval policy for this entity".to_string(),
+        )),
+        Err(e) => {
+            log::error!("Failed to fetch approval policy: {}", e);
+            error_response::<ApprovalPolicy>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare an approve_tender transaction publishing a draft once enough
+/// approvers have signed
+pub async fn approve_publication(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<ApproveRequest>,
+) -> HttpResponse {
+    approve(config, path.into_inner(), ApprovalStage::Publication, req.into_inner()).await
+}
+
+/// Prepare an approve_award transaction making a provisional award final
+/// once enough approvers have signed
+pub async fn approve_award(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<ApproveRequest>,
+) -> HttpResponse {
+    approve(config, path.into_inner(), ApprovalStage::Award, req.into_inner()).await
+}
+
+async fn approve(config: web::Data<Config>, tender_id: u64, stage: ApprovalStage, req: ApproveRequest) -> HttpResponse {
+    log::info!("Approving {} of tender {} by {}", stage.as_str(), tender_id, req.approver);
+
+    match stellar::approve(&config, tender_id, stage, &req.approver).await {
+        Ok(prepared) => {
+            log::info!("Prepared approval transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare approval: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Approvals recorded on chain for each stage of a tender
+#[derive(Debug, Serialize)]
+pub struct TenderApprovals {
+    pub policy: Option<ApprovalPolicy>, // as in force when the tender was created
+    pub publication: Vec<Approval>,
+    pub award: Vec<Approval>,
+}
+
+pub async fn get_approvals(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching approvals of tender {}", tender_id);
+
+    let result = async {
+        Ok::<_, Box<dyn std::error::Error>>(TenderApprovals {
+            policy: stellar::get_tender_approvers(&config, tender_id).await?,
+            publication: stellar::get_approvals(&config, tender_id, ApprovalStage::Publication).await?,
+            award: stellar::get_approvals(&config, tender_id, ApprovalStage::Award).await?,
+        })
+    }
+    .await;
+
+    match result {
+        Ok(approvals) => HttpResponse::Ok().json(ApiResponse::success(approvals)),
+        Err(e) => {
+            log::error!("Failed to fetch approvals: {}", e);
+            error_response::<TenderApprovals>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -1,29 +1,93 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{http::StatusCode, web, HttpResponse};
 use crate::config::Config;
+use crate::models::error::TenderError;
 use crate::models::types::*;
-use crate::services::{encryption, stellar};
+use crate::routes::error_response;
+use crate::services::{encryption, sealed, stellar};
 
-/// Submit a bid
+/// Prepare a submit_bid transaction for the bidder's wallet to sign
 pub async fn submit_bid(
     config: web::Data<Config>,
     req: web::Json<SubmitBidRequest>,
 ) -> HttpResponse {
     log::info!("Submitting bid for tender {}", req.tender_id);
+
+    // Refuse envelopes the tender authority could never open
+    match sealed_to_tender(&config, req.tender_id, &req.encrypted_amount).await {
+        Ok(Ok(())) => {}
+        Ok(Err(reason)) => {
+            return HttpResponse::BadRequest().json(ApiResponse::<PreparedTransaction>::error(reason))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch tender for bid: {}", e);
+            return error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
     
     match stellar::submit_bid(&config, req.into_inner()).await {
-        Ok(_) => {
-            log::info!("Bid submitted successfully");
-            HttpResponse::Ok().json(ApiResponse::success("Bid submitted"))
+        Ok(prepared) => {
+            log::info!("Prepared submit_bid transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
         }
         Err(e) => {
-            log::error!("Failed to submit bid: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<String>::error(e.to_string()))
+            log::error!("Failed to prepare bid: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a replace_bid transaction for the bidder's wallet to sign;
+/// allowed until the bidding deadline
+pub async fn replace_bid(
+    config: web::Data<Config>,
+    req: web::Json<SubmitBidRequest>,
+) -> HttpResponse {
+    log::info!("Replacing bid for tender {}", req.tender_id);
+
+    match sealed_to_tender(&config, req.tender_id, &req.encrypted_amount).await {
+        Ok(Ok(())) => {}
+        Ok(Err(reason)) => {
+            return HttpResponse::BadRequest().json(ApiResponse::<PreparedTransaction>::error(reason))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch tender for bid: {}", e);
+            return error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    match stellar::replace_bid(&config, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared replace_bid transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare bid replacement: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
-/// Reveal a bid
+/// Prepare a withdraw_bid transaction for the bidder's wallet to sign; the
+/// bond is refunded
+pub async fn withdraw_bid(
+    config: web::Data<Config>,
+    req: web::Json<WithdrawBidRequest>,
+) -> HttpResponse {
+    log::info!("Withdrawing bid for tender {}", req.tender_id);
+
+    match stellar::withdraw_bid(&config, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared withdraw_bid transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare bid withdrawal: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a reveal_bid transaction for the bidder's wallet to sign
 pub async fn reveal_bid(
     config: web::Data<Config>,
     req: web::Json<RevealBidRequest>,
@@ -31,14 +95,13 @@ pub async fn reveal_bid(
     log::info!("Revealing bid for tender {}", req.tender_id);
     
     match stellar::reveal_bid(&config, req.into_inner()).await {
-        Ok(_) => {
-            log::info!("Bid revealed successfully");
-            HttpResponse::Ok().json(ApiResponse::success("Bid revealed"))
+        Ok(prepared) => {
+            log::info!("Prepared reveal_bid transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
         }
         Err(e) => {
-            log::error!("Failed to reveal bid: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<String>::error(e.to_string()))
+            log::error!("Failed to prepare reveal: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
@@ -57,48 +120,112 @@ pub async fn get_bid(
             .json(ApiResponse::<Bid>::error("Bid not found".to_string())),
         Err(e) => {
             log::error!("Failed to fetch bid: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<Bid>::error(e.to_string()))
+            error_response::<Bid>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
-/// Encrypt amount (helper endpoint)
-pub async fn encrypt_amount(
+/// Check a sealed amount against the tender's authority key (helper endpoint).
+/// Bidders seal client-side; this only confirms the envelope is openable.
+pub async fn verify_sealed(
     config: web::Data<Config>,
-    req: web::Json<EncryptRequest>,
+    req: web::Json<VerifySealedRequest>,
 ) -> HttpResponse {
-    log::info!("Encrypting amount");
-    
-    match encryption::encrypt_amount(req.amount, &config.encryption_key) {
-        Ok((encrypted, key)) => {
-            HttpResponse::Ok().json(ApiResponse::success(EncryptResponse {
-                encrypted_amount: encrypted,
-                decryption_key: key,
-            }))
+    log::info!("Verifying sealed amount for tender {}", req.tender_id);
+
+    match sealed_to_tender(&config, req.tender_id, &req.sealed_amount).await {
+        Ok(result) => HttpResponse::Ok().json(ApiResponse::success(VerifySealedResponse {
+            valid: result.is_ok(),
+            reason: result.err(),
+        })),
+        Err(e) => {
+            log::error!("Failed to fetch tender: {}", e);
+            error_response::<VerifySealedResponse>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Open a bidder's sealed amount with the authority secret (helper endpoint).
+/// Only accepted once bidding has closed; the secret is not retained.
+pub async fn open_sealed(
+    config: web::Data<Config>,
+    req: web::Json<OpenSealedRequest>,
+) -> HttpResponse {
+    log::info!("Opening sealed bid of {} for tender {}", req.bidder, req.tender_id);
+
+    let tender = match stellar::get_tender(&config, req.tender_id).await {
+        Ok(tender) => tender,
+        Err(e) => {
+            log::error!("Failed to fetch tender: {}", e);
+            return error_response::<OpenSealedResponse>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let current_time = chrono::Utc::now().timestamp() as u64;
+    if current_time < tender.deadline {
+        return error_response::<OpenSealedResponse>(&TenderError::RevealNotStarted, StatusCode::CONFLICT);
+    }
+
+    let secret = match sealed::parse_key_hex(&req.authority_secret) {
+        Ok(secret) if sealed::parse_key_hex(&tender.encryption_key).ok() == Some(sealed::public_key(&secret)) => {
+            secret
+        }
+        _ => {
+            return error_response::<OpenSealedResponse>(&TenderError::Unauthorized, StatusCode::FORBIDDEN)
+        }
+    };
+
+    let bid = match stellar::get_bid(&config, req.tender_id, &req.bidder).await {
+        Ok(Some(bid)) => bid,
+        Ok(None) => {
+            return error_response::<OpenSealedResponse>(&TenderError::BidNotFound, StatusCode::NOT_FOUND)
         }
         Err(e) => {
-            log::error!("Failed to encrypt: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<EncryptResponse>::error(e.to_string()))
+            log::error!("Failed to fetch bid: {}", e);
+            return error_response::<OpenSealedResponse>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    match sealed::open_sealed_amount(&bid.encrypted_amount, req.tender_id, &secret) {
+        Ok(amount) => HttpResponse::Ok().json(ApiResponse::success(OpenSealedResponse {
+            amount,
+            revealed_amount: bid.revealed_amount,
+            matches_reveal: bid.revealed_amount.map(|revealed| revealed == amount),
+        })),
+        Err(e) => {
+            log::error!("Failed to open sealed bid: {}", e);
+            HttpResponse::UnprocessableEntity()
+                .json(ApiResponse::<OpenSealedResponse>::error(e.to_string()))
         }
     }
 }
 
-/// Decrypt amount (helper endpoint)
+/// `Ok(Err(reason))` when the envelope is not addressed to the tender's key
+async fn sealed_to_tender(
+    config: &Config,
+    tender_id: u64,
+    sealed_amount: &str,
+) -> Result<Result<(), String>, Box<dyn std::error::Error>> {
+    let tender = stellar::get_tender(config, tender_id).await?;
+    let authority_key = sealed::parse_key_hex(&tender.encryption_key)?;
+    Ok(sealed::verify_sealed_amount(sealed_amount, &authority_key).map_err(|e| e.to_string()))
+}
+
+/// Decrypt amount sealed with the server key by earlier versions (helper endpoint)
 pub async fn decrypt_amount(
     config: web::Data<Config>,
     req: web::Json<DecryptRequest>,
 ) -> HttpResponse {
     log::info!("Decrypting amount");
     
-    match encryption::decrypt_amount(
-        &req.encrypted_amount,
-        &req.decryption_key,
-        &config.encryption_key,
-    ) {
+    match encryption::decrypt_amount(&req.encrypted_amount, req.tender_id, &config.encryption_keys) {
         Ok(amount) => {
-            HttpResponse::Ok().json(ApiResponse::success(DecryptResponse { amount }))
+            let key_id = encryption::key_id_of(&req.encrypted_amount).ok().flatten();
+            HttpResponse::Ok().json(ApiResponse::success(DecryptResponse {
+                amount,
+                key_id,
+                stale: key_id != Some(config.encryption_keys.current_id()),
+            }))
         }
         Err(e) => {
             log::error!("Failed to decrypt: {}", e);
@@ -106,4 +233,56 @@ pub async fn decrypt_amount(
                 .json(ApiResponse::<DecryptResponse>::error(e.to_string()))
         }
     }
+}
+
+/// Re-seal an encrypted amount under the current key after a rotation
+pub async fn reencrypt_amount(
+    config: web::Data<Config>,
+    req: web::Json<ReencryptRequest>,
+) -> HttpResponse {
+    log::info!("Re-encrypting amount for tender {}", req.tender_id);
+
+    match encryption::reencrypt_amount(&req.encrypted_amount, req.tender_id, &config.encryption_keys) {
+        Ok((encrypted, key)) => {
+            HttpResponse::Ok().json(ApiResponse::success(EncryptResponse {
+                encrypted_amount: encrypted,
+                decryption_key: key,
+                key_id: config.encryption_keys.current_id(),
+            }))
+        }
+        Err(e) => {
+            log::error!("Failed to re-encrypt: {}", e);
+            HttpResponse::BadRequest()
+                .json(ApiResponse::<EncryptResponse>::error(e.to_string()))
+        }
+    }
+}
+
+/// Compute a bid commitment (helper endpoint)
+pub async fn compute_commitment(req: web::Json<CommitmentRequest>) -> HttpResponse {
+    log::info!("Computing commitment for tender {}", req.tender_id);
+
+    let salt = match &req.salt {
+        Some(salt_hex) => match hex::decode(salt_hex).ok().and_then(|b| <[u8; 32]>::try_from(b).ok()) {
+            Some(salt) => salt,
+            None => {
+                return HttpResponse::BadRequest().json(ApiResponse::<CommitmentResponse>::error(
+                    "Salt must be 32 hex-encoded bytes".to_string(),
+                ))
+            }
+        },
+        None => encryption::generate_salt(),
+    };
+
+    match encryption::compute_commitment(req.amount, &salt, &req.bidder, req.tender_id) {
+        Ok(commitment) => HttpResponse::Ok().json(ApiResponse::success(CommitmentResponse {
+            commitment: hex::encode(commitment),
+            salt: hex::encode(salt),
+        })),
+        Err(e) => {
+            log::error!("Failed to compute commitment: {}", e);
+            HttpResponse::BadRequest()
+                .json(ApiResponse::<CommitmentResponse>::error(e.to_string()))
+        }
+    }
 }
\ No newline at end of file
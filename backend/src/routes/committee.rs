@@ -0,0 +1,153 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::error::TenderError;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::{sealed, shamir, stellar};
+
+/// Prepare a set_committee transaction for the creator's wallet to sign
+pub async fn set_committee(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<SetCommitteeRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Setting committee for tender {}", tender_id);
+
+    match stellar::set_committee(&config, tender_id, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared set_committee transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare committee for tender {}: {}", tender_id, e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Committee of a tender and which members have handed in their shares
+pub async fn get_committee(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching committee for tender {}", tender_id);
+
+    let result = async {
+        let committee = stellar::get_committee(&config, tender_id)
+            .await?
+            .ok_or(TenderError::CommitteeNotFound)?;
+        let shares = stellar::get_key_shares(&config, tender_id).await?;
+        Ok::<_, Box<dyn std::error::Error>>(CommitteeStatus {
+            quorum: shares.len() as u32 >= committee.threshold,
+            contributors: shares.into_iter().map(|s| s.member).collect(),
+            committee,
+        })
+    }
+    .await;
+
+    match result {
+        Ok(status) => HttpResponse::Ok().json(ApiResponse::success(status)),
+        Err(e) => {
+            log::error!("Failed to fetch committee: {}", e);
+            error_response::<CommitteeStatus>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a submit_key_share transaction for the member's wallet to sign
+pub async fn submit_key_share(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<SubmitKeyShareRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Submitting key share for tender {} from {}", tender_id, req.member);
+
+    if let Err(e) = shamir::parse_share_hex(&req.share) {
+        return HttpResponse::BadRequest().json(ApiResponse::<PreparedTransaction>::error(e.to_string()));
+    }
+
+    match stellar::submit_key_share(&config, tender_id, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared submit_key_share transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare key share: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Reconstruct the tender key from the on-chain shares and open every
+/// sealed bid. Fails until the committee has reached its threshold.
+pub async fn open_bids(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Opening sealed bids for tender {}", tender_id);
+
+    let secret = match committee_secret(&config, tender_id).await {
+        Ok(Ok(secret)) => secret,
+        Ok(Err(reason)) => {
+            return HttpResponse::Conflict().json(ApiResponse::<Vec<OpenedBid>>::error(reason))
+        }
+        Err(e) => {
+            log::error!("Failed to reconstruct tender key: {}", e);
+            return error_response::<Vec<OpenedBid>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let result = async {
+        let mut opened = Vec::new();
+        for bidder in stellar::get_tender_bidders(&config, tender_id).await? {
+            let Some(bid) = stellar::get_bid(&config, tender_id, &bidder).await? else {
+                continue;
+            };
+            let amount = sealed::open_sealed_amount(&bid.encrypted_amount, tender_id, &secret).ok();
+            opened.push(OpenedBid {
+                bidder,
+                amount,
+                revealed_amount: bid.revealed_amount,
+                matches_reveal: amount.zip(bid.revealed_amount).map(|(a, r)| a == r),
+            });
+        }
+        Ok::<_, Box<dyn std::error::Error>>(opened)
+    }
+    .await;
+
+    match result {
+        Ok(opened) => HttpResponse::Ok().json(ApiResponse::success(opened)),
+        Err(e) => {
+            log::error!("Failed to open bids: {}", e);
+            error_response::<Vec<OpenedBid>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// `Ok(Err(reason))` while the shares on chain cannot yet yield the key
+async fn committee_secret(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Result<[u8; 32], String>, Box<dyn std::error::Error>> {
+    let tender = stellar::get_tender(config, tender_id).await?;
+    let committee = stellar::get_committee(config, tender_id)
+        .await?
+        .ok_or(TenderError::CommitteeNotFound)?;
+    let shares = stellar::get_key_shares(config, tender_id).await?;
+
+    if (shares.len() as u32) < committee.threshold {
+        return Ok(Err(format!(
+            "{} of {} key shares submitted",
+            shares.len(),
+            committee.threshold
+        )));
+    }
+
+    let shares = shares
+        .iter()
+        .map(|s| shamir::parse_share_hex(&s.share))
+        .collect::<Result<Vec<_>, _>>()?;
+    let secret = shamir::combine(&shares)?;
+    if hex::encode(sealed::public_key(&secret)) != tender.encryption_key {
+        return Ok(Err("Key shares do not reconstruct the tender key".to_string()));
+    }
+    Ok(Ok(secret))
+}
@@ -0,0 +1,74 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::error::TenderError;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::stellar;
+
+/// Prepare a debar_bidder transaction for the SuperAdmin's wallet to sign
+pub async fn debar_bidder(config: web::Data<Config>, req: web::Json<DebarBidderRequest>) -> HttpResponse {
+    log::info!("Debarring bidder {} until {}", req.bidder, req.end);
+
+    if req.reason.trim().is_empty() {
+        return error_response::<PreparedTransaction>(&TenderError::ReasonRequired, StatusCode::BAD_REQUEST);
+    }
+
+    match stellar::debar_bidder(&config, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared debar_bidder transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare debarment: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a lift_debarment transaction for the SuperAdmin's wallet to sign
+pub async fn lift_debarment(
+    config: web::Data<Config>,
+    path: web::Path<String>,
+    req: web::Json<LiftDebarmentRequest>,
+) -> HttpResponse {
+    let bidder = path.into_inner();
+    log::info!("Lifting debarment of {}", bidder);
+
+    match stellar::lift_debarment(&config, &bidder, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared lift_debarment transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare lifting debarment: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Public list of debarments on record, read from the contract
+pub async fn get_debarments(config: web::Data<Config>, query: web::Query<DebarmentListQuery>) -> HttpResponse {
+    let start = query.start.unwrap_or(0);
+    let limit = query.limit.unwrap_or(50);
+
+    match stellar::get_debarments(&config, start, limit).await {
+        Ok(debarments) => HttpResponse::Ok().json(ApiResponse::success(debarments)),
+        Err(e) => {
+            log::error!("Failed to fetch debarments: {}", e);
+            error_response::<Vec<Debarment>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Whether a bidder is debarred now, with its debarment on record
+pub async fn get_debarment(config: web::Data<Config>, path: web::Path<String>) -> HttpResponse {
+    let bidder = path.into_inner();
+
+    match stellar::get_debarment_status(&config, &bidder).await {
+        Ok(status) => HttpResponse::Ok().json(ApiResponse::success(status)),
+        Err(e) => {
+            log::error!("Failed to fetch debarment: {}", e);
+            error_response::<DebarmentStatus>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::error::TenderError;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::stellar;
+
+/// Prepare a fund_escrow transaction for the creator's wallet to sign
+pub async fn fund_escrow(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<FundEscrowRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Funding escrow for tender {}", tender_id);
+
+    let total = req.milestones.iter().try_fold(0u32, |total, m| total.checked_add(m.percentage));
+    if req.amount <= 0 || total != Some(100) || req.milestones.iter().any(|m| m.percentage == 0) {
+        return error_response::<PreparedTransaction>(&TenderError::InvalidEscrow, StatusCode::BAD_REQUEST);
+    }
+
+    match stellar::fund_escrow(&config, tender_id, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared fund_escrow transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare escrow for tender {}: {}", tender_id, e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Escrow of a tender with the status of each milestone
+pub async fn get_escrow(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching escrow for tender {}", tender_id);
+
+    let result = async {
+        let escrow = stellar::get_escrow(&config, tender_id)
+            .await?
+            .ok_or(TenderError::EscrowNotFound)?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        Ok::<_, Box<dyn std::error::Error>>(EscrowStatus::new(escrow, now))
+    }
+    .await;
+
+    match result {
+        Ok(status) => HttpResponse::Ok().json(ApiResponse::success(status)),
+        Err(e) => {
+            log::error!("Failed to fetch escrow: {}", e);
+            error_response::<EscrowStatus>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a release_milestone transaction for the creator or inspector
+pub async fn release_milestone(
+    config: web::Data<Config>,
+    path: web::Path<(u64, u32)>,
+    req: web::Json<MilestoneActionRequest>,
+) -> HttpResponse {
+    settle_milestone(&config, "release_milestone", path.into_inner(), &req.caller).await
+}
+
+/// Prepare a refund_milestone transaction for the creator
+pub async fn refund_milestone(
+    config: web::Data<Config>,
+    path: web::Path<(u64, u32)>,
+    req: web::Json<MilestoneActionRequest>,
+) -> HttpResponse {
+    settle_milestone(&config, "refund_milestone", path.into_inner(), &req.caller).await
+}
+
+async fn settle_milestone(
+    config: &Config,
+    function: &str,
+    (tender_id, index): (u64, u32),
+    caller: &str,
+) -> HttpResponse {
+    log::info!("Preparing {} of milestone {} for tender {} by {}", function, index, tender_id, caller);
+
+    match stellar::settle_milestone(config, function, tender_id, index, caller).await {
+        Ok(prepared) => {
+            log::info!("Prepared {} transaction {}", function, prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare {}: {}", function, e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
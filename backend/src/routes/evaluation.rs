@@ -0,0 +1,47 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::stellar;
+
+/// Prepare a submit_scores transaction for the evaluator's wallet to sign
+pub async fn submit_scores(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<SubmitScoresRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Submitting scores for tender {} bid {}", tender_id, req.bidder);
+
+    let req = req.into_inner();
+    let result = async {
+        stellar::require_role(&config, &req.evaluator, Role::Evaluator).await?;
+        stellar::submit_scores(&config, tender_id, req).await
+    }
+    .await;
+
+    match result {
+        Ok(prepared) => {
+            log::info!("Prepared submit_scores transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare scores: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Score breakdown of every valid bid, computed when the tender closed
+pub async fn get_scorecards(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching scorecards for tender {}", tender_id);
+
+    match stellar::get_scorecards(&config, tender_id).await {
+        Ok(cards) => HttpResponse::Ok().json(ApiResponse::success(cards)),
+        Err(e) => {
+            log::error!("Failed to fetch scorecards: {}", e);
+            error_response::<Vec<Scorecard>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
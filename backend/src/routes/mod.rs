@@ -1,2 +1,27 @@
 pub mod tender;
-pub mod bid;
\ No newline at end of file
+pub mod bid;
+pub mod tx;
+pub mod committee;
+pub mod opening;
+pub mod escrow;
+pub mod evaluation;
+pub mod role;
+pub mod approval;
+pub mod registry;
+pub mod debarment;
+
+use actix_web::{http::StatusCode, HttpResponse};
+use serde::Serialize;
+use std::error::Error;
+use crate::models::error::TenderError;
+use crate::models::types::ApiResponse;
+
+/// Build an error response. Contract errors carry their own status and
+/// machine-readable code; anything else falls back to `fallback`.
+pub fn error_response<T: Serialize>(e: &(dyn Error + 'static), fallback: StatusCode) -> HttpResponse {
+    match e.downcast_ref::<TenderError>() {
+        Some(err) => HttpResponse::build(err.status())
+            .json(ApiResponse::<T>::error_with_code(err.to_string(), err.code())),
+        None => HttpResponse::build(fallback).json(ApiResponse::<T>::error(e.to_string())),
+    }
+}
@@ -0,0 +1,125 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::error::TenderError;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::{encryption, sealed, stellar};
+
+/// Prepare a publish_opening_key transaction for the creator's wallet to
+/// sign. The secret is checked against the tender's encryption key first,
+/// since a wrong key would leave the bids unopenable.
+pub async fn publish_opening_key(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<PublishOpeningKeyRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Publishing opening key for tender {}", tender_id);
+
+    let tender = match time_lock_tender(&config, tender_id).await {
+        Ok(tender) => tender,
+        Err(e) => {
+            log::error!("Failed to fetch tender {}: {}", tender_id, e);
+            return error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let matches = sealed::parse_key_hex(&req.secret_key)
+        .map(|secret| hex::encode(sealed::public_key(&secret)) == tender.encryption_key)
+        .unwrap_or(false);
+    if !matches {
+        return HttpResponse::BadRequest().json(ApiResponse::<PreparedTransaction>::error(
+            "Secret key does not match the tender's encryption key".to_string(),
+        ));
+    }
+
+    match stellar::publish_opening_key(&config, tender_id, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared publish_opening_key transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare opening key: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Open every unrevealed bid with the published key and prepare the
+/// open_bids batch for the caller's wallet to sign. Bids whose envelope
+/// does not open their commitment are left out and listed as skipped.
+pub async fn open_bids(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<OpenBidsRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Opening bids of time-lock tender {}", tender_id);
+
+    let result = async {
+        time_lock_tender(&config, tender_id).await?;
+        let secret = stellar::get_opening_key(&config, tender_id)
+            .await?
+            .ok_or(TenderError::OpeningKeyNotPublished)?;
+        let secret = sealed::parse_key_hex(&secret)?;
+
+        let mut openings = Vec::new();
+        let mut skipped = Vec::new();
+        for bidder in stellar::get_tender_bidders(&config, tender_id).await? {
+            let Some(bid) = stellar::get_bid(&config, tender_id, &bidder).await? else {
+                continue;
+            };
+            if bid.revealed_amount.is_some() {
+                continue;
+            }
+            match open_commitment(&bid, &secret) {
+                Some(opening) => openings.push(opening),
+                None => skipped.push(bidder),
+            }
+        }
+
+        let transaction = stellar::open_bids(&config, tender_id, &req.caller, &openings).await?;
+        Ok::<_, Box<dyn std::error::Error>>(OpenBidsPreparation {
+            transaction,
+            openings,
+            skipped,
+        })
+    }
+    .await;
+
+    match result {
+        Ok(preparation) => {
+            log::info!(
+                "Prepared open_bids transaction {} ({} opened, {} skipped)",
+                preparation.transaction.hash,
+                preparation.openings.len(),
+                preparation.skipped.len()
+            );
+            HttpResponse::Ok().json(ApiResponse::success(preparation))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare open_bids for tender {}: {}", tender_id, e);
+            error_response::<OpenBidsPreparation>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+async fn time_lock_tender(config: &Config, tender_id: u64) -> Result<Tender, Box<dyn std::error::Error>> {
+    let tender = stellar::get_tender(config, tender_id).await?;
+    if tender.opening_mode != OpeningMode::TimeLock {
+        return Err(TenderError::WrongOpeningMode.into());
+    }
+    Ok(tender)
+}
+
+/// The opening a sealed bid carries, if it matches the stored commitment
+fn open_commitment(bid: &Bid, secret: &[u8; 32]) -> Option<BidOpening> {
+    let (amount, salt) = sealed::open_sealed_bid(&bid.encrypted_amount, bid.tender_id, secret).ok()?;
+    let salt = salt?;
+    let commitment = encryption::compute_commitment(amount, &salt, &bid.bidder, bid.tender_id).ok()?;
+    (hex::encode(commitment) == bid.commitment).then(|| BidOpening {
+        bidder: bid.bidder.clone(),
+        amount,
+        salt: hex::encode(salt),
+    })
+}
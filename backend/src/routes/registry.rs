@@ -0,0 +1,106 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::stellar;
+
+/// Prepare a register_bidder transaction for the bidder's wallet to sign
+pub async fn register_bidder(config: web::Data<Config>, req: web::Json<RegisterBidderRequest>) -> HttpResponse {
+    log::info!("Registering bidder {}", req.bidder);
+
+    match stellar::register_bidder(&config, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared register_bidder transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare bidder registration: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare an approve_bidder transaction for a Verifier's wallet to sign
+pub async fn approve_bidder(
+    config: web::Data<Config>,
+    path: web::Path<String>,
+    req: web::Json<ApproveBidderRequest>,
+) -> HttpResponse {
+    let bidder = path.into_inner();
+    log::info!("Approving bidder {} until {}", bidder, req.expires_at);
+
+    let req = req.into_inner();
+    let result = async {
+        stellar::require_role(&config, &req.verifier, Role::Verifier).await?;
+        stellar::approve_bidder(&config, &bidder, req).await
+    }
+    .await;
+
+    match result {
+        Ok(prepared) => {
+            log::info!("Prepared approve_bidder transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare bidder approval: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a suspend_bidder transaction for a Verifier's wallet to sign
+pub async fn suspend_bidder(
+    config: web::Data<Config>,
+    path: web::Path<String>,
+    req: web::Json<SuspendBidderRequest>,
+) -> HttpResponse {
+    let bidder = path.into_inner();
+    log::info!("Suspending bidder {}", bidder);
+
+    let req = req.into_inner();
+    let result = async {
+        stellar::require_role(&config, &req.verifier, Role::Verifier).await?;
+        stellar::suspend_bidder(&config, &bidder, req).await
+    }
+    .await;
+
+    match result {
+        Ok(prepared) => {
+            log::info!("Prepared suspend_bidder transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare bidder suspension: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// A bidder's registry entry, read from the contract
+pub async fn get_registration(config: web::Data<Config>, path: web::Path<String>) -> HttpResponse {
+    let bidder = path.into_inner();
+
+    match stellar::get_registration(&config, &bidder).await {
+        Ok(Some(registration)) => HttpResponse::Ok().json(ApiResponse::success(registration)),
+        Ok(None) => HttpResponse::NotFound().json(ApiResponse::<Registration>::error(
+            "Bidder is not registered".to_string(),
+        )),
+        Err(e) => {
+            log::error!("Failed to fetch registration: {}", e);
+            error_response::<Registration>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Strikes a bidder collected for bids it never revealed
+pub async fn get_strikes(config: web::Data<Config>, path: web::Path<String>) -> HttpResponse {
+    let bidder = path.into_inner();
+
+    match stellar::get_strikes(&config, &bidder).await {
+        Ok(strikes) => HttpResponse::Ok().json(ApiResponse::success(strikes)),
+        Err(e) => {
+            log::error!("Failed to fetch strikes: {}", e);
+            error_response::<StrikeRecord>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
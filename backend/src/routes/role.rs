@@ -0,0 +1,50 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::stellar;
+
+/// Prepare a grant_role transaction for the SuperAdmin's wallet to sign
+pub async fn grant_role(config: web::Data<Config>, req: web::Json<RoleRequest>) -> HttpResponse {
+    log::info!("Granting {} to {}", req.role.as_str(), req.account);
+
+    match stellar::grant_role(&config, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared grant_role transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare role grant: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a revoke_role transaction for the SuperAdmin's wallet to sign
+pub async fn revoke_role(config: web::Data<Config>, req: web::Json<RoleRequest>) -> HttpResponse {
+    log::info!("Revoking {} from {}", req.role.as_str(), req.account);
+
+    match stellar::revoke_role(&config, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared revoke_role transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare role revocation: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Whether an address holds a role, e.g. `/api/roles/G.../procuring_entity`
+pub async fn has_role(config: web::Data<Config>, path: web::Path<(String, Role)>) -> HttpResponse {
+    let (account, role) = path.into_inner();
+
+    match stellar::has_role(&config, &account, role).await {
+        Ok(held) => HttpResponse::Ok().json(ApiResponse::success(held)),
+        Err(e) => {
+            log::error!("Failed to check role: {}", e);
+            error_response::<bool>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
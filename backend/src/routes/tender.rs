@@ -1,28 +1,32 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{http::StatusCode, web, HttpResponse};
 use crate::config::Config;
+use crate::models::error::TenderError;
 use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::index::{IndexStore, TenderFilter};
 use crate::services::stellar;
 
-/// Get all tenders
-pub async fn get_all_tenders(config: web::Data<Config>) -> HttpResponse {
-    log::info!("Fetching all tenders");
+/// List tenders from the event index with filtering, sorting and
+/// cursor pagination
+pub async fn get_all_tenders(
+    store: web::Data<IndexStore>,
+    query: web::Query<TenderListQuery>,
+) -> HttpResponse {
+    log::info!("Fetching tenders: {:?}", query);
     
-    match stellar::get_all_tenders(&config).await {
-        Ok(tender_ids) => {
-            let mut tenders = Vec::new();
-            
-            for id in tender_ids {
-                if let Ok(tender) = stellar::get_tender(&config, id).await {
-                    tenders.push(tender);
-                }
-            }
-            
-            HttpResponse::Ok().json(ApiResponse::success(tenders))
+    let now = chrono::Utc::now().timestamp() as u64;
+    let filter = match TenderFilter::from_query(&query, now) {
+        Ok(filter) => filter,
+        Err(message) => {
+            return HttpResponse::BadRequest().json(ApiResponse::<TenderPage>::error(message));
         }
+    };
+
+    match store.query_tenders(&filter) {
+        Ok(page) => HttpResponse::Ok().json(ApiResponse::success(page)),
         Err(e) => {
             log::error!("Failed to fetch tenders: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<Vec<Tender>>::error(e.to_string()))
+            error_response::<TenderPage>(&e, StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
@@ -39,13 +43,12 @@ pub async fn get_tender(
         Ok(tender) => HttpResponse::Ok().json(ApiResponse::success(tender)),
         Err(e) => {
             log::error!("Failed to fetch tender {}: {}", tender_id, e);
-            HttpResponse::NotFound()
-                .json(ApiResponse::<Tender>::error(e.to_string()))
+            error_response::<Tender>(e.as_ref(), StatusCode::NOT_FOUND)
         }
     }
 }
 
-/// Create new tender
+/// Prepare a create_tender transaction for the creator's wallet to sign
 pub async fn create_tender(
     config: web::Data<Config>,
     req: web::Json<CreateTenderRequest>,
@@ -55,31 +58,33 @@ pub async fn create_tender(
     // Validate deadlines
     let current_time = chrono::Utc::now().timestamp() as u64;
     if req.deadline <= current_time {
-        return HttpResponse::BadRequest()
-            .json(ApiResponse::<u64>::error("Deadline must be in future".to_string()));
+        return error_response::<PreparedTransaction>(&TenderError::InvalidDeadline, StatusCode::BAD_REQUEST);
     }
     
     if req.reveal_deadline <= req.deadline {
-        return HttpResponse::BadRequest()
-            .json(ApiResponse::<u64>::error(
-                "Reveal deadline must be after bidding deadline".to_string()
-            ));
+        return error_response::<PreparedTransaction>(&TenderError::InvalidRevealDeadline, StatusCode::BAD_REQUEST);
     }
     
-    match stellar::create_tender(&config, req.into_inner()).await {
-        Ok(tender_id) => {
-            log::info!("Tender created with ID: {}", tender_id);
-            HttpResponse::Ok().json(ApiResponse::success(tender_id))
+    let req = req.into_inner();
+    let result = async {
+        stellar::require_role(&config, &req.creator, Role::ProcuringEntity).await?;
+        stellar::create_tender(&config, req).await
+    }
+    .await;
+
+    match result {
+        Ok(prepared) => {
+            log::info!("Prepared create_tender transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
         }
         Err(e) => {
-            log::error!("Failed to create tender: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<u64>::error(e.to_string()))
+            log::error!("Failed to prepare tender creation: {}", e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
-/// Close tender
+/// Prepare a close_tender transaction for the caller's wallet to sign
 pub async fn close_tender(
     config: web::Data<Config>,
     path: web::Path<u64>,
@@ -89,52 +94,212 @@ pub async fn close_tender(
     log::info!("Closing tender {}", tender_id);
     
     match stellar::close_tender(&config, tender_id, &req.caller).await {
-        Ok(_) => {
-            log::info!("Tender {} closed successfully", tender_id);
-            HttpResponse::Ok().json(ApiResponse::success("Tender closed"))
+        Ok(prepared) => {
+            log::info!("Prepared close_tender transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
         }
         Err(e) => {
-            log::error!("Failed to close tender {}: {}", tender_id, e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<String>::error(e.to_string()))
+            log::error!("Failed to prepare closing tender {}: {}", tender_id, e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
-/// Get winner of a tender
+/// Prepare an amend_tender transaction for the creator's wallet to sign.
+/// Terms left out of the request are taken from the current tender.
+pub async fn amend_tender(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<AmendTenderRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Amending tender {}", tender_id);
+
+    let req = req.into_inner();
+    let result = async {
+        let tender = stellar::get_tender(&config, tender_id).await?;
+        let terms = Amendment {
+            description: req.description.unwrap_or(tender.description),
+            ipfs_hash: req.ipfs_hash.unwrap_or(tender.ipfs_hash),
+            deadline: req.deadline.unwrap_or(tender.deadline),
+            reveal_deadline: req.reveal_deadline.unwrap_or(tender.reveal_deadline),
+            amended_at: 0, // set by the contract
+        };
+        stellar::amend_tender(&config, tender_id, &req.creator, &terms).await
+    }
+    .await;
+
+    match result {
+        Ok(prepared) => {
+            log::info!("Prepared amend_tender transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare amending tender {}: {}", tender_id, e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Amendment history of a tender, oldest first, read from the contract
+pub async fn get_amendments(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching amendments of tender {}", tender_id);
+
+    match stellar::get_amendments(&config, tender_id).await {
+        Ok(amendments) => HttpResponse::Ok().json(ApiResponse::success(amendments)),
+        Err(e) => {
+            log::error!("Failed to fetch amendments: {}", e);
+            error_response::<Vec<Amendment>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// How each bidder fared at the reveal, recorded by the contract at close
+pub async fn get_reveal_outcomes(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching reveal outcomes of tender {}", tender_id);
+
+    match stellar::get_reveal_outcomes(&config, tender_id).await {
+        Ok(outcomes) => HttpResponse::Ok().json(ApiResponse::success(outcomes)),
+        Err(e) => {
+            log::error!("Failed to fetch reveal outcomes: {}", e);
+            error_response::<Vec<BidderOutcome>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare a cancel_tender transaction for the creator's or admin's wallet
+/// to sign; the contract refunds every escrowed bond
+pub async fn cancel_tender(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<CancelTenderRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Cancelling tender {}", tender_id);
+
+    if req.reason.trim().is_empty() {
+        return error_response::<PreparedTransaction>(&TenderError::ReasonRequired, StatusCode::BAD_REQUEST);
+    }
+
+    match stellar::cancel_tender(&config, tender_id, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared cancel_tender transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare cancelling tender {}: {}", tender_id, e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Reason and author of a tender's cancellation, read from the contract
+pub async fn get_cancellation(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching cancellation of tender {}", tender_id);
+
+    match stellar::get_cancellation(&config, tender_id).await {
+        Ok(Some(cancellation)) => HttpResponse::Ok().json(ApiResponse::success(cancellation)),
+        Ok(None) => HttpResponse::NotFound()
+            .json(ApiResponse::<Cancellation>::error("Tender has not been cancelled".to_string())),
+        Err(e) => {
+            log::error!("Failed to fetch cancellation: {}", e);
+            error_response::<Cancellation>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Prepare an award_tender transaction, picking one of the bidders tied
+/// under a manual tie-break
+pub async fn award_tender(
+    config: web::Data<Config>,
+    path: web::Path<u64>,
+    req: web::Json<AwardTenderRequest>,
+) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Awarding tender {} to {}", tender_id, req.bidder);
+
+    match stellar::award_tender(&config, tender_id, req.into_inner()).await {
+        Ok(prepared) => {
+            log::info!("Prepared award_tender transaction {}", prepared.hash);
+            HttpResponse::Ok().json(ApiResponse::success(prepared))
+        }
+        Err(e) => {
+            log::error!("Failed to prepare award of tender {}: {}", tender_id, e);
+            error_response::<PreparedTransaction>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Bidders awaiting a manual award; empty once awarded or if there was no tie
+pub async fn get_tied_bidders(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching tied bidders for tender {}", tender_id);
+
+    match stellar::get_tied_bidders(&config, tender_id).await {
+        Ok(bidders) => HttpResponse::Ok().json(ApiResponse::success(bidders)),
+        Err(e) => {
+            log::error!("Failed to fetch tied bidders: {}", e);
+            error_response::<Vec<String>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Per-lot awards of a multi-lot tender, read from the contract
+pub async fn get_lot_winners(config: web::Data<Config>, path: web::Path<u64>) -> HttpResponse {
+    let tender_id = path.into_inner();
+    log::info!("Fetching lot winners for tender {}", tender_id);
+
+    match stellar::get_lot_winners(&config, tender_id).await {
+        Ok(winners) => HttpResponse::Ok().json(ApiResponse::success(winners)),
+        Err(e) => {
+            log::error!("Failed to fetch lot winners: {}", e);
+            error_response::<Vec<Winner>>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Get winner of a tender, from the index or the contract if not indexed yet.
+/// Multi-lot tenders have no single winner; their awards are served by
+/// `get_lot_winners`.
 pub async fn get_winner(
     config: web::Data<Config>,
+    store: web::Data<IndexStore>,
     path: web::Path<u64>,
 ) -> HttpResponse {
     let tender_id = path.into_inner();
     log::info!("Fetching winner for tender {}", tender_id);
     
+    if let Ok(Some(winner)) = store.winner(tender_id) {
+        return HttpResponse::Ok().json(ApiResponse::success(winner));
+    }
+
     match stellar::get_winner(&config, tender_id).await {
         Ok(Some(winner)) => HttpResponse::Ok().json(ApiResponse::success(winner)),
         Ok(None) => HttpResponse::NotFound()
             .json(ApiResponse::<Winner>::error("No winner found".to_string())),
         Err(e) => {
             log::error!("Failed to fetch winner: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<Winner>::error(e.to_string()))
+            error_response::<Winner>(e.as_ref(), StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
-/// Get all bidders for a tender
+/// Get all bidders for a tender, served from the event index
 pub async fn get_bidders(
-    config: web::Data<Config>,
+    store: web::Data<IndexStore>,
     path: web::Path<u64>,
 ) -> HttpResponse {
     let tender_id = path.into_inner();
     log::info!("Fetching bidders for tender {}", tender_id);
     
-    match stellar::get_tender_bidders(&config, tender_id).await {
+    match store.tender_bidders(tender_id) {
         Ok(bidders) => HttpResponse::Ok().json(ApiResponse::success(bidders)),
         Err(e) => {
             log::error!("Failed to fetch bidders: {}", e);
-            HttpResponse::InternalServerError()
-                .json(ApiResponse::<Vec<String>>::error(e.to_string()))
+            error_response::<Vec<String>>(&e, StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
\ No newline at end of file
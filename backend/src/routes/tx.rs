@@ -0,0 +1,24 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use crate::config::Config;
+use crate::models::types::*;
+use crate::routes::error_response;
+use crate::services::stellar;
+
+/// Submit a wallet-signed transaction and wait for its final status
+pub async fn submit_transaction(
+    config: web::Data<Config>,
+    req: web::Json<SubmitTransactionRequest>,
+) -> HttpResponse {
+    log::info!("Submitting signed transaction");
+
+    match stellar::submit_transaction(&config, &req.xdr).await {
+        Ok(result) => {
+            log::info!("Transaction {} succeeded", result.hash);
+            HttpResponse::Ok().json(ApiResponse::success(result))
+        }
+        Err(e) => {
+            log::error!("Failed to submit transaction: {}", e);
+            error_response::<TransactionResult>(e.as_ref(), StatusCode::BAD_REQUEST)
+        }
+    }
+}
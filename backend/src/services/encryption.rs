@@ -1,14 +1,18 @@
 // backend/src/services/encryption.rs
 
 use aes_gcm::{
-    aead::{Aead, KeyInit},
+    aead::{consts::U12, Aead, KeyInit, Payload},
     Aes256Gcm, Nonce,
 };
 use aes_gcm::aes::cipher::InvalidLength;
+use hkdf::Hkdf;
 use rand::rngs::OsRng;
 use rand::RngCore;
 use base64::{engine::general_purpose, Engine as _};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{Limits, ScAddress, ScVal, WriteXdr};
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::error::Error;
 
@@ -52,82 +56,221 @@ impl From<InvalidLength> for EncryptionError {
     }
 }
 
-/// Encrypt amount using AES-256-GCM
-/// Returns (base64(nonce || ciphertext), hex(nonce))
+/// Versioned master keys. New ciphertexts use `current`; older ids are kept
+/// so bids sealed before a rotation still decrypt.
+#[derive(Clone)]
+pub struct KeyRing {
+    current: u32,
+    keys: BTreeMap<u32, String>,
+}
+
+impl KeyRing {
+    pub fn new(current_id: u32, master_key: &str) -> Self {
+        let mut keys = BTreeMap::new();
+        keys.insert(current_id, master_key.to_string());
+        Self {
+            current: current_id,
+            keys,
+        }
+    }
+
+    /// Keep a retired key available for decryption
+    pub fn with_previous(mut self, key_id: u32, master_key: &str) -> Self {
+        self.keys.entry(key_id).or_insert_with(|| master_key.to_string());
+        self
+    }
+
+    pub fn current_id(&self) -> u32 {
+        self.current
+    }
+
+    fn current_key(&self) -> &str {
+        &self.keys[&self.current]
+    }
+}
+
+// Never print key material
+impl fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyRing")
+            .field("current", &self.current)
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Ciphertext header: magic || format version || key id (u32 BE).
+/// The header is authenticated as AES-GCM associated data.
+const MAGIC: [u8; 2] = *b"OT";
+const FORMAT_V1: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+const NONCE_LEN: usize = 12;
+
+/// Encrypt amount using AES-256-GCM under a key derived for `tender_id`
+/// Returns (base64(header || nonce || ciphertext), hex(nonce))
 pub fn encrypt_amount(
     amount: i128,
-    master_key: &str,
+    tender_id: u64,
+    keys: &KeyRing,
 ) -> Result<(String, String), EncryptionError> {
-    // Convert amount to bytes (little-endian to match your original)
-    let amount_bytes = amount.to_le_bytes();
-
-    // Generate 12-byte nonce (GCM standard)
-    let mut nonce_bytes = [0u8; 12];
+    let mut nonce_bytes = [0u8; NONCE_LEN];
     OsRng.fill_bytes(&mut nonce_bytes);
-    let nonce = Nonce::from_slice(&nonce_bytes); // note: this may emit a dependency warning in your toolchain
+    let nonce = Nonce::from(nonce_bytes);
 
-    // Derive 32-byte key (simple truncation/pad as in your original)
-    let key_bytes = derive_key(master_key);
+    let key_id = keys.current_id();
+    let header = header(key_id);
+    let cipher = Aes256Gcm::new_from_slice(&derive_key(keys.current_key(), tender_id)?)?;
+    let ciphertext = cipher.encrypt(
+        &nonce,
+        Payload {
+            msg: &amount.to_le_bytes(),
+            aad: &header,
+        },
+    )?;
 
-    // Construct cipher. new_from_slice returns InvalidLength on wrong key size
-    let cipher = Aes256Gcm::new_from_slice(&key_bytes)?;
-
-    // Encrypt plaintext
-    let ciphertext = cipher.encrypt(nonce, amount_bytes.as_ref())?;
-
-    // Serialize nonce + ciphertext together and base64-encode
-    let mut encrypted_data = nonce_bytes.to_vec();
+    let mut encrypted_data = header.to_vec();
+    encrypted_data.extend_from_slice(&nonce_bytes);
     encrypted_data.extend_from_slice(&ciphertext);
-    let encrypted_base64 = general_purpose::STANDARD.encode(&encrypted_data);
-
-    // Also return nonce as hex string (your "decryption_key")
-    let decryption_key = hex::encode(&nonce_bytes);
 
-    Ok((encrypted_base64, decryption_key))
+    Ok((general_purpose::STANDARD.encode(&encrypted_data), hex::encode(nonce_bytes)))
 }
 
-/// Decrypt amount using AES-256-GCM
-/// `decryption_key` is expected to be hex-encoded nonce (kept to match your API)
+/// Decrypt amount, selecting the master key by the id in the header.
+/// Ciphertexts from before versioned headers are decrypted with the legacy
+/// padded key derivation.
 pub fn decrypt_amount(
     encrypted_base64: &str,
-    _decryption_key: &str,
-    master_key: &str,
+    tender_id: u64,
+    keys: &KeyRing,
 ) -> Result<i128, EncryptionError> {
-    // Base64 decode
     let encrypted_data = general_purpose::STANDARD.decode(encrypted_base64)?;
 
-    // Must contain at least 12 bytes of nonce
-    if encrypted_data.len() < 12 {
-        return Err(EncryptionError::InvalidData("encrypted data too short"));
-    }
+    let plaintext = match parse_header(&encrypted_data) {
+        Some(key_id) => {
+            let master_key = keys
+                .keys
+                .get(&key_id)
+                .ok_or(EncryptionError::InvalidData("unknown key id"))?;
+            let (header, rest) = encrypted_data.split_at(HEADER_LEN);
+            let (nonce_bytes, ciphertext) = split_nonce(rest)?;
+            let cipher = Aes256Gcm::new_from_slice(&derive_key(master_key, tender_id)?)?;
+            cipher.decrypt(
+                &nonce_bytes,
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )?
+        }
+        None => decrypt_legacy(&encrypted_data, keys)?,
+    };
+
+    let amount_bytes: [u8; 16] = plaintext
+        .try_into()
+        .map_err(|_| EncryptionError::InvalidData("invalid plaintext length"))?;
+    Ok(i128::from_le_bytes(amount_bytes))
+}
 
-    // Split nonce and ciphertext
-    let nonce_bytes = &encrypted_data[..12];
-    let ciphertext = &encrypted_data[12..];
-    let nonce = Nonce::from_slice(nonce_bytes);
+/// Re-seal a ciphertext under the current key, e.g. after a rotation.
+/// Returns the new ciphertext in the same format as `encrypt_amount`.
+pub fn reencrypt_amount(
+    encrypted_base64: &str,
+    tender_id: u64,
+    keys: &KeyRing,
+) -> Result<(String, String), EncryptionError> {
+    let amount = decrypt_amount(encrypted_base64, tender_id, keys)?;
+    encrypt_amount(amount, tender_id, keys)
+}
 
-    // Derive key and construct cipher
-    let key_bytes = derive_key(master_key);
-    let cipher = Aes256Gcm::new_from_slice(&key_bytes)?;
+/// Key id a ciphertext was sealed under; `None` for legacy ciphertexts
+pub fn key_id_of(encrypted_base64: &str) -> Result<Option<u32>, EncryptionError> {
+    Ok(parse_header(&general_purpose::STANDARD.decode(encrypted_base64)?))
+}
 
-    // Decrypt
-    let plaintext = cipher.decrypt(nonce, ciphertext)?;
+fn header(key_id: u32) -> [u8; HEADER_LEN] {
+    let mut header = [0u8; HEADER_LEN];
+    header[..2].copy_from_slice(&MAGIC);
+    header[2] = FORMAT_V1;
+    header[3..].copy_from_slice(&key_id.to_be_bytes());
+    header
+}
 
-    // Expect 16 bytes for i128
-    if plaintext.len() != 16 {
-        return Err(EncryptionError::InvalidData("invalid plaintext length"));
+fn parse_header(data: &[u8]) -> Option<u32> {
+    if data.len() < HEADER_LEN + NONCE_LEN || data[..2] != MAGIC || data[2] != FORMAT_V1 {
+        return None;
     }
+    Some(u32::from_be_bytes(data[3..HEADER_LEN].try_into().ok()?))
+}
 
-    let mut amount_bytes = [0u8; 16];
-    amount_bytes.copy_from_slice(&plaintext);
-    let amount = i128::from_le_bytes(amount_bytes);
+fn split_nonce(data: &[u8]) -> Result<(Nonce<U12>, &[u8]), EncryptionError> {
+    if data.len() < NONCE_LEN {
+        return Err(EncryptionError::InvalidData("encrypted data too short"));
+    }
+    let (nonce_bytes, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce_bytes: [u8; NONCE_LEN] = nonce_bytes
+        .try_into()
+        .map_err(|_| EncryptionError::InvalidData("invalid nonce length"))?;
+    Ok((Nonce::from(nonce_bytes), ciphertext))
+}
 
-    Ok(amount)
+/// Pre-HKDF format: base64(nonce || ciphertext) under the padded master key.
+/// Tried against every key in the ring since it carries no key id.
+fn decrypt_legacy(data: &[u8], keys: &KeyRing) -> Result<Vec<u8>, EncryptionError> {
+    let (nonce, ciphertext) = split_nonce(data)?;
+    let mut last_error = EncryptionError::InvalidData("no keys configured");
+    for master_key in keys.keys.values().rev() {
+        let cipher = Aes256Gcm::new_from_slice(&legacy_key(master_key))?;
+        match cipher.decrypt(&nonce, ciphertext) {
+            Ok(plaintext) => return Ok(plaintext),
+            Err(e) => last_error = e.into(),
+        }
+    }
+    Err(last_error)
+}
+
+/// Generate a random 32-byte salt for a bid commitment
+pub fn generate_salt() -> [u8; 32] {
+    let mut salt = [0u8; 32];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+/// Compute the sealed-bid commitment checked by the contract's `reveal_bid`:
+/// sha256(amount as i128 BE || salt || bidder as ScVal XDR || tender_id as u64 BE)
+pub fn compute_commitment(
+    amount: i128,
+    salt: &[u8; 32],
+    bidder: &str,
+    tender_id: u64,
+) -> Result<[u8; 32], EncryptionError> {
+    let address: ScAddress = bidder
+        .parse()
+        .map_err(|_| EncryptionError::InvalidData("invalid bidder address"))?;
+    let address_xdr = ScVal::Address(address)
+        .to_xdr(Limits::none())
+        .map_err(|_| EncryptionError::InvalidData("failed to encode bidder address"))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(amount.to_be_bytes());
+    hasher.update(salt);
+    hasher.update(&address_xdr);
+    hasher.update(tender_id.to_be_bytes());
+    Ok(hasher.finalize().into())
+}
+
+/// HKDF-SHA256 of the master key, bound to one tender so a key recovered
+/// for one tender says nothing about another
+fn derive_key(master_key: &str, tender_id: u64) -> Result<[u8; 32], EncryptionError> {
+    let info = format!("opentender/bid-amount/v1/tender/{}", tender_id);
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, master_key.as_bytes())
+        .expand(info.as_bytes(), &mut key)
+        .map_err(|_| EncryptionError::InvalidData("key derivation failed"))?;
+    Ok(key)
 }
 
-/// Simple 32-byte key derivation (pad/truncate).
-/// For production use, prefer a proper KDF (HKDF / Argon2 / PBKDF2) or SHA-256.
-fn derive_key(master_key: &str) -> [u8; 32] {
+/// Original 32-byte key derivation (pad/truncate); only used to read old bids
+fn legacy_key(master_key: &str) -> [u8; 32] {
     let mut key = [0u8; 32];
     let bytes = master_key.as_bytes();
     let len = bytes.len().min(32);
@@ -139,14 +282,88 @@ fn derive_key(master_key: &str) -> [u8; 32] {
 mod tests {
     use super::*;
 
+    fn keys() -> KeyRing {
+        KeyRing::new(1, "test-master-key-32-chars-long!!")
+    }
+
     #[test]
     fn test_encrypt_decrypt() {
         let amount = 150_000i128;
-        let master_key = "test-master-key-32-chars-long!!";
 
-        let (encrypted, _nonce_hex) = encrypt_amount(amount, master_key).unwrap();
-        let decrypted = decrypt_amount(&encrypted, &_nonce_hex, master_key).unwrap();
+        let (encrypted, _nonce_hex) = encrypt_amount(amount, 7, &keys()).unwrap();
+        let decrypted = decrypt_amount(&encrypted, 7, &keys()).unwrap();
 
         assert_eq!(amount, decrypted);
+        assert_eq!(key_id_of(&encrypted).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_key_is_bound_to_tender() {
+        let (encrypted, _) = encrypt_amount(150_000, 7, &keys()).unwrap();
+        assert!(decrypt_amount(&encrypted, 8, &keys()).is_err());
+        assert_ne!(derive_key("k", 1).unwrap(), derive_key("k", 2).unwrap());
+    }
+
+    #[test]
+    fn test_header_is_authenticated() {
+        let (encrypted, _) = encrypt_amount(150_000, 7, &keys()).unwrap();
+        let rotated = keys().with_previous(2, "test-master-key-32-chars-long!!");
+
+        // Relabel the key id: same key material, but the header no longer matches
+        let mut data = general_purpose::STANDARD.decode(&encrypted).unwrap();
+        data[3..HEADER_LEN].copy_from_slice(&2u32.to_be_bytes());
+        let tampered = general_purpose::STANDARD.encode(&data);
+        assert!(decrypt_amount(&tampered, 7, &rotated).is_err());
+    }
+
+    #[test]
+    fn test_rotation_keeps_old_bids_readable() {
+        let old = KeyRing::new(1, "old-master-key-with-enough-entropy!");
+        let (sealed_v1, _) = encrypt_amount(90_000, 3, &old).unwrap();
+
+        let rotated = KeyRing::new(2, "new-master-key-with-enough-entropy!")
+            .with_previous(1, "old-master-key-with-enough-entropy!");
+        assert_eq!(decrypt_amount(&sealed_v1, 3, &rotated).unwrap(), 90_000);
+
+        let (sealed_v2, _) = reencrypt_amount(&sealed_v1, 3, &rotated).unwrap();
+        assert_eq!(key_id_of(&sealed_v2).unwrap(), Some(2));
+
+        // Once the old key is dropped, only the re-encrypted copy still opens
+        let retired = KeyRing::new(2, "new-master-key-with-enough-entropy!");
+        assert_eq!(decrypt_amount(&sealed_v2, 3, &retired).unwrap(), 90_000);
+        assert!(decrypt_amount(&sealed_v1, 3, &retired).is_err());
+    }
+
+    #[test]
+    fn test_decrypts_legacy_ciphertext() {
+        let master_key = "test-master-key-32-chars-long!!";
+        let nonce = [9u8; NONCE_LEN];
+        let cipher = Aes256Gcm::new_from_slice(&legacy_key(master_key)).unwrap();
+        let ciphertext = cipher.encrypt(&Nonce::from(nonce), 42i128.to_le_bytes().as_ref()).unwrap();
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&ciphertext);
+        let legacy = general_purpose::STANDARD.encode(&data);
+
+        assert_eq!(key_id_of(&legacy).unwrap(), None);
+        assert_eq!(decrypt_amount(&legacy, 1, &keys()).unwrap(), 42);
+        let (upgraded, _) = reencrypt_amount(&legacy, 1, &keys()).unwrap();
+        assert_eq!(key_id_of(&upgraded).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_commitment_matches_contract() {
+        // Same vector as `test_commitment_known_vector` in the contract
+        let commitment = compute_commitment(
+            150_000,
+            &[0x11; 32],
+            "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M",
+            42,
+        )
+        .unwrap();
+
+        assert_eq!(
+            hex::encode(commitment),
+            "1c9f7654a471c94cfd9edf0f7ffeb4dc0a159cce27cf844475237af786bc9d2b"
+        );
     }
 }
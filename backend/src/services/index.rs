@@ -0,0 +1,944 @@
+//! SQLite-backed index of contract state, rebuilt from contract events.
+//!
+//! Amounts are `i128` on chain and are stored as decimal TEXT so nothing is
+//! lost to SQLite's 64-bit integers. Where an amount must be compared in SQL
+//! it is also stored as an order-preserving BLOB (see `amount_key`).
+
+use crate::models::types::{
+    Amendment, OpeningMode, SortOrder, Tender, TenderListQuery, TenderPage, TenderSortField, TenderStatus, TieBreak,
+    Winner,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// A state change decoded from one contract event
+#[derive(Debug, Clone)]
+pub enum IndexUpdate {
+    TenderCreated(Box<Tender>),
+    BidSubmitted { tender_id: u64, bidder: String },
+    BidWithdrawn { tender_id: u64, bidder: String },
+    BidRevealed { tender_id: u64, bidder: String, amount: i128 },
+    WinnerSelected(Winner),
+    TenderClosed { tender_id: u64 },
+    TenderCancelled { tender_id: u64 },
+    TenderAmended { tender_id: u64, amendment: Amendment },
+    TenderPublished { tender_id: u64 },
+    AwardPending { tender_id: u64 },
+    AwardFinalized { tender_id: u64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexedEvent {
+    /// RPC event id, unique per event; used to make replays idempotent
+    pub id: String,
+    pub ledger: u32,
+    pub update: IndexUpdate,
+}
+
+pub struct IndexStore {
+    conn: Mutex<Connection>,
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS tenders (
+        id INTEGER PRIMARY KEY,
+        creator TEXT NOT NULL,
+        title TEXT NOT NULL,
+        description TEXT NOT NULL,
+        ipfs_hash TEXT NOT NULL,
+        deadline INTEGER NOT NULL,
+        reveal_deadline INTEGER NOT NULL,
+        min_bid TEXT NOT NULL,
+        is_closed INTEGER NOT NULL DEFAULT 0,
+        created_at INTEGER NOT NULL,
+        ledger INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS bids (
+        tender_id INTEGER NOT NULL,
+        bidder TEXT NOT NULL,
+        revealed_amount TEXT,
+        ledger INTEGER NOT NULL,
+        PRIMARY KEY (tender_id, bidder)
+    );
+    CREATE TABLE IF NOT EXISTS winners (
+        tender_id INTEGER PRIMARY KEY,
+        bidder TEXT NOT NULL,
+        amount TEXT NOT NULL,
+        selected_at INTEGER NOT NULL,
+        ledger INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS processed_events (
+        id TEXT PRIMARY KEY
+    );
+    CREATE TABLE IF NOT EXISTS indexer_state (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        cursor TEXT NOT NULL
+    );
+";
+
+/// Bump when adding a step to `migrate`
+const SCHEMA_VERSION: u32 = 13;
+
+fn migrate(conn: &Connection) -> rusqlite::Result<()> {
+    let version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    if version < 1 {
+        conn.execute_batch(SCHEMA)?;
+    }
+    if version < 2 {
+        conn.execute_batch(
+            "ALTER TABLE tenders ADD COLUMN min_bid_key BLOB;
+             CREATE INDEX IF NOT EXISTS tenders_deadline ON tenders (deadline);
+             CREATE INDEX IF NOT EXISTS tenders_creator ON tenders (creator);",
+        )?;
+        let rows: Vec<(i64, String)> = conn
+            .prepare("SELECT id, min_bid FROM tenders")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (id, min_bid) in rows {
+            conn.execute(
+                "UPDATE tenders SET min_bid_key = ?2 WHERE id = ?1",
+                params![id, amount_key(parse_amount(min_bid))],
+            )?;
+        }
+    }
+    if version < 3 {
+        // Tenders indexed before sealed bids have no authority key
+        conn.execute_batch("ALTER TABLE tenders ADD COLUMN encryption_key TEXT NOT NULL DEFAULT '';")?;
+    }
+    if version < 4 {
+        conn.execute_batch(
+            "ALTER TABLE tenders ADD COLUMN opening_mode TEXT NOT NULL DEFAULT 'BidderReveal';",
+        )?;
+    }
+    if version < 5 {
+        // Bond terms as JSON; NULL when the tender takes no bond
+        conn.execute_batch("ALTER TABLE tenders ADD COLUMN bond TEXT;")?;
+    }
+    if version < 6 {
+        // Evaluation method as JSON; NULL reads as lowest price
+        conn.execute_batch("ALTER TABLE tenders ADD COLUMN evaluation TEXT;")?;
+    }
+    if version < 7 {
+        // NULL for winners indexed before second-price awards: payable = amount
+        conn.execute_batch("ALTER TABLE winners ADD COLUMN payable TEXT;")?;
+    }
+    if version < 8 {
+        conn.execute_batch(
+            "ALTER TABLE tenders ADD COLUMN tie_break TEXT NOT NULL DEFAULT 'EarliestCommit';",
+        )?;
+    }
+    if version < 9 {
+        // Lots as JSON; tenders indexed before lots are single-lot
+        conn.execute_batch(
+            "ALTER TABLE tenders ADD COLUMN lots TEXT NOT NULL DEFAULT '[]';
+             ALTER TABLE tenders ADD COLUMN max_lots_per_bidder INTEGER NOT NULL DEFAULT 0;",
+        )?;
+    }
+    if version < 10 {
+        conn.execute_batch("ALTER TABLE tenders ADD COLUMN is_cancelled INTEGER NOT NULL DEFAULT 0;")?;
+    }
+    if version < 11 {
+        conn.execute_batch(
+            "ALTER TABLE tenders ADD COLUMN is_draft INTEGER NOT NULL DEFAULT 0;
+             ALTER TABLE tenders ADD COLUMN award_pending INTEGER NOT NULL DEFAULT 0;",
+        )?;
+    }
+    if version < 12 {
+        // Eligibility as JSON; NULL reads as open to all
+        conn.execute_batch("ALTER TABLE tenders ADD COLUMN eligibility TEXT;")?;
+    }
+    if version < 13 {
+        conn.execute_batch("ALTER TABLE tenders ADD COLUMN max_strikes INTEGER NOT NULL DEFAULT 0;")?;
+    }
+
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+}
+
+impl IndexStore {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        migrate(&conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Paging cursor to resume `getEvents` from, if anything was indexed yet
+    pub fn cursor(&self) -> rusqlite::Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT cursor FROM indexer_state WHERE id = 1", [], |row| row.get(0))
+            .optional()
+    }
+
+    /// Apply a batch of events and advance the cursor atomically
+    pub fn apply(&self, events: &[IndexedEvent], cursor: Option<&str>) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        for event in events {
+            let fresh = tx.execute(
+                "INSERT OR IGNORE INTO processed_events (id) VALUES (?1)",
+                params![event.id],
+            )?;
+            if fresh == 0 {
+                continue;
+            }
+
+            match &event.update {
+                IndexUpdate::TenderCreated(t) => {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO tenders (id, creator, title, description, ipfs_hash,
+                            deadline, reveal_deadline, min_bid, is_closed, created_at, ledger,
+                            min_bid_key, encryption_key, opening_mode, bond, evaluation, tie_break,
+                            lots, max_lots_per_bidder, is_cancelled, is_draft, award_pending, eligibility,
+                            max_strikes)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                            ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+                        params![
+                            t.id as i64,
+                            t.creator,
+                            t.title,
+                            t.description,
+                            t.ipfs_hash,
+                            t.deadline as i64,
+                            t.reveal_deadline as i64,
+                            t.min_bid.to_string(),
+                            t.is_closed,
+                            t.created_at as i64,
+                            event.ledger,
+                            amount_key(t.min_bid),
+                            t.encryption_key,
+                            t.opening_mode.as_str(),
+                            t.bond.as_ref().map(|b| serde_json::to_string(b).expect("bond serializes")),
+                            serde_json::to_string(&t.evaluation).expect("evaluation serializes"),
+                            t.tie_break.as_str(),
+                            serde_json::to_string(&t.lots).expect("lots serialize"),
+                            t.max_lots_per_bidder,
+                            t.is_cancelled,
+                            t.is_draft,
+                            t.award_pending,
+                            serde_json::to_string(&t.eligibility).expect("eligibility serializes"),
+                            t.max_strikes,
+                        ],
+                    )?;
+                }
+                IndexUpdate::BidSubmitted { tender_id, bidder } => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO bids (tender_id, bidder, ledger) VALUES (?1, ?2, ?3)",
+                        params![*tender_id as i64, bidder, event.ledger],
+                    )?;
+                }
+                IndexUpdate::BidWithdrawn { tender_id, bidder } => {
+                    tx.execute(
+                        "DELETE FROM bids WHERE tender_id = ?1 AND bidder = ?2",
+                        params![*tender_id as i64, bidder],
+                    )?;
+                }
+                IndexUpdate::BidRevealed { tender_id, bidder, amount } => {
+                    tx.execute(
+                        "UPDATE bids SET revealed_amount = ?3 WHERE tender_id = ?1 AND bidder = ?2",
+                        params![*tender_id as i64, bidder, amount.to_string()],
+                    )?;
+                }
+                IndexUpdate::WinnerSelected(w) => {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO winners (tender_id, bidder, amount, selected_at, ledger, payable)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            w.tender_id as i64,
+                            w.bidder,
+                            w.amount.to_string(),
+                            w.selected_at as i64,
+                            event.ledger,
+                            w.payable.to_string(),
+                        ],
+                    )?;
+                }
+                IndexUpdate::TenderClosed { tender_id } => {
+                    tx.execute(
+                        "UPDATE tenders SET is_closed = 1 WHERE id = ?1",
+                        params![*tender_id as i64],
+                    )?;
+                }
+                IndexUpdate::TenderAmended { tender_id, amendment } => {
+                    tx.execute(
+                        "UPDATE tenders SET description = ?2, ipfs_hash = ?3, deadline = ?4, reveal_deadline = ?5
+                         WHERE id = ?1",
+                        params![
+                            *tender_id as i64,
+                            amendment.description,
+                            amendment.ipfs_hash,
+                            amendment.deadline as i64,
+                            amendment.reveal_deadline as i64,
+                        ],
+                    )?;
+                }
+                IndexUpdate::TenderPublished { tender_id } => {
+                    tx.execute("UPDATE tenders SET is_draft = 0 WHERE id = ?1", params![*tender_id as i64])?;
+                }
+                IndexUpdate::AwardPending { tender_id } => {
+                    tx.execute("UPDATE tenders SET award_pending = 1 WHERE id = ?1", params![*tender_id as i64])?;
+                }
+                IndexUpdate::AwardFinalized { tender_id } => {
+                    tx.execute("UPDATE tenders SET award_pending = 0 WHERE id = ?1", params![*tender_id as i64])?;
+                }
+                IndexUpdate::TenderCancelled { tender_id } => {
+                    tx.execute(
+                        "UPDATE tenders SET is_closed = 1, is_cancelled = 1 WHERE id = ?1",
+                        params![*tender_id as i64],
+                    )?;
+                }
+            }
+        }
+
+        if let Some(cursor) = cursor {
+            tx.execute(
+                "INSERT INTO indexer_state (id, cursor) VALUES (1, ?1)
+                 ON CONFLICT(id) DO UPDATE SET cursor = excluded.cursor",
+                params![cursor],
+            )?;
+        }
+        tx.commit()
+    }
+
+    #[cfg(test)]
+    pub fn list_tenders(&self) -> rusqlite::Result<Vec<Tender>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM tenders ORDER BY id", TENDER_COLUMNS))?;
+        let rows = stmt.query_map([], tender_from_row)?;
+        rows.collect()
+    }
+
+    /// One page of tenders matching `filter`, using keyset pagination on
+    /// (sort column, id) so pages stay stable while new tenders are indexed
+    pub fn query_tenders(&self, filter: &TenderFilter) -> rusqlite::Result<TenderPage> {
+        let mut clauses: Vec<&str> = Vec::new();
+        let mut args: Vec<Value> = Vec::new();
+
+        match filter.status {
+            Some(TenderStatus::Open) => {
+                clauses.push("is_closed = 0 AND is_draft = 0 AND deadline > ?");
+                args.push(Value::Integer(filter.now as i64));
+            }
+            Some(TenderStatus::Reveal) => {
+                clauses.push("is_closed = 0 AND is_draft = 0 AND deadline <= ?");
+                args.push(Value::Integer(filter.now as i64));
+            }
+            Some(TenderStatus::Closed) => clauses.push("is_closed = 1 AND is_cancelled = 0"),
+            Some(TenderStatus::Cancelled) => clauses.push("is_cancelled = 1"),
+            Some(TenderStatus::Draft) => clauses.push("is_closed = 0 AND is_draft = 1"),
+            None => {}
+        }
+        if let Some(creator) = &filter.creator {
+            clauses.push("creator = ?");
+            args.push(Value::Text(creator.clone()));
+        }
+        if let Some(from) = filter.deadline_from {
+            clauses.push("deadline >= ?");
+            args.push(Value::Integer(from as i64));
+        }
+        if let Some(to) = filter.deadline_to {
+            clauses.push("deadline <= ?");
+            args.push(Value::Integer(to as i64));
+        }
+        if let Some(from) = filter.min_bid_from {
+            clauses.push("min_bid_key >= ?");
+            args.push(Value::Blob(amount_key(from)));
+        }
+        if let Some(to) = filter.min_bid_to {
+            clauses.push("min_bid_key <= ?");
+            args.push(Value::Blob(amount_key(to)));
+        }
+        if let Some(search) = &filter.search {
+            clauses.push("title LIKE ? ESCAPE '\\'");
+            args.push(Value::Text(format!("%{}%", escape_like(search))));
+        }
+
+        let column = sort_column(filter.sort);
+        let (cmp, dir) = match filter.order {
+            SortOrder::Asc => (">", "ASC"),
+            SortOrder::Desc => ("<", "DESC"),
+        };
+        let keyset = format!("({column} {cmp} ? OR ({column} = ? AND id {cmp} ?))");
+        if let Some(after) = &filter.after {
+            clauses.push(&keyset);
+            args.push(after.key.to_sql());
+            args.push(after.key.to_sql());
+            args.push(Value::Integer(after.id as i64));
+        }
+
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        // One extra row tells us whether another page exists
+        args.push(Value::Integer(filter.limit as i64 + 1));
+        let sql = format!(
+            "SELECT {TENDER_COLUMNS} FROM tenders {where_clause}
+             ORDER BY {column} {dir}, id {dir} LIMIT ?"
+        );
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+        let mut tenders = stmt
+            .query_map(params_from_iter(args), tender_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let next_cursor = if tenders.len() > filter.limit as usize {
+            tenders.truncate(filter.limit as usize);
+            tenders.last().map(|last| {
+                PageCursor {
+                    key: SortKey::of(last, filter.sort),
+                    id: last.id,
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+        Ok(TenderPage { tenders, next_cursor })
+    }
+
+    pub fn tender_bidders(&self, tender_id: u64) -> rusqlite::Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT bidder FROM bids WHERE tender_id = ?1 ORDER BY ledger, bidder")?;
+        let rows = stmt.query_map(params![tender_id as i64], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    pub fn winner(&self, tender_id: u64) -> rusqlite::Result<Option<Winner>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT tender_id, bidder, amount, selected_at, payable FROM winners WHERE tender_id = ?1",
+            params![tender_id as i64],
+            |row| {
+                let amount = parse_amount(row.get(2)?);
+                Ok(Winner {
+                    tender_id: row.get::<_, i64>(0)? as u64,
+                    bidder: row.get(1)?,
+                    amount,
+                    payable: row.get::<_, Option<String>>(4)?.map_or(amount, parse_amount),
+                    selected_at: row.get::<_, i64>(3)? as u64,
+                    lot: None,
+                })
+            },
+        )
+        .optional()
+    }
+}
+
+fn parse_amount(value: String) -> i128 {
+    value.parse().unwrap_or_default()
+}
+
+/// Big-endian bytes with the sign bit flipped, so SQLite's bytewise BLOB
+/// comparison orders amounts numerically
+fn amount_key(value: i128) -> Vec<u8> {
+    ((value as u128) ^ (1 << 127)).to_be_bytes().to_vec()
+}
+
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+const TENDER_COLUMNS: &str = "id, creator, title, description, ipfs_hash, deadline, \
+    reveal_deadline, min_bid, is_closed, created_at, encryption_key, opening_mode, bond, evaluation, tie_break, \
+    lots, max_lots_per_bidder, is_cancelled, is_draft, award_pending, eligibility, max_strikes";
+
+fn tender_from_row(row: &rusqlite::Row) -> rusqlite::Result<Tender> {
+    Ok(Tender {
+        id: row.get::<_, i64>(0)? as u64,
+        creator: row.get(1)?,
+        title: row.get(2)?,
+        description: row.get(3)?,
+        ipfs_hash: row.get(4)?,
+        deadline: row.get::<_, i64>(5)? as u64,
+        reveal_deadline: row.get::<_, i64>(6)? as u64,
+        min_bid: parse_amount(row.get(7)?),
+        is_closed: row.get(8)?,
+        created_at: row.get::<_, i64>(9)? as u64,
+        encryption_key: row.get(10)?,
+        opening_mode: OpeningMode::parse(&row.get::<_, String>(11)?).unwrap_or_default(),
+        bond: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        evaluation: row
+            .get::<_, Option<String>>(13)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        tie_break: TieBreak::parse(&row.get::<_, String>(14)?).unwrap_or_default(),
+        lots: serde_json::from_str(&row.get::<_, String>(15)?).unwrap_or_default(),
+        max_lots_per_bidder: row.get(16)?,
+        is_cancelled: row.get(17)?,
+        is_draft: row.get(18)?,
+        award_pending: row.get(19)?,
+        eligibility: row
+            .get::<_, Option<String>>(20)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        max_strikes: row.get(21)?,
+    })
+}
+
+fn sort_column(field: TenderSortField) -> &'static str {
+    match field {
+        TenderSortField::Id => "id",
+        TenderSortField::Deadline => "deadline",
+        TenderSortField::RevealDeadline => "reveal_deadline",
+        TenderSortField::CreatedAt => "created_at",
+        TenderSortField::MinBid => "min_bid_key",
+        TenderSortField::Title => "title",
+    }
+}
+
+/// Validated form of `TenderListQuery`
+#[derive(Debug)]
+pub struct TenderFilter {
+    pub status: Option<TenderStatus>,
+    pub creator: Option<String>,
+    pub deadline_from: Option<u64>,
+    pub deadline_to: Option<u64>,
+    pub min_bid_from: Option<i128>,
+    pub min_bid_to: Option<i128>,
+    pub search: Option<String>,
+    pub sort: TenderSortField,
+    pub order: SortOrder,
+    pub after: Option<PageCursor>,
+    pub limit: u32,
+    /// Ledger time used to tell open tenders from ones in reveal
+    pub now: u64,
+}
+
+impl TenderFilter {
+    pub fn from_query(query: &TenderListQuery, now: u64) -> Result<Self, String> {
+        let amount = |value: &Option<String>, name: &str| {
+            value
+                .as_deref()
+                .map(|v| v.parse::<i128>().map_err(|_| format!("{} must be an integer", name)))
+                .transpose()
+        };
+        let after = query
+            .cursor
+            .as_deref()
+            .map(|c| PageCursor::decode(c, query.sort).ok_or_else(|| "Invalid cursor".to_string()))
+            .transpose()?;
+
+        Ok(Self {
+            status: query.status,
+            creator: query.creator.clone(),
+            deadline_from: query.deadline_from,
+            deadline_to: query.deadline_to,
+            min_bid_from: amount(&query.min_bid_from, "min_bid_from")?,
+            min_bid_to: amount(&query.min_bid_to, "min_bid_to")?,
+            search: query.q.clone().filter(|q| !q.trim().is_empty()),
+            sort: query.sort,
+            order: query.order,
+            after,
+            limit: query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+            now,
+        })
+    }
+}
+
+/// Sort-column value of the last row on a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    Int(i64),
+    Text(String),
+    Amount(String),
+}
+
+impl SortKey {
+    fn of(tender: &Tender, field: TenderSortField) -> Self {
+        match field {
+            TenderSortField::Id => SortKey::Int(tender.id as i64),
+            TenderSortField::Deadline => SortKey::Int(tender.deadline as i64),
+            TenderSortField::RevealDeadline => SortKey::Int(tender.reveal_deadline as i64),
+            TenderSortField::CreatedAt => SortKey::Int(tender.created_at as i64),
+            TenderSortField::MinBid => SortKey::Amount(tender.min_bid.to_string()),
+            TenderSortField::Title => SortKey::Text(tender.title.clone()),
+        }
+    }
+
+    fn matches(&self, field: TenderSortField) -> bool {
+        match self {
+            SortKey::Int(_) => !matches!(field, TenderSortField::MinBid | TenderSortField::Title),
+            SortKey::Text(_) => field == TenderSortField::Title,
+            SortKey::Amount(v) => field == TenderSortField::MinBid && v.parse::<i128>().is_ok(),
+        }
+    }
+
+    fn to_sql(&self) -> Value {
+        match self {
+            SortKey::Int(v) => Value::Integer(*v),
+            SortKey::Text(v) => Value::Text(v.clone()),
+            SortKey::Amount(v) => Value::Blob(amount_key(parse_amount(v.clone()))),
+        }
+    }
+}
+
+/// Opaque keyset cursor handed to clients as `next_cursor`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageCursor {
+    pub key: SortKey,
+    pub id: u64,
+}
+
+impl PageCursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// A cursor is only valid for the sort field it was issued under
+    pub fn decode(cursor: &str, sort: TenderSortField) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        let cursor: PageCursor = serde_json::from_slice(&bytes).ok()?;
+        cursor.key.matches(sort).then_some(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::types::{Eligibility, Evaluation};
+
+    const CREATOR: &str = "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M";
+
+    fn tender(id: u64) -> Tender {
+        Tender {
+            id,
+            creator: CREATOR.to_string(),
+            title: format!("Tender {}", id),
+            description: "desc".to_string(),
+            ipfs_hash: "QmHash".to_string(),
+            deadline: 1_000,
+            reveal_deadline: 2_000,
+            min_bid: 100_000,
+            is_closed: false,
+            created_at: 500,
+            encryption_key: "0707070707070707070707070707070707070707070707070707070707070707".to_string(),
+            opening_mode: OpeningMode::BidderReveal,
+            bond: None,
+            evaluation: Evaluation::LowestPrice,
+            tie_break: TieBreak::EarliestCommit,
+            lots: Vec::new(),
+            max_lots_per_bidder: 0,
+            eligibility: Eligibility::Open,
+            max_strikes: 0,
+            is_cancelled: false,
+            is_draft: false,
+            award_pending: false,
+        }
+    }
+
+    fn event(id: &str, ledger: u32, update: IndexUpdate) -> IndexedEvent {
+        IndexedEvent {
+            id: id.to_string(),
+            ledger,
+            update,
+        }
+    }
+
+    #[test]
+    fn test_apply_full_lifecycle() {
+        let store = IndexStore::open_in_memory().unwrap();
+        let events = vec![
+            event("1", 10, IndexUpdate::TenderCreated(Box::new(tender(1)))),
+            event("2", 11, IndexUpdate::BidSubmitted { tender_id: 1, bidder: "A".to_string() }),
+            event("3", 12, IndexUpdate::BidSubmitted { tender_id: 1, bidder: "B".to_string() }),
+            event("4", 20, IndexUpdate::BidRevealed { tender_id: 1, bidder: "B".to_string(), amount: 120_000 }),
+            event(
+                "5",
+                30,
+                IndexUpdate::WinnerSelected(Winner {
+                    tender_id: 1,
+                    bidder: "B".to_string(),
+                    amount: 120_000,
+                    payable: 120_000,
+                    selected_at: 3_000,
+                    lot: None,
+                }),
+            ),
+            event("6", 30, IndexUpdate::TenderClosed { tender_id: 1 }),
+        ];
+        store.apply(&events, Some("cursor-6")).unwrap();
+
+        let tenders = store.list_tenders().unwrap();
+        assert_eq!(tenders.len(), 1);
+        assert!(tenders[0].is_closed);
+        assert_eq!(tenders[0].encryption_key, tender(1).encryption_key);
+        assert_eq!(tenders[0].min_bid, 100_000);
+        assert_eq!(store.tender_bidders(1).unwrap(), vec!["A", "B"]);
+        assert_eq!(store.winner(1).unwrap().unwrap().amount, 120_000);
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("cursor-6"));
+    }
+
+    #[test]
+    fn test_withdrawal_and_cancellation() {
+        let store = IndexStore::open_in_memory().unwrap();
+        let events = vec![
+            event("1", 10, IndexUpdate::TenderCreated(Box::new(tender(1)))),
+            event("2", 10, IndexUpdate::TenderCreated(Box::new(tender(2)))),
+            event("3", 11, IndexUpdate::BidSubmitted { tender_id: 1, bidder: "A".to_string() }),
+            event("4", 12, IndexUpdate::BidSubmitted { tender_id: 1, bidder: "B".to_string() }),
+            event("5", 13, IndexUpdate::BidWithdrawn { tender_id: 1, bidder: "A".to_string() }),
+            event("6", 14, IndexUpdate::TenderCancelled { tender_id: 2 }),
+        ];
+        store.apply(&events, Some("cursor-6")).unwrap();
+
+        assert_eq!(store.tender_bidders(1).unwrap(), vec!["B"]);
+        let tenders = store.list_tenders().unwrap();
+        assert!(!tenders[0].is_cancelled);
+        assert!(tenders[1].is_closed && tenders[1].is_cancelled);
+
+        let q = |query: &str| ids(&store.query_tenders(&filter(query, 500)).unwrap());
+        assert_eq!(q("status=cancelled"), vec![2]);
+        assert!(q("status=closed").is_empty());
+    }
+
+    #[test]
+    fn test_draft_publication_and_provisional_award() {
+        let store = IndexStore::open_in_memory().unwrap();
+        let draft = Tender { is_draft: true, ..tender(1) };
+        let events = vec![
+            event("1", 10, IndexUpdate::TenderCreated(Box::new(draft))),
+            event("2", 10, IndexUpdate::TenderCreated(Box::new(tender(2)))),
+        ];
+        store.apply(&events, Some("cursor-2")).unwrap();
+
+        let q = |query: &str| ids(&store.query_tenders(&filter(query, 500)).unwrap());
+        assert_eq!(q("status=draft"), vec![1]);
+        assert_eq!(q("status=open"), vec![2]);
+
+        let events = vec![
+            event("3", 11, IndexUpdate::TenderPublished { tender_id: 1 }),
+            event("4", 12, IndexUpdate::TenderClosed { tender_id: 2 }),
+            event("5", 12, IndexUpdate::AwardPending { tender_id: 2 }),
+        ];
+        store.apply(&events, Some("cursor-5")).unwrap();
+        assert!(q("status=draft").is_empty());
+        assert_eq!(q("status=open"), vec![1]);
+        assert!(store.list_tenders().unwrap()[1].award_pending);
+
+        store.apply(&[event("6", 13, IndexUpdate::AwardFinalized { tender_id: 2 })], Some("cursor-6")).unwrap();
+        assert!(!store.list_tenders().unwrap()[1].award_pending);
+    }
+
+    #[test]
+    fn test_amendment_updates_terms() {
+        let store = IndexStore::open_in_memory().unwrap();
+        let amendment = Amendment {
+            description: "Revised scope".to_string(),
+            ipfs_hash: "QmRevised".to_string(),
+            deadline: 1_500,
+            reveal_deadline: 2_500,
+            amended_at: 900,
+        };
+        let events = vec![
+            event("1", 10, IndexUpdate::TenderCreated(Box::new(tender(1)))),
+            event("2", 11, IndexUpdate::TenderAmended { tender_id: 1, amendment }),
+        ];
+        store.apply(&events, Some("cursor-2")).unwrap();
+
+        let tender = &store.list_tenders().unwrap()[0];
+        assert_eq!((tender.description.as_str(), tender.ipfs_hash.as_str()), ("Revised scope", "QmRevised"));
+        assert_eq!((tender.deadline, tender.reveal_deadline), (1_500, 2_500));
+        // Still open for bids past the original deadline
+        assert_eq!(ids(&store.query_tenders(&filter("status=open", 1_200)).unwrap()), vec![1]);
+    }
+
+    #[test]
+    fn test_replay_is_idempotent() {
+        let store = IndexStore::open_in_memory().unwrap();
+        let created = event("1", 10, IndexUpdate::TenderCreated(Box::new(tender(1))));
+        store.apply(std::slice::from_ref(&created), Some("c1")).unwrap();
+        store.apply(&[event("2", 11, IndexUpdate::TenderClosed { tender_id: 1 })], Some("c2")).unwrap();
+
+        // Re-delivering the creation event must not reopen the tender
+        store.apply(&[created], Some("c2")).unwrap();
+        assert!(store.list_tenders().unwrap()[0].is_closed);
+    }
+
+    #[test]
+    fn test_cursor_survives_reopen() {
+        let path = std::env::temp_dir().join(format!("opentender-index-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap().to_string();
+
+        let store = IndexStore::open(&path).unwrap();
+        assert_eq!(store.cursor().unwrap(), None);
+        store.apply(&[event("1", 10, IndexUpdate::TenderCreated(Box::new(tender(7))))], Some("resume-here")).unwrap();
+        drop(store);
+
+        let store = IndexStore::open(&path).unwrap();
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("resume-here"));
+        assert_eq!(store.list_tenders().unwrap()[0].id, 7);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_large_amounts_are_exact() {
+        let store = IndexStore::open_in_memory().unwrap();
+        let mut big = tender(1);
+        big.min_bid = i128::MAX;
+        store.apply(&[event("1", 10, IndexUpdate::TenderCreated(Box::new(big)))], Some("c")).unwrap();
+        assert_eq!(store.list_tenders().unwrap()[0].min_bid, i128::MAX);
+    }
+
+    fn seeded_store() -> IndexStore {
+        let store = IndexStore::open_in_memory().unwrap();
+        let specs: [(u64, &str, u64, i128, bool); 5] = [
+            (1, "Road resurfacing", 1_000, 500, false),
+            (2, "Bridge repair", 3_000, -20, false),
+            (3, "School roof", 2_000, i128::MAX, true),
+            (4, "Road_lighting 100%", 4_000, 100, false),
+            (5, "Water mains", 5_000, 100, false),
+        ];
+        let events: Vec<IndexedEvent> = specs
+            .iter()
+            .map(|(id, title, deadline, min_bid, closed)| {
+                let mut t = tender(*id);
+                t.title = title.to_string();
+                t.deadline = *deadline;
+                t.min_bid = *min_bid;
+                t.is_closed = *closed;
+                if *id == 5 {
+                    t.creator = "GOTHER".to_string();
+                }
+                event(&id.to_string(), *id as u32, IndexUpdate::TenderCreated(Box::new(t)))
+            })
+            .collect();
+        store.apply(&events, Some("c")).unwrap();
+        store
+    }
+
+    fn filter(query: &str, now: u64) -> TenderFilter {
+        let query = actix_web::web::Query::<TenderListQuery>::from_query(query).unwrap();
+        TenderFilter::from_query(&query, now).unwrap()
+    }
+
+    fn ids(page: &TenderPage) -> Vec<u64> {
+        page.tenders.iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let store = seeded_store();
+        let q = |query: &str| ids(&store.query_tenders(&filter(query, 2_500)).unwrap());
+
+        assert_eq!(q(""), vec![1, 2, 3, 4, 5]);
+        assert_eq!(q("status=open"), vec![2, 4, 5]);
+        assert_eq!(q("status=reveal"), vec![1]);
+        assert_eq!(q("status=closed"), vec![3]);
+        assert_eq!(q("creator=GOTHER"), vec![5]);
+        assert_eq!(q("deadline_from=2000&deadline_to=4000"), vec![2, 3, 4]);
+        assert_eq!(q("min_bid_from=0&min_bid_to=500"), vec![1, 4, 5]);
+        assert_eq!(q("min_bid_from=1000"), vec![3]);
+        assert_eq!(q("q=ROAD"), vec![1, 4]);
+        // LIKE wildcards in the search text are matched literally
+        assert_eq!(q("q=d_l"), vec![4]);
+        assert_eq!(q("q=100%25"), vec![4]);
+    }
+
+    #[test]
+    fn test_query_sorts_amounts_numerically() {
+        let store = seeded_store();
+        let page = store.query_tenders(&filter("sort=min_bid", 0)).unwrap();
+        assert_eq!(ids(&page), vec![2, 4, 5, 1, 3]);
+
+        let page = store.query_tenders(&filter("sort=min_bid&order=desc", 0)).unwrap();
+        assert_eq!(ids(&page), vec![3, 1, 5, 4, 2]);
+    }
+
+    #[test]
+    fn test_query_cursor_walks_all_pages() {
+        let store = seeded_store();
+        for (sort, expected) in [
+            ("sort=deadline&order=desc", vec![5, 4, 2, 3, 1]),
+            ("sort=min_bid", vec![2, 4, 5, 1, 3]),
+            ("sort=title", vec![2, 1, 4, 3, 5]),
+        ] {
+            let mut seen = Vec::new();
+            let mut cursor: Option<String> = None;
+            loop {
+                let mut query = format!("{}&limit=2", sort);
+                if let Some(c) = &cursor {
+                    query.push_str(&format!("&cursor={}", c));
+                }
+                let page = store.query_tenders(&filter(&query, 0)).unwrap();
+                assert!(page.tenders.len() <= 2);
+                seen.extend(ids(&page));
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            assert_eq!(seen, expected, "{}", sort);
+        }
+    }
+
+    #[test]
+    fn test_query_rejects_bad_input() {
+        let query = |q: &str| {
+            let query = actix_web::web::Query::<TenderListQuery>::from_query(q).unwrap();
+            TenderFilter::from_query(&query, 0)
+        };
+        assert!(query("min_bid_from=abc").is_err());
+        assert!(query("cursor=not-a-cursor").is_err());
+
+        // A cursor issued for one sort order is not valid for another
+        let cursor = PageCursor { key: SortKey::Text("Bridge".to_string()), id: 2 }.encode();
+        assert!(query(&format!("sort=title&cursor={}", cursor)).is_ok());
+        assert!(query(&format!("sort=min_bid&cursor={}", cursor)).is_err());
+
+        assert_eq!(query("limit=0").unwrap().limit, 1);
+        assert_eq!(query("limit=100000").unwrap().limit, MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_migrates_v1_database() {
+        let path = std::env::temp_dir().join(format!("opentender-index-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap().to_string();
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(SCHEMA).unwrap();
+            conn.execute(
+                "INSERT INTO tenders (id, creator, title, description, ipfs_hash, deadline,
+                    reveal_deadline, min_bid, is_closed, created_at, ledger)
+                 VALUES (1, 'G', 'Old', '', '', 10, 20, '-5', 0, 1, 1)",
+                [],
+            )
+            .unwrap();
+        }
+
+        let store = IndexStore::open(&path).unwrap();
+        let page = store.query_tenders(&filter("min_bid_to=0", 0)).unwrap();
+        assert_eq!(ids(&page), vec![1]);
+        assert_eq!(page.tenders[0].encryption_key, "");
+        assert_eq!(page.tenders[0].opening_mode, OpeningMode::BidderReveal);
+        assert_eq!(page.tenders[0].bond, None);
+        assert_eq!(page.tenders[0].evaluation, Evaluation::LowestPrice);
+        assert_eq!(page.tenders[0].tie_break, TieBreak::EarliestCommit);
+        assert!(page.tenders[0].lots.is_empty());
+        drop(store);
+        // Reopening an up-to-date database is a no-op
+        assert!(IndexStore::open(&path).is_ok());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,609 @@
+//! Background task that follows the contract's events through `getEvents`
+//! and folds them into the `IndexStore`.
+
+use crate::config::Config;
+use crate::models::types::{Amendment, Tender, Winner};
+use crate::services::index::{IndexStore, IndexUpdate, IndexedEvent};
+use crate::services::rpc::{EventInfo, RpcClient};
+use crate::services::scval::{self, ScValError};
+use crate::services::stellar;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_xdr::curr::ScVal;
+
+/// Events requested per `getEvents` page
+const PAGE_LIMIT: u32 = 100;
+/// Tenders requested per `get_tenders` call; matches the contract's cap
+const BACKFILL_PAGE_SIZE: u32 = 50;
+
+/// Topics emitted by the contract that the index understands. Lot bids only
+/// add the bidder to the tender; per-lot reveals and awards are read from
+/// the contract (`get_lot_winners`) rather than indexed.
+const TOPICS: [(&str, &str); 12] = [
+    ("tender", "created"),
+    ("tender", "amended"),
+    ("tender", "published"),
+    ("bid", "submitted"),
+    ("bid", "withdrawn"),
+    ("bid", "revealed"),
+    ("lot", "bid"),
+    ("winner", "selected"),
+    ("tender", "closed"),
+    ("award", "pending"),
+    ("award", "final"),
+    ("tender", "cancelled"),
+];
+
+/// Poll forever; errors are logged and retried on the next tick
+pub async fn run(config: Config, store: Arc<IndexStore>) {
+    let rpc = RpcClient::new(&config.rpc_url);
+    let interval = Duration::from_secs(config.indexer_poll_secs);
+    log::info!("Indexer started for contract {}", config.contract_id);
+
+    // Events older than the RPC retention window are gone, so a fresh index
+    // starts from current contract state
+    if matches!(store.cursor(), Ok(None)) && config.indexer_start_ledger.is_none() {
+        match backfill(&config, &store).await {
+            Ok(count) => log::info!("Backfilled {} tenders from contract state", count),
+            Err(e) => log::error!("Index backfill failed: {}", e),
+        }
+    }
+
+    loop {
+        match poll_once(&config, &rpc, &store).await {
+            // A full page means we are behind; fetch the next one straight away
+            Ok(count) if count as u32 >= PAGE_LIMIT => continue,
+            Ok(count) => {
+                if count > 0 {
+                    log::info!("Indexed {} contract events", count);
+                }
+            }
+            Err(e) => log::error!("Indexer poll failed: {}", e),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Fetch one page of events after the stored cursor and apply it.
+/// Returns the number of events received.
+pub async fn poll_once(
+    config: &Config,
+    rpc: &RpcClient,
+    store: &IndexStore,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let cursor = store.cursor()?;
+    let start_ledger = match (&cursor, config.indexer_start_ledger) {
+        (Some(_), _) => None,
+        (None, Some(ledger)) => Some(ledger),
+        (None, None) => Some(rpc.get_latest_ledger().await?.sequence),
+    };
+
+    let page = rpc
+        .get_events(&config.contract_id, &topic_filters()?, start_ledger, cursor.as_deref(), PAGE_LIMIT)
+        .await?;
+
+    let mut batch = Vec::with_capacity(page.events.len());
+    for event in &page.events {
+        // A malformed event would fail the same way every time and stall
+        // the cursor; contract lookups are retried next tick instead
+        let decoded = match decode(event) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                log::warn!("Skipping undecodable event {}: {}", event.id, e);
+                continue;
+            }
+        };
+        if let Some(update) = resolve(config, event, decoded).await? {
+            batch.push(IndexedEvent {
+                id: event.id.clone(),
+                ledger: event.ledger,
+                update,
+            });
+        }
+    }
+
+    let next_cursor = page
+        .cursor
+        .or_else(|| page.events.last().map(|e| e.paging_token.clone().unwrap_or_else(|| e.id.clone())));
+    if let Some(next_cursor) = next_cursor {
+        store.apply(&batch, Some(&next_cursor))?;
+    }
+
+    Ok(page.events.len())
+}
+
+/// Seed the index from contract reads. Synthetic event ids keep a later
+/// backfill from duplicating rows.
+pub async fn backfill(config: &Config, store: &IndexStore) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut tenders = Vec::new();
+    loop {
+        let start = tenders.last().map_or(1, |t: &Tender| t.id + 1);
+        let page = stellar::get_tenders(config, start, BACKFILL_PAGE_SIZE).await?;
+        if page.is_empty() {
+            break;
+        }
+        tenders.extend(page);
+    }
+
+    let mut batch = Vec::new();
+    for tender in &tenders {
+        let tender_id = tender.id;
+        batch.push(synthetic(
+            format!("backfill-tender-{}", tender_id),
+            IndexUpdate::TenderCreated(Box::new(tender.clone())),
+        ));
+
+        for bidder in stellar::get_tender_bidders(config, tender_id).await? {
+            let revealed = stellar::get_bid(config, tender_id, &bidder)
+                .await?
+                .and_then(|bid| bid.revealed_amount);
+            batch.push(synthetic(
+                format!("backfill-bid-{}-{}", tender_id, bidder),
+                IndexUpdate::BidSubmitted { tender_id, bidder: bidder.clone() },
+            ));
+            if let Some(amount) = revealed {
+                batch.push(synthetic(
+                    format!("backfill-reveal-{}-{}", tender_id, bidder),
+                    IndexUpdate::BidRevealed { tender_id, bidder, amount },
+                ));
+            }
+        }
+
+        if let Some(winner) = stellar::get_winner(config, tender_id).await? {
+            batch.push(synthetic(
+                format!("backfill-winner-{}", tender_id),
+                IndexUpdate::WinnerSelected(winner),
+            ));
+        }
+    }
+
+    store.apply(&batch, None)?;
+    Ok(tenders.len())
+}
+
+fn synthetic(id: String, update: IndexUpdate) -> IndexedEvent {
+    IndexedEvent { id, ledger: 0, update }
+}
+
+fn topic_filters() -> Result<Vec<Vec<String>>, ScValError> {
+    TOPICS
+        .iter()
+        .map(|(a, b)| Ok(vec![scval::to_base64(&scval::symbol(a)?)?, scval::to_base64(&scval::symbol(b)?)?]))
+        .collect()
+}
+
+/// Decode an event's topics and payload
+fn decode(event: &EventInfo) -> Result<Option<ContractEvent>, ScValError> {
+    let topics = event
+        .topic
+        .iter()
+        .map(|t| scval::from_base64::<ScVal>(t))
+        .collect::<Result<Vec<_>, _>>()?;
+    let value: ScVal = scval::from_base64(&event.value)?;
+    decode_event(&topics, &value, &event.ledger_closed_at)
+}
+
+/// Turn a decoded event into an index update; tender creation only carries
+/// the id, so the full record is read back from the contract.
+async fn resolve(
+    config: &Config,
+    event: &EventInfo,
+    decoded: Option<ContractEvent>,
+) -> Result<Option<IndexUpdate>, Box<dyn std::error::Error>> {
+    let update = match decoded {
+        Some(ContractEvent::TenderCreated { tender_id }) => {
+            IndexUpdate::TenderCreated(Box::new(stellar::get_tender(config, tender_id).await?))
+        }
+        Some(ContractEvent::Update(update)) => update,
+        None => {
+            log::warn!("Skipping unrecognised event {}", event.id);
+            return Ok(None);
+        }
+    };
+    Ok(Some(update))
+}
+
+enum ContractEvent {
+    TenderCreated { tender_id: u64 },
+    Update(IndexUpdate),
+}
+
+fn decode_event(
+    topics: &[ScVal],
+    value: &ScVal,
+    closed_at: &str,
+) -> Result<Option<ContractEvent>, ScValError> {
+    let names = topics
+        .iter()
+        .map(|t| match t {
+            ScVal::Symbol(s) => Ok(s.0.to_utf8_string_lossy()),
+            _ => Err(ScValError("event topic is not a symbol".to_string())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    let event = match names.as_slice() {
+        ["tender", "created"] => ContractEvent::TenderCreated {
+            tender_id: scval::to_u64(value)?,
+        },
+        ["bid", "submitted"] => {
+            let fields = tuple(value, 2)?;
+            ContractEvent::Update(IndexUpdate::BidSubmitted {
+                tender_id: scval::to_u64(&fields[0])?,
+                bidder: scval::to_address(&fields[1])?,
+            })
+        }
+        // A bidder enters the tender with their first lot bid; withdrawing
+        // is tender-wide and published as ("bid", "withdrawn")
+        ["lot", "bid"] => {
+            let fields = tuple(value, 3)?;
+            ContractEvent::Update(IndexUpdate::BidSubmitted {
+                tender_id: scval::to_u64(&fields[0])?,
+                bidder: scval::to_address(&fields[2])?,
+            })
+        }
+        ["bid", "withdrawn"] => {
+            let fields = tuple(value, 2)?;
+            ContractEvent::Update(IndexUpdate::BidWithdrawn {
+                tender_id: scval::to_u64(&fields[0])?,
+                bidder: scval::to_address(&fields[1])?,
+            })
+        }
+        ["bid", "revealed"] => {
+            let fields = tuple(value, 3)?;
+            ContractEvent::Update(IndexUpdate::BidRevealed {
+                tender_id: scval::to_u64(&fields[0])?,
+                bidder: scval::to_address(&fields[1])?,
+                amount: scval::to_i128(&fields[2])?,
+            })
+        }
+        ["winner", "selected"] => {
+            // Older contracts published no payable amount and no tied
+            // bidders; the tie list is not indexed
+            let fields = scval::to_vec(value, |v| Ok(v.clone()))?;
+            if !(3..=5).contains(&fields.len()) {
+                return Err(ScValError(format!("expected 3 to 5 values, got {}", fields.len())));
+            }
+            let amount = scval::to_i128(&fields[2])?;
+            ContractEvent::Update(IndexUpdate::WinnerSelected(Winner {
+                tender_id: scval::to_u64(&fields[0])?,
+                bidder: scval::to_address(&fields[1])?,
+                amount,
+                payable: fields.get(3).map_or(Ok(amount), scval::to_i128)?,
+                selected_at: chrono::DateTime::parse_from_rfc3339(closed_at)
+                    .map(|t| t.timestamp() as u64)
+                    .unwrap_or_default(),
+                lot: None,
+            }))
+        }
+        ["tender", "closed"] => ContractEvent::Update(IndexUpdate::TenderClosed {
+            tender_id: scval::to_u64(value)?,
+        }),
+        ["tender", "amended"] => {
+            let fields = tuple(value, 2)?;
+            ContractEvent::Update(IndexUpdate::TenderAmended {
+                tender_id: scval::to_u64(&fields[0])?,
+                amendment: Amendment::try_from(&fields[1])?,
+            })
+        }
+        ["tender", "published"] => ContractEvent::Update(IndexUpdate::TenderPublished {
+            tender_id: scval::to_u64(value)?,
+        }),
+        ["award", "pending"] => ContractEvent::Update(IndexUpdate::AwardPending {
+            tender_id: scval::to_u64(value)?,
+        }),
+        ["award", "final"] => ContractEvent::Update(IndexUpdate::AwardFinalized {
+            tender_id: scval::to_u64(value)?,
+        }),
+        // The reason stays on chain; read it with `get_cancellation`
+        ["tender", "cancelled"] => ContractEvent::Update(IndexUpdate::TenderCancelled {
+            tender_id: scval::to_u64(&tuple(value, 3)?[0])?,
+        }),
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// Event payload tuples are published as an `ScVec`
+fn tuple(value: &ScVal, len: usize) -> Result<Vec<ScVal>, ScValError> {
+    let fields = scval::to_vec(value, |v| Ok(v.clone()))?;
+    if fields.len() != len {
+        return Err(ScValError(format!("expected {}-tuple, got {} values", len, fields.len())));
+    }
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::types::{Eligibility, Evaluation, OpeningMode, TieBreak};
+    use crate::services::mock_rpc::MockRpcServer;
+    use serde_json::{json, Value};
+    use stellar_xdr::curr::{ScVec, VecM};
+
+    const BIDDER: &str = "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M";
+    const CONTRACT: &str = "CD5JXQDVQJK2ZHYX7U76I45N2DKKFMVCA6WJ6ZQHO2EL6WJV2VAD6GP4";
+
+    fn test_config(rpc_url: &str) -> Config {
+        Config::for_tests(rpc_url)
+    }
+
+    fn vec_val(items: Vec<ScVal>) -> ScVal {
+        ScVal::Vec(Some(ScVec(VecM::try_from(items).unwrap())))
+    }
+
+    fn event_json(id: &str, ledger: u32, topic: (&str, &str), value: ScVal) -> Value {
+        json!({
+            "type": "contract",
+            "id": id,
+            "pagingToken": id,
+            "ledger": ledger,
+            "ledgerClosedAt": "2026-01-01T00:00:00Z",
+            "contractId": CONTRACT,
+            "topic": [
+                scval::to_base64(&scval::symbol(topic.0).unwrap()).unwrap(),
+                scval::to_base64(&scval::symbol(topic.1).unwrap()).unwrap(),
+            ],
+            "value": scval::to_base64(&value).unwrap(),
+        })
+    }
+
+    fn tender_value() -> ScVal {
+        ScVal::try_from(&Tender {
+            id: 1,
+            creator: BIDDER.to_string(),
+            title: "Roads".to_string(),
+            description: "Resurfacing".to_string(),
+            ipfs_hash: "QmDocs".to_string(),
+            deadline: 1_000,
+            reveal_deadline: 2_000,
+            min_bid: 100_000,
+            is_closed: false,
+            created_at: 10,
+            encryption_key: "0707070707070707070707070707070707070707070707070707070707070707".to_string(),
+            opening_mode: OpeningMode::BidderReveal,
+            bond: None,
+            evaluation: Evaluation::LowestPrice,
+            tie_break: TieBreak::EarliestCommit,
+            lots: Vec::new(),
+            max_lots_per_bidder: 0,
+            eligibility: Eligibility::Open,
+            max_strikes: 0,
+            is_cancelled: false,
+            is_draft: false,
+            award_pending: false,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_bid_revealed() {
+        let topics = vec![scval::symbol("bid").unwrap(), scval::symbol("revealed").unwrap()];
+        let value = vec_val(vec![
+            ScVal::U64(3),
+            scval::address(BIDDER).unwrap(),
+            scval::i128(150_000),
+        ]);
+        match decode_event(&topics, &value, "").unwrap() {
+            Some(ContractEvent::Update(IndexUpdate::BidRevealed { tender_id, bidder, amount })) => {
+                assert_eq!(tender_id, 3);
+                assert_eq!(bidder, BIDDER);
+                assert_eq!(amount, 150_000);
+            }
+            _ => panic!("expected a reveal"),
+        }
+    }
+
+    #[test]
+    fn test_decode_lot_bid() {
+        let topics = vec![scval::symbol("lot").unwrap(), scval::symbol("bid").unwrap()];
+        let value = vec_val(vec![ScVal::U64(3), ScVal::U32(1), scval::address(BIDDER).unwrap()]);
+        match decode_event(&topics, &value, "").unwrap() {
+            Some(ContractEvent::Update(IndexUpdate::BidSubmitted { tender_id, bidder })) => {
+                assert_eq!(tender_id, 3);
+                assert_eq!(bidder, BIDDER);
+            }
+            _ => panic!("expected a bid"),
+        }
+
+        // Per-lot awards are read from the contract
+        let topics = vec![scval::symbol("lot").unwrap(), scval::symbol("awarded").unwrap()];
+        assert!(decode_event(&topics, &ScVal::Void, "").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decode_tender_amended() {
+        let amendment = Amendment {
+            description: "Revised scope".to_string(),
+            ipfs_hash: "QmRevised".to_string(),
+            deadline: 5_000,
+            reveal_deadline: 6_000,
+            amended_at: 4_000,
+        };
+        let topics = vec![scval::symbol("tender").unwrap(), scval::symbol("amended").unwrap()];
+        let value = vec_val(vec![ScVal::U64(2), ScVal::try_from(&amendment).unwrap()]);
+        match decode_event(&topics, &value, "").unwrap() {
+            Some(ContractEvent::Update(IndexUpdate::TenderAmended { tender_id, amendment: decoded })) => {
+                assert_eq!(tender_id, 2);
+                assert_eq!(decoded, amendment);
+            }
+            _ => panic!("expected an amendment"),
+        }
+    }
+
+    #[test]
+    fn test_decode_approval_outcomes() {
+        let decode = |a: &str, b: &str| {
+            let topics = vec![scval::symbol(a).unwrap(), scval::symbol(b).unwrap()];
+            match decode_event(&topics, &ScVal::U64(6), "").unwrap() {
+                Some(ContractEvent::Update(update)) => update,
+                _ => panic!("expected an update"),
+            }
+        };
+        assert!(matches!(decode("tender", "published"), IndexUpdate::TenderPublished { tender_id: 6 }));
+        assert!(matches!(decode("award", "pending"), IndexUpdate::AwardPending { tender_id: 6 }));
+        assert!(matches!(decode("award", "final"), IndexUpdate::AwardFinalized { tender_id: 6 }));
+
+        // Individual approvals stay on chain; read them with `get_approvals`
+        let topics = vec![scval::symbol("award").unwrap(), scval::symbol("approved").unwrap()];
+        let value = vec_val(vec![ScVal::U64(6), scval::address(BIDDER).unwrap()]);
+        assert!(decode_event(&topics, &value, "").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decode_tender_cancelled() {
+        let topics = vec![scval::symbol("tender").unwrap(), scval::symbol("cancelled").unwrap()];
+        let value = vec_val(vec![
+            ScVal::U64(4),
+            scval::address(BIDDER).unwrap(),
+            scval::string("Duplicate").unwrap(),
+        ]);
+        match decode_event(&topics, &value, "").unwrap() {
+            Some(ContractEvent::Update(IndexUpdate::TenderCancelled { tender_id })) => assert_eq!(tender_id, 4),
+            _ => panic!("expected a cancellation"),
+        }
+    }
+
+    #[test]
+    fn test_decode_winner_selected() {
+        let topics = vec![scval::symbol("winner").unwrap(), scval::symbol("selected").unwrap()];
+        let mut fields = vec![ScVal::U64(3), scval::address(BIDDER).unwrap(), scval::i128(150_000)];
+        let payable = |fields: Vec<ScVal>| match decode_event(&topics, &vec_val(fields), "").unwrap() {
+            Some(ContractEvent::Update(IndexUpdate::WinnerSelected(w))) => (w.amount, w.payable),
+            _ => panic!("expected a winner"),
+        };
+
+        assert_eq!(payable(fields.clone()), (150_000, 150_000));
+        fields.push(scval::i128(130_000));
+        assert_eq!(payable(fields.clone()), (150_000, 130_000));
+        fields.push(vec_val(vec![scval::address(BIDDER).unwrap()]));
+        assert_eq!(payable(fields), (150_000, 130_000));
+    }
+
+    #[test]
+    fn test_decode_ignores_unknown_topics() {
+        let topics = vec![scval::symbol("other").unwrap(), scval::symbol("thing").unwrap()];
+        assert!(decode_event(&topics, &ScVal::Void, "").unwrap().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_poll_indexes_and_resumes_from_cursor() {
+        let server = MockRpcServer::start(|method, params| match method {
+            "getEvents" if params["pagination"]["cursor"].is_null() => json!({
+                "result": {
+                    "events": [
+                        event_json("0001", 101, ("tender", "created"), ScVal::U64(1)),
+                        event_json(
+                            "0002",
+                            102,
+                            ("bid", "submitted"),
+                            vec_val(vec![ScVal::U64(1), scval::address(BIDDER).unwrap()]),
+                        ),
+                        event_json("0003", 103, ("tender", "closed"), ScVal::U64(1)),
+                    ],
+                    "latestLedger": 110,
+                }
+            }),
+            "getEvents" => json!({ "result": { "events": [], "latestLedger": 111 } }),
+            "simulateTransaction" => json!({
+                "result": {
+                    "results": [{ "auth": [], "xdr": scval::to_base64(&tender_value()).unwrap() }],
+                    "latestLedger": 110,
+                }
+            }),
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        })
+        .await;
+        let config = test_config(&server.url);
+        let rpc = RpcClient::new(&server.url);
+        let store = IndexStore::open_in_memory().unwrap();
+
+        assert_eq!(poll_once(&config, &rpc, &store).await.unwrap(), 3);
+        let tenders = store.list_tenders().unwrap();
+        assert_eq!(tenders.len(), 1);
+        assert_eq!(tenders[0].title, "Roads");
+        assert!(tenders[0].is_closed);
+        assert_eq!(store.tender_bidders(1).unwrap(), vec![BIDDER]);
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("0003"));
+
+        assert_eq!(poll_once(&config, &rpc, &store).await.unwrap(), 0);
+        let requests: Vec<Value> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r["method"] == "getEvents")
+            .collect();
+        assert_eq!(requests[0]["params"]["startLedger"], 100);
+        assert_eq!(requests[1]["params"]["pagination"]["cursor"], "0003");
+        assert!(requests[1]["params"]["startLedger"].is_null());
+        assert_eq!(requests[0]["params"]["filters"][0]["topics"].as_array().unwrap().len(), 12);
+    }
+
+    #[actix_rt::test]
+    async fn test_poll_skips_undecodable_events() {
+        let server = MockRpcServer::start(|method, _| match method {
+            "getEvents" => json!({
+                "result": {
+                    "events": [
+                        // A bid event without its bidder
+                        event_json("0001", 101, ("bid", "submitted"), ScVal::U64(1)),
+                        event_json("0002", 102, ("tender", "closed"), ScVal::U64(1)),
+                    ],
+                    "latestLedger": 110,
+                }
+            }),
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        })
+        .await;
+        let config = test_config(&server.url);
+        let rpc = RpcClient::new(&server.url);
+        let store = IndexStore::open_in_memory().unwrap();
+
+        assert_eq!(poll_once(&config, &rpc, &store).await.unwrap(), 2);
+        assert!(store.tender_bidders(1).unwrap().is_empty());
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("0002"));
+    }
+
+    #[actix_rt::test]
+    async fn test_poll_retries_on_rpc_errors() {
+        let server = MockRpcServer::start(|method, _| match method {
+            "getEvents" => json!({
+                "result": {
+                    "events": [event_json("0001", 101, ("tender", "created"), ScVal::U64(1))],
+                    "latestLedger": 110,
+                }
+            }),
+            _ => json!({ "error": { "code": -32603, "message": "internal error" } }),
+        })
+        .await;
+        let config = test_config(&server.url);
+        let rpc = RpcClient::new(&server.url);
+        let store = IndexStore::open_in_memory().unwrap();
+
+        assert!(poll_once(&config, &rpc, &store).await.is_err());
+        assert_eq!(store.cursor().unwrap(), None);
+    }
+
+    #[actix_rt::test]
+    async fn test_poll_retries_on_failed_tender_lookup() {
+        // e.g. a lagging node that cannot see the new tender yet
+        let server = MockRpcServer::start(|method, _| match method {
+            "getEvents" => json!({
+                "result": {
+                    "events": [event_json("0001", 101, ("tender", "created"), ScVal::U64(1))],
+                    "latestLedger": 110,
+                }
+            }),
+            "simulateTransaction" => json!({
+                "result": { "error": "HostError: Error(Storage, MissingValue)", "latestLedger": 110 }
+            }),
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        })
+        .await;
+        let config = test_config(&server.url);
+        let rpc = RpcClient::new(&server.url);
+        let store = IndexStore::open_in_memory().unwrap();
+
+        assert!(poll_once(&config, &rpc, &store).await.is_err());
+        assert_eq!(store.cursor().unwrap(), None);
+    }
+}
@@ -0,0 +1,78 @@
+//! In-process Soroban RPC stand-in for tests. The handler receives the
+//! JSON-RPC method and params and returns the `result`/`error` part of the
+//! response; every request body is recorded for later assertions.
+
+use actix_web::{web, App, HttpResponse, HttpServer};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+type Handler = dyn Fn(&str, &Value) -> Value + Send + Sync;
+
+pub struct MockRpcServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Value>>>,
+    handle: actix_web::dev::ServerHandle,
+}
+
+struct MockState {
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<Value>>>,
+}
+
+impl MockRpcServer {
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&str, &Value) -> Value + Send + Sync + 'static,
+    {
+        let handler: Arc<Handler> = Arc::new(handler);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let state_handler = handler.clone();
+        let state_requests = requests.clone();
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(web::Data::new(MockState {
+                    handler: state_handler.clone(),
+                    requests: state_requests.clone(),
+                }))
+                .route("/", web::post().to(dispatch))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .expect("bind mock RPC server");
+
+        let addr = server.addrs()[0];
+        let server = server.run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        Self {
+            url: format!("http://{}/", addr),
+            requests,
+            handle,
+        }
+    }
+
+    /// Every JSON-RPC request received so far, in arrival order
+    pub fn requests(&self) -> Vec<Value> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockRpcServer {
+    fn drop(&mut self) {
+        // The stop command is sent eagerly; the returned future only waits for it
+        drop(self.handle.stop(false));
+    }
+}
+
+async fn dispatch(state: web::Data<MockState>, body: web::Json<Value>) -> HttpResponse {
+    let request = body.into_inner();
+    state.requests.lock().unwrap().push(request.clone());
+
+    let method = request["method"].as_str().unwrap_or_default();
+    let mut response = (state.handler)(method, &request["params"]);
+    response["jsonrpc"] = "2.0".into();
+    response["id"] = request["id"].clone();
+    HttpResponse::Ok().json(response)
+}
@@ -1,2 +1,10 @@
 pub mod stellar;
-pub mod encryption;
\ No newline at end of file
+pub mod encryption;
+pub mod sealed;
+pub mod shamir;
+pub mod rpc;
+pub mod scval;
+pub mod index;
+pub mod indexer;
+#[cfg(test)]
+pub mod mock_rpc;
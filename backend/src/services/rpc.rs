@@ -0,0 +1,266 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Minimal JSON-RPC 2.0 client for the Soroban RPC endpoints the backend uses.
+/// All payloads are base64-encoded XDR, exactly as the RPC server exchanges them.
+pub struct RpcClient {
+    http: reqwest::Client,
+    url: String,
+    next_id: AtomicU64,
+}
+
+#[derive(Debug)]
+pub enum RpcError {
+    Http(reqwest::Error),
+    /// JSON-RPC level error object returned by the server
+    Server { code: i64, message: String },
+    /// The response was not the shape we expected
+    Malformed(String),
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcError::Http(e) => write!(f, "RPC request failed: {}", e),
+            RpcError::Server { code, message } => write!(f, "RPC error {}: {}", code, message),
+            RpcError::Malformed(msg) => write!(f, "Malformed RPC response: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+impl From<reqwest::Error> for RpcError {
+    fn from(e: reqwest::Error) -> Self {
+        RpcError::Http(e)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateTransactionResponse {
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub transaction_data: Option<String>,
+    #[serde(default)]
+    pub min_resource_fee: Option<String>,
+    #[serde(default)]
+    pub results: Vec<SimulateHostFunctionResult>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimulateHostFunctionResult {
+    #[serde(default)]
+    pub auth: Vec<String>,
+    pub xdr: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendTransactionResponse {
+    /// PENDING, DUPLICATE, TRY_AGAIN_LATER or ERROR
+    pub status: String,
+    pub hash: String,
+    #[serde(default)]
+    pub error_result_xdr: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResponse {
+    /// SUCCESS, NOT_FOUND or FAILED
+    pub status: String,
+    #[serde(default)]
+    pub result_meta_xdr: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLedgerEntriesResponse {
+    #[serde(default)]
+    pub entries: Vec<LedgerEntryResult>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LedgerEntryResult {
+    /// base64 `LedgerEntryData`
+    pub xdr: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEventsResponse {
+    #[serde(default)]
+    pub events: Vec<EventInfo>,
+    /// Resume point after the last returned event (newer RPC versions only)
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventInfo {
+    pub id: String,
+    pub ledger: u32,
+    pub ledger_closed_at: String,
+    /// base64 `ScVal` topics
+    pub topic: Vec<String>,
+    /// base64 `ScVal` event data
+    pub value: String,
+    #[serde(default)]
+    pub paging_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetLatestLedgerResponse {
+    pub sequence: u32,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.to_string(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// `transaction` is a base64 `TransactionEnvelope`
+    pub async fn simulate_transaction(
+        &self,
+        transaction: &str,
+    ) -> Result<SimulateTransactionResponse, RpcError> {
+        self.call("simulateTransaction", json!({ "transaction": transaction })).await
+    }
+
+    /// `transaction` is a base64 signed `TransactionEnvelope`
+    pub async fn send_transaction(
+        &self,
+        transaction: &str,
+    ) -> Result<SendTransactionResponse, RpcError> {
+        self.call("sendTransaction", json!({ "transaction": transaction })).await
+    }
+
+    pub async fn get_transaction(&self, hash: &str) -> Result<GetTransactionResponse, RpcError> {
+        self.call("getTransaction", json!({ "hash": hash })).await
+    }
+
+    /// `keys` are base64 `LedgerKey`s
+    pub async fn get_ledger_entries(
+        &self,
+        keys: &[String],
+    ) -> Result<GetLedgerEntriesResponse, RpcError> {
+        self.call("getLedgerEntries", json!({ "keys": keys })).await
+    }
+
+    /// Contract events for `contract_id` matching any of `topics` (each a list
+    /// of base64 `ScVal` segments). Pass `start_ledger` on the first request and
+    /// `cursor` afterwards; the RPC rejects both together.
+    pub async fn get_events(
+        &self,
+        contract_id: &str,
+        topics: &[Vec<String>],
+        start_ledger: Option<u32>,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<GetEventsResponse, RpcError> {
+        let mut pagination = json!({ "limit": limit });
+        if let Some(cursor) = cursor {
+            pagination["cursor"] = cursor.into();
+        }
+        let mut params = json!({
+            "filters": [{
+                "type": "contract",
+                "contractIds": [contract_id],
+                "topics": topics,
+            }],
+            "pagination": pagination,
+        });
+        if let Some(start_ledger) = start_ledger {
+            params["startLedger"] = start_ledger.into();
+        }
+        self.call("getEvents", params).await
+    }
+
+    pub async fn get_latest_ledger(&self) -> Result<GetLatestLedgerResponse, RpcError> {
+        self.call("getLatestLedger", json!({})).await
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, RpcError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(RpcError::Server {
+                code: error["code"].as_i64().unwrap_or(0),
+                message: error["message"].as_str().unwrap_or("unknown error").to_string(),
+            });
+        }
+
+        let result = response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| RpcError::Malformed(format!("{} returned no result", method)))?;
+        serde_json::from_value(result).map_err(|e| RpcError::Malformed(format!("{}: {}", method, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::mock_rpc::MockRpcServer;
+
+    #[actix_rt::test]
+    async fn test_server_error_is_surfaced() {
+        let server = MockRpcServer::start(|_, _| {
+            json!({ "error": { "code": -32602, "message": "invalid parameters" } })
+        })
+        .await;
+        let client = RpcClient::new(&server.url);
+
+        match client.get_transaction("abcd").await {
+            Err(RpcError::Server { code, message }) => {
+                assert_eq!(code, -32602);
+                assert_eq!(message, "invalid parameters");
+            }
+            other => panic!("expected server error, got {:?}", other),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_request_envelope() {
+        let server = MockRpcServer::start(|_, _| {
+            json!({ "result": { "entries": [], "latestLedger": 42 } })
+        })
+        .await;
+        let client = RpcClient::new(&server.url);
+
+        let response = client.get_ledger_entries(&["AAAA".to_string()]).await.unwrap();
+        assert!(response.entries.is_empty());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0]["jsonrpc"], "2.0");
+        assert_eq!(requests[0]["method"], "getLedgerEntries");
+        assert_eq!(requests[0]["params"]["keys"][0], "AAAA");
+    }
+}
@@ -0,0 +1,1008 @@
+//! `ScVal` encoding/decoding for the contract's `#[contracttype]` structs.
+//!
+//! Soroban serialises a struct as an `ScMap` keyed by field-name symbols in
+//! lexicographic order, `Option::None` as `Void` and `String`/`BytesN` as
+//! `ScString`/`ScBytes`. The conversions below follow those rules so values
+//! round-trip with what the contract stores.
+
+use crate::models::types::{
+    Amendment, Approval, ApprovalPolicy, Bid, BidBond, BidOpening, BidderOutcome, Cancellation, Committee,
+    CreateTenderRequest, Criterion, Debarment, Eligibility, Escrow, Evaluation, KeyShare, Lot, Milestone,
+    MilestoneStatus, MilestoneTerms, OpeningMode, Registration, RegistrationStatus, RevealOutcome, Scorecard,
+    StrikeRecord, Tender, TieBreak, WeightedCriteria, Winner,
+};
+use base64::{engine::general_purpose, Engine as _};
+use std::fmt;
+use std::str::FromStr;
+use stellar_xdr::curr::{
+    Int128Parts, Limits, ReadXdr, ScAddress, ScBytes, ScMap, ScMapEntry, ScString, ScSymbol,
+    ScVal, ScVec, WriteXdr,
+};
+
+#[derive(Debug)]
+pub struct ScValError(pub String);
+
+impl fmt::Display for ScValError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ScVal conversion failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for ScValError {}
+
+impl From<stellar_xdr::curr::Error> for ScValError {
+    fn from(e: stellar_xdr::curr::Error) -> Self {
+        ScValError(e.to_string())
+    }
+}
+
+/// Serialise any XDR value to the base64 form used by the RPC API
+pub fn to_base64<T: WriteXdr>(value: &T) -> Result<String, ScValError> {
+    Ok(general_purpose::STANDARD.encode(value.to_xdr(Limits::none())?))
+}
+
+pub fn from_base64<T: ReadXdr>(encoded: &str) -> Result<T, ScValError> {
+    let bytes = general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| ScValError(format!("invalid base64: {}", e)))?;
+    Ok(T::from_xdr(bytes, Limits::none())?)
+}
+
+// Encoders for contract arguments
+
+pub fn address(value: &str) -> Result<ScVal, ScValError> {
+    ScAddress::from_str(value)
+        .map(ScVal::Address)
+        .map_err(|_| ScValError(format!("invalid address: {}", value)))
+}
+
+pub fn string(value: &str) -> Result<ScVal, ScValError> {
+    Ok(ScVal::String(ScString(value.try_into()?)))
+}
+
+pub fn symbol(value: &str) -> Result<ScVal, ScValError> {
+    Ok(ScVal::Symbol(ScSymbol(value.try_into()?)))
+}
+
+pub fn u32(value: u32) -> ScVal {
+    ScVal::U32(value)
+}
+
+pub fn u64(value: u64) -> ScVal {
+    ScVal::U64(value)
+}
+
+pub fn i128(value: i128) -> ScVal {
+    ScVal::I128(Int128Parts {
+        hi: (value >> 64) as i64,
+        lo: value as u64,
+    })
+}
+
+/// `BytesN<32>` from a hex string
+pub fn bytes32_hex(value: &str) -> Result<ScVal, ScValError> {
+    let bytes = hex::decode(value).map_err(|e| ScValError(format!("invalid hex: {}", e)))?;
+    if bytes.len() != 32 {
+        return Err(ScValError(format!("expected 32 bytes, got {}", bytes.len())));
+    }
+    Ok(ScVal::Bytes(ScBytes(bytes.try_into()?)))
+}
+
+/// `Bytes` from a hex string
+pub fn bytes_hex(value: &str) -> Result<ScVal, ScValError> {
+    let bytes = hex::decode(value).map_err(|e| ScValError(format!("invalid hex: {}", e)))?;
+    Ok(ScVal::Bytes(ScBytes(bytes.try_into()?)))
+}
+
+pub fn vec(items: Vec<ScVal>) -> Result<ScVal, ScValError> {
+    Ok(ScVal::Vec(Some(ScVec(items.try_into()?))))
+}
+
+/// Unit variant of a `#[contracttype]` enum: a one-element vec of its name
+pub fn unit_enum(name: &str) -> Result<ScVal, ScValError> {
+    vec(vec![symbol(name)?])
+}
+
+// Decoders for contract return values
+
+pub fn to_u32(val: &ScVal) -> Result<u32, ScValError> {
+    match val {
+        ScVal::U32(v) => Ok(*v),
+        other => Err(unexpected("u32", other)),
+    }
+}
+
+pub fn to_u64(val: &ScVal) -> Result<u64, ScValError> {
+    match val {
+        ScVal::U64(v) => Ok(*v),
+        other => Err(unexpected("u64", other)),
+    }
+}
+
+pub fn to_i128(val: &ScVal) -> Result<i128, ScValError> {
+    match val {
+        ScVal::I128(parts) => Ok(((parts.hi as i128) << 64) | parts.lo as i128),
+        other => Err(unexpected("i128", other)),
+    }
+}
+
+pub fn to_bool(val: &ScVal) -> Result<bool, ScValError> {
+    match val {
+        ScVal::Bool(v) => Ok(*v),
+        other => Err(unexpected("bool", other)),
+    }
+}
+
+pub fn to_address(val: &ScVal) -> Result<String, ScValError> {
+    match val {
+        ScVal::Address(addr) => Ok(addr.to_string()),
+        other => Err(unexpected("address", other)),
+    }
+}
+
+pub fn to_string(val: &ScVal) -> Result<String, ScValError> {
+    match val {
+        ScVal::String(s) => Ok(s.0.to_utf8_string_lossy()),
+        other => Err(unexpected("string", other)),
+    }
+}
+
+pub fn to_bytes_hex(val: &ScVal) -> Result<String, ScValError> {
+    match val {
+        ScVal::Bytes(b) => Ok(hex::encode(b.0.as_slice())),
+        other => Err(unexpected("bytes", other)),
+    }
+}
+
+pub fn to_unit_enum(val: &ScVal) -> Result<String, ScValError> {
+    match val {
+        ScVal::Vec(Some(ScVec(items))) if items.len() == 1 => match &items[0] {
+            ScVal::Symbol(s) => Ok(s.0.to_utf8_string_lossy()),
+            other => Err(unexpected("symbol", other)),
+        },
+        other => Err(unexpected("enum variant", other)),
+    }
+}
+
+/// `BondPolicy::NotRequired` or `BondPolicy::Required(BidBond)`
+pub fn bond_policy(bond: Option<&BidBond>) -> Result<ScVal, ScValError> {
+    match bond {
+        None => unit_enum("NotRequired"),
+        Some(bond) => vec(vec![
+            symbol("Required")?,
+            struct_map(vec![
+                ("token", address(&bond.token)?),
+                ("amount", i128(bond.amount)),
+                ("beneficiary", address(&bond.beneficiary)?),
+            ])?,
+        ]),
+    }
+}
+
+/// `Evaluation::LowestPrice` or `Evaluation::Weighted(WeightedCriteria)`
+pub fn evaluation(evaluation: &Evaluation) -> Result<ScVal, ScValError> {
+    match evaluation {
+        Evaluation::LowestPrice => unit_enum("LowestPrice"),
+        Evaluation::HighestPrice => unit_enum("HighestPrice"),
+        Evaluation::Vickrey => unit_enum("Vickrey"),
+        Evaluation::Weighted(weighted) => {
+            let criteria = weighted
+                .criteria
+                .iter()
+                .map(|c| struct_map(vec![("name", string(&c.name)?), ("weight", u32(c.weight))]))
+                .collect::<Result<_, _>>()?;
+            vec(vec![
+                symbol("Weighted")?,
+                struct_map(vec![
+                    ("price_weight", u32(weighted.price_weight)),
+                    ("criteria", vec(criteria)?),
+                    ("evaluator", address(&weighted.evaluator)?),
+                ])?,
+            ])
+        }
+    }
+}
+
+fn to_evaluation(val: &ScVal) -> Result<Evaluation, ScValError> {
+    let items = match val {
+        ScVal::Vec(Some(ScVec(items))) if !items.is_empty() => items,
+        other => return Err(unexpected("enum variant", other)),
+    };
+    match (&items[0], items.get(1)) {
+        (ScVal::Symbol(name), None) => match name.0.as_slice() {
+            b"LowestPrice" => Ok(Evaluation::LowestPrice),
+            b"HighestPrice" => Ok(Evaluation::HighestPrice),
+            b"Vickrey" => Ok(Evaluation::Vickrey),
+            _ => Err(ScValError("unknown evaluation method".to_string())),
+        },
+        (ScVal::Symbol(name), Some(weighted)) if name.0.as_slice() == b"Weighted" => {
+            let fields = Fields::from(weighted)?;
+            Ok(Evaluation::Weighted(WeightedCriteria {
+                price_weight: to_u32(fields.get("price_weight")?)?,
+                criteria: to_vec(fields.get("criteria")?, |c| {
+                    let fields = Fields::from(c)?;
+                    Ok(Criterion {
+                        name: to_string(fields.get("name")?)?,
+                        weight: to_u32(fields.get("weight")?)?,
+                    })
+                })?,
+                evaluator: to_address(fields.get("evaluator")?)?,
+            }))
+        }
+        _ => Err(ScValError("unknown evaluation method".to_string())),
+    }
+}
+
+/// `Eligibility::Open`, `Registered` or `InviteOnly(Vec<Address>)`
+pub fn eligibility(eligibility: &Eligibility) -> Result<ScVal, ScValError> {
+    match eligibility {
+        Eligibility::Open => unit_enum("Open"),
+        Eligibility::Registered => unit_enum("Registered"),
+        Eligibility::InviteOnly(invited) => {
+            let invited = invited.iter().map(|a| address(a)).collect::<Result<_, _>>()?;
+            vec(vec![symbol("InviteOnly")?, vec(invited)?])
+        }
+    }
+}
+
+fn to_eligibility(val: &ScVal) -> Result<Eligibility, ScValError> {
+    let items = match val {
+        ScVal::Vec(Some(ScVec(items))) if !items.is_empty() => items,
+        other => return Err(unexpected("enum variant", other)),
+    };
+    match (&items[0], items.get(1)) {
+        (ScVal::Symbol(name), None) => match name.0.as_slice() {
+            b"Open" => Ok(Eligibility::Open),
+            b"Registered" => Ok(Eligibility::Registered),
+            _ => Err(ScValError("unknown eligibility".to_string())),
+        },
+        (ScVal::Symbol(name), Some(invited)) if name.0.as_slice() == b"InviteOnly" => {
+            Ok(Eligibility::InviteOnly(to_vec(invited, to_address)?))
+        }
+        _ => Err(ScValError("unknown eligibility".to_string())),
+    }
+}
+
+/// The contract's `TenderRules` argument of `create_tender`
+pub fn tender_rules(req: &CreateTenderRequest) -> Result<ScVal, ScValError> {
+    struct_map(vec![
+        ("opening_mode", unit_enum(req.opening_mode.as_str())?),
+        ("bond", bond_policy(req.bond.as_ref())?),
+        ("evaluation", evaluation(&req.evaluation)?),
+        ("tie_break", unit_enum(req.tie_break.as_str())?),
+        ("lots", lots(&req.lots)?),
+        ("max_lots_per_bidder", u32(req.max_lots_per_bidder)),
+        ("eligibility", eligibility(&req.eligibility)?),
+        ("max_strikes", u32(req.max_strikes)),
+    ])
+}
+
+fn lots(lots: &[Lot]) -> Result<ScVal, ScValError> {
+    let lots = lots
+        .iter()
+        .map(|lot| struct_map(vec![("title", string(&lot.title)?), ("min_bid", i128(lot.min_bid))]))
+        .collect::<Result<_, _>>()?;
+    vec(lots)
+}
+
+fn to_lots(val: &ScVal) -> Result<Vec<Lot>, ScValError> {
+    to_vec(val, |lot| {
+        let fields = Fields::from(lot)?;
+        Ok(Lot {
+            title: to_string(fields.get("title")?)?,
+            min_bid: to_i128(fields.get("min_bid")?)?,
+        })
+    })
+}
+
+fn to_bond_policy(val: &ScVal) -> Result<Option<BidBond>, ScValError> {
+    let items = match val {
+        ScVal::Vec(Some(ScVec(items))) if !items.is_empty() => items,
+        other => return Err(unexpected("enum variant", other)),
+    };
+    match (&items[0], items.get(1)) {
+        (ScVal::Symbol(name), None) if name.0.as_slice() == b"NotRequired" => Ok(None),
+        (ScVal::Symbol(name), Some(bond)) if name.0.as_slice() == b"Required" => {
+            let fields = Fields::from(bond)?;
+            Ok(Some(BidBond {
+                token: to_address(fields.get("token")?)?,
+                amount: to_i128(fields.get("amount")?)?,
+                beneficiary: to_address(fields.get("beneficiary")?)?,
+            }))
+        }
+        _ => Err(ScValError("unknown bond policy".to_string())),
+    }
+}
+
+fn to_milestone_status(val: &ScVal) -> Result<MilestoneStatus, ScValError> {
+    let name = to_unit_enum(val)?;
+    MilestoneStatus::parse(&name).ok_or_else(|| ScValError(format!("unknown milestone status `{}`", name)))
+}
+
+fn to_tie_break(val: &ScVal) -> Result<TieBreak, ScValError> {
+    let name = to_unit_enum(val)?;
+    TieBreak::parse(&name).ok_or_else(|| ScValError(format!("unknown tie-break `{}`", name)))
+}
+
+fn to_opening_mode(val: &ScVal) -> Result<OpeningMode, ScValError> {
+    let name = to_unit_enum(val)?;
+    OpeningMode::parse(&name).ok_or_else(|| ScValError(format!("unknown opening mode `{}`", name)))
+}
+
+pub fn to_option<T>(
+    val: &ScVal,
+    decode: impl Fn(&ScVal) -> Result<T, ScValError>,
+) -> Result<Option<T>, ScValError> {
+    match val {
+        ScVal::Void => Ok(None),
+        other => decode(other).map(Some),
+    }
+}
+
+pub fn to_vec<T>(
+    val: &ScVal,
+    decode: impl Fn(&ScVal) -> Result<T, ScValError>,
+) -> Result<Vec<T>, ScValError> {
+    match val {
+        ScVal::Vec(Some(ScVec(items))) => items.iter().map(decode).collect(),
+        ScVal::Vec(None) => Ok(vec![]),
+        other => Err(unexpected("vec", other)),
+    }
+}
+
+fn unexpected(expected: &str, got: &ScVal) -> ScValError {
+    ScValError(format!("expected {}, got {:?}", expected, got.discriminant()))
+}
+
+/// Build a contract struct value; entries are sorted the way the host expects
+fn struct_map(fields: Vec<(&str, ScVal)>) -> Result<ScVal, ScValError> {
+    let mut fields = fields;
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+    let entries = fields
+        .into_iter()
+        .map(|(name, val)| Ok(ScMapEntry { key: symbol(name)?, val }))
+        .collect::<Result<Vec<_>, ScValError>>()?;
+    Ok(ScVal::Map(Some(ScMap(entries.try_into()?))))
+}
+
+/// Field lookup on a decoded contract struct
+struct Fields<'a>(&'a ScMap);
+
+impl<'a> Fields<'a> {
+    fn from(val: &'a ScVal) -> Result<Self, ScValError> {
+        match val {
+            ScVal::Map(Some(map)) => Ok(Fields(map)),
+            other => Err(unexpected("map", other)),
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<&'a ScVal, ScValError> {
+        self.0
+            .iter()
+            .find(|entry| matches!(&entry.key, ScVal::Symbol(s) if s.0.as_slice() == name.as_bytes()))
+            .map(|entry| &entry.val)
+            .ok_or_else(|| ScValError(format!("missing field `{}`", name)))
+    }
+}
+
+impl TryFrom<&ScVal> for Tender {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Tender {
+            id: to_u64(fields.get("id")?)?,
+            creator: to_address(fields.get("creator")?)?,
+            title: to_string(fields.get("title")?)?,
+            description: to_string(fields.get("description")?)?,
+            ipfs_hash: to_string(fields.get("ipfs_hash")?)?,
+            deadline: to_u64(fields.get("deadline")?)?,
+            reveal_deadline: to_u64(fields.get("reveal_deadline")?)?,
+            min_bid: to_i128(fields.get("min_bid")?)?,
+            is_closed: to_bool(fields.get("is_closed")?)?,
+            created_at: to_u64(fields.get("created_at")?)?,
+            encryption_key: to_bytes_hex(fields.get("encryption_key")?)?,
+            opening_mode: to_opening_mode(fields.get("opening_mode")?)?,
+            bond: to_bond_policy(fields.get("bond")?)?,
+            evaluation: to_evaluation(fields.get("evaluation")?)?,
+            tie_break: to_tie_break(fields.get("tie_break")?)?,
+            lots: to_lots(fields.get("lots")?)?,
+            max_lots_per_bidder: to_u32(fields.get("max_lots_per_bidder")?)?,
+            eligibility: to_eligibility(fields.get("eligibility")?)?,
+            max_strikes: to_u32(fields.get("max_strikes")?)?,
+            is_cancelled: to_bool(fields.get("is_cancelled")?)?,
+            is_draft: to_bool(fields.get("is_draft")?)?,
+            award_pending: to_bool(fields.get("award_pending")?)?,
+        })
+    }
+}
+
+impl TryFrom<&Tender> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(tender: &Tender) -> Result<Self, ScValError> {
+        struct_map(vec![
+            ("id", u64(tender.id)),
+            ("creator", address(&tender.creator)?),
+            ("title", string(&tender.title)?),
+            ("description", string(&tender.description)?),
+            ("ipfs_hash", string(&tender.ipfs_hash)?),
+            ("deadline", u64(tender.deadline)),
+            ("reveal_deadline", u64(tender.reveal_deadline)),
+            ("min_bid", i128(tender.min_bid)),
+            ("is_closed", ScVal::Bool(tender.is_closed)),
+            ("created_at", u64(tender.created_at)),
+            ("encryption_key", bytes32_hex(&tender.encryption_key)?),
+            ("opening_mode", unit_enum(tender.opening_mode.as_str())?),
+            ("bond", bond_policy(tender.bond.as_ref())?),
+            ("evaluation", evaluation(&tender.evaluation)?),
+            ("tie_break", unit_enum(tender.tie_break.as_str())?),
+            ("lots", lots(&tender.lots)?),
+            ("max_lots_per_bidder", u32(tender.max_lots_per_bidder)),
+            ("eligibility", eligibility(&tender.eligibility)?),
+            ("max_strikes", u32(tender.max_strikes)),
+            ("is_cancelled", ScVal::Bool(tender.is_cancelled)),
+            ("is_draft", ScVal::Bool(tender.is_draft)),
+            ("award_pending", ScVal::Bool(tender.award_pending)),
+        ])
+    }
+}
+
+impl TryFrom<&ScVal> for Amendment {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Amendment {
+            description: to_string(fields.get("description")?)?,
+            ipfs_hash: to_string(fields.get("ipfs_hash")?)?,
+            deadline: to_u64(fields.get("deadline")?)?,
+            reveal_deadline: to_u64(fields.get("reveal_deadline")?)?,
+            amended_at: to_u64(fields.get("amended_at")?)?,
+        })
+    }
+}
+
+impl TryFrom<&Amendment> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(amendment: &Amendment) -> Result<Self, ScValError> {
+        struct_map(vec![
+            ("description", string(&amendment.description)?),
+            ("ipfs_hash", string(&amendment.ipfs_hash)?),
+            ("deadline", u64(amendment.deadline)),
+            ("reveal_deadline", u64(amendment.reveal_deadline)),
+            ("amended_at", u64(amendment.amended_at)),
+        ])
+    }
+}
+
+impl TryFrom<&ScVal> for Cancellation {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Cancellation {
+            cancelled_by: to_address(fields.get("cancelled_by")?)?,
+            reason: to_string(fields.get("reason")?)?,
+            cancelled_at: to_u64(fields.get("cancelled_at")?)?,
+        })
+    }
+}
+
+impl TryFrom<&ScVal> for Bid {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Bid {
+            bidder: to_address(fields.get("bidder")?)?,
+            tender_id: to_u64(fields.get("tender_id")?)?,
+            encrypted_amount: to_string(fields.get("encrypted_amount")?)?,
+            commitment: to_bytes_hex(fields.get("commitment")?)?,
+            revealed_amount: to_option(fields.get("revealed_amount")?, to_i128)?,
+            is_valid: to_bool(fields.get("is_valid")?)?,
+            timestamp: to_u64(fields.get("timestamp")?)?,
+            bond: to_i128(fields.get("bond")?)?,
+        })
+    }
+}
+
+impl TryFrom<&Bid> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(bid: &Bid) -> Result<Self, ScValError> {
+        struct_map(vec![
+            ("bidder", address(&bid.bidder)?),
+            ("tender_id", u64(bid.tender_id)),
+            ("encrypted_amount", string(&bid.encrypted_amount)?),
+            ("commitment", bytes32_hex(&bid.commitment)?),
+            ("revealed_amount", bid.revealed_amount.map_or(ScVal::Void, i128)),
+            ("is_valid", ScVal::Bool(bid.is_valid)),
+            ("timestamp", u64(bid.timestamp)),
+            ("bond", i128(bid.bond)),
+        ])
+    }
+}
+
+impl TryFrom<&ScVal> for Winner {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Winner {
+            tender_id: to_u64(fields.get("tender_id")?)?,
+            bidder: to_address(fields.get("bidder")?)?,
+            amount: to_i128(fields.get("amount")?)?,
+            payable: to_i128(fields.get("payable")?)?,
+            selected_at: to_u64(fields.get("selected_at")?)?,
+            lot: to_option(fields.get("lot")?, to_u32)?,
+        })
+    }
+}
+
+impl TryFrom<&Winner> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(winner: &Winner) -> Result<Self, ScValError> {
+        struct_map(vec![
+            ("tender_id", u64(winner.tender_id)),
+            ("bidder", address(&winner.bidder)?),
+            ("amount", i128(winner.amount)),
+            ("payable", i128(winner.payable)),
+            ("selected_at", u64(winner.selected_at)),
+            ("lot", winner.lot.map_or(ScVal::Void, u32)),
+        ])
+    }
+}
+
+impl TryFrom<&ScVal> for Committee {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Committee {
+            members: to_vec(fields.get("members")?, to_address)?,
+            share_hashes: to_vec(fields.get("share_hashes")?, to_bytes_hex)?,
+            threshold: to_u32(fields.get("threshold")?)?,
+        })
+    }
+}
+
+impl TryFrom<&ScVal> for Debarment {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Debarment {
+            bidder: to_address(fields.get("bidder")?)?,
+            reason: to_string(fields.get("reason")?)?,
+            start: to_u64(fields.get("start")?)?,
+            end: to_u64(fields.get("end")?)?,
+            debarred_by: to_address(fields.get("debarred_by")?)?,
+            lifted_at: to_option(fields.get("lifted_at")?, to_u64)?,
+        })
+    }
+}
+
+impl TryFrom<&ScVal> for Registration {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        let status = to_unit_enum(fields.get("status")?)?;
+        Ok(Registration {
+            credentials_hash: to_string(fields.get("credentials_hash")?)?,
+            status: RegistrationStatus::parse(&status)
+                .ok_or_else(|| ScValError(format!("unknown registration status `{}`", status)))?,
+            registered_at: to_u64(fields.get("registered_at")?)?,
+            verified_by: to_option(fields.get("verified_by")?, to_address)?,
+            expires_at: to_u64(fields.get("expires_at")?)?,
+        })
+    }
+}
+
+impl TryFrom<&ScVal> for StrikeRecord {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(StrikeRecord {
+            count: to_u32(fields.get("count")?)?,
+            last_tender: to_u64(fields.get("last_tender")?)?,
+            last_struck_at: to_u64(fields.get("last_struck_at")?)?,
+        })
+    }
+}
+
+impl TryFrom<&ScVal> for BidderOutcome {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        let outcome = to_unit_enum(fields.get("outcome")?)?;
+        Ok(BidderOutcome {
+            bidder: to_address(fields.get("bidder")?)?,
+            outcome: RevealOutcome::parse(&outcome)
+                .ok_or_else(|| ScValError(format!("unknown reveal outcome `{}`", outcome)))?,
+        })
+    }
+}
+
+impl TryFrom<&ScVal> for ApprovalPolicy {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(ApprovalPolicy {
+            approvers: to_vec(fields.get("approvers")?, to_address)?,
+            threshold: to_u32(fields.get("threshold")?)?,
+        })
+    }
+}
+
+impl TryFrom<&ApprovalPolicy> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(policy: &ApprovalPolicy) -> Result<Self, ScValError> {
+        let approvers = policy.approvers.iter().map(|a| address(a)).collect::<Result<_, _>>()?;
+        struct_map(vec![("approvers", vec(approvers)?), ("threshold", u32(policy.threshold))])
+    }
+}
+
+impl TryFrom<&ScVal> for Approval {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Approval {
+            approver: to_address(fields.get("approver")?)?,
+            approved_at: to_u64(fields.get("approved_at")?)?,
+        })
+    }
+}
+
+impl TryFrom<&Committee> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(committee: &Committee) -> Result<Self, ScValError> {
+        let members = committee.members.iter().map(|m| address(m)).collect::<Result<_, _>>()?;
+        let hashes = committee.share_hashes.iter().map(|h| bytes32_hex(h)).collect::<Result<_, _>>()?;
+        struct_map(vec![
+            ("members", vec(members)?),
+            ("share_hashes", vec(hashes)?),
+            ("threshold", u32(committee.threshold)),
+        ])
+    }
+}
+
+impl TryFrom<&BidOpening> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(opening: &BidOpening) -> Result<Self, ScValError> {
+        struct_map(vec![
+            ("bidder", address(&opening.bidder)?),
+            ("amount", i128(opening.amount)),
+            ("salt", bytes32_hex(&opening.salt)?),
+        ])
+    }
+}
+
+impl TryFrom<&ScVal> for KeyShare {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(KeyShare {
+            member: to_address(fields.get("member")?)?,
+            share: to_bytes_hex(fields.get("share")?)?,
+            submitted_at: to_u64(fields.get("submitted_at")?)?,
+        })
+    }
+}
+
+impl TryFrom<&ScVal> for Scorecard {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Scorecard {
+            bidder: to_address(fields.get("bidder")?)?,
+            amount: to_i128(fields.get("amount")?)?,
+            price_score: to_i128(fields.get("price_score")?)?,
+            criteria_scores: to_vec(fields.get("criteria_scores")?, to_i128)?,
+            total: to_i128(fields.get("total")?)?,
+        })
+    }
+}
+
+impl TryFrom<&MilestoneTerms> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(terms: &MilestoneTerms) -> Result<Self, ScValError> {
+        struct_map(vec![
+            ("description", string(&terms.description)?),
+            ("percentage", u32(terms.percentage)),
+            ("due_date", u64(terms.due_date)),
+        ])
+    }
+}
+
+impl TryFrom<&ScVal> for Milestone {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Milestone {
+            description: to_string(fields.get("description")?)?,
+            percentage: to_u32(fields.get("percentage")?)?,
+            due_date: to_u64(fields.get("due_date")?)?,
+            amount: to_i128(fields.get("amount")?)?,
+            status: to_milestone_status(fields.get("status")?)?,
+        })
+    }
+}
+
+impl TryFrom<&Milestone> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(milestone: &Milestone) -> Result<Self, ScValError> {
+        struct_map(vec![
+            ("description", string(&milestone.description)?),
+            ("percentage", u32(milestone.percentage)),
+            ("due_date", u64(milestone.due_date)),
+            ("amount", i128(milestone.amount)),
+            ("status", unit_enum(milestone.status.as_str())?),
+        ])
+    }
+}
+
+impl TryFrom<&ScVal> for Escrow {
+    type Error = ScValError;
+
+    fn try_from(val: &ScVal) -> Result<Self, ScValError> {
+        let fields = Fields::from(val)?;
+        Ok(Escrow {
+            token: to_address(fields.get("token")?)?,
+            amount: to_i128(fields.get("amount")?)?,
+            inspector: to_option(fields.get("inspector")?, to_address)?,
+            milestones: to_vec(fields.get("milestones")?, |v| Milestone::try_from(v))?,
+            funded_at: to_u64(fields.get("funded_at")?)?,
+        })
+    }
+}
+
+impl TryFrom<&Escrow> for ScVal {
+    type Error = ScValError;
+
+    fn try_from(escrow: &Escrow) -> Result<Self, ScValError> {
+        let milestones = escrow.milestones.iter().map(ScVal::try_from).collect::<Result<_, _>>()?;
+        struct_map(vec![
+            ("token", address(&escrow.token)?),
+            ("amount", i128(escrow.amount)),
+            ("inspector", escrow.inspector.as_deref().map_or(Ok(ScVal::Void), address)?),
+            ("milestones", vec(milestones)?),
+            ("funded_at", u64(escrow.funded_at)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M";
+
+    fn sample_tender() -> Tender {
+        Tender {
+            id: 7,
+            creator: ADDRESS.to_string(),
+            title: "Road works".to_string(),
+            description: "Resurfacing".to_string(),
+            ipfs_hash: "QmHash".to_string(),
+            deadline: 1_000,
+            reveal_deadline: 2_000,
+            min_bid: 100_000,
+            is_closed: false,
+            created_at: 500,
+            encryption_key: "0707070707070707070707070707070707070707070707070707070707070707".to_string(),
+            opening_mode: OpeningMode::TimeLock,
+            bond: Some(BidBond {
+                token: ADDRESS.to_string(),
+                amount: 500,
+                beneficiary: ADDRESS.to_string(),
+            }),
+            evaluation: Evaluation::Weighted(WeightedCriteria {
+                price_weight: 40,
+                criteria: vec![Criterion {
+                    name: "Quality".to_string(),
+                    weight: 60,
+                }],
+                evaluator: ADDRESS.to_string(),
+            }),
+            tie_break: TieBreak::Lottery,
+            lots: vec![
+                Lot {
+                    title: "Materials".to_string(),
+                    min_bid: 50_000,
+                },
+                Lot {
+                    title: "Labour".to_string(),
+                    min_bid: 20_000,
+                },
+            ],
+            max_lots_per_bidder: 1,
+            eligibility: Eligibility::InviteOnly(vec![ADDRESS.to_string()]),
+            max_strikes: 2,
+            is_cancelled: true,
+            is_draft: false,
+            award_pending: false,
+        }
+    }
+
+    #[test]
+    fn test_tender_roundtrip_through_xdr() {
+        let tender = sample_tender();
+        let encoded = to_base64(&ScVal::try_from(&tender).unwrap()).unwrap();
+        let decoded = Tender::try_from(&from_base64::<ScVal>(&encoded).unwrap()).unwrap();
+
+        assert_eq!(decoded.id, tender.id);
+        assert_eq!(decoded.creator, tender.creator);
+        assert_eq!(decoded.title, tender.title);
+        assert_eq!(decoded.min_bid, tender.min_bid);
+        assert_eq!(decoded.reveal_deadline, tender.reveal_deadline);
+        assert_eq!(decoded.encryption_key, tender.encryption_key);
+        assert_eq!(decoded.opening_mode, OpeningMode::TimeLock);
+        assert_eq!(decoded.bond, tender.bond);
+        assert_eq!(decoded.evaluation, tender.evaluation);
+        assert_eq!(decoded.tie_break, TieBreak::Lottery);
+        assert_eq!(decoded.lots, tender.lots);
+        assert_eq!(decoded.max_lots_per_bidder, 1);
+        assert_eq!(decoded.eligibility, tender.eligibility);
+        assert_eq!(decoded.max_strikes, 2);
+        assert!(decoded.is_cancelled);
+
+        let unbonded = Tender { bond: None, ..tender };
+        let encoded = to_base64(&ScVal::try_from(&unbonded).unwrap()).unwrap();
+        assert_eq!(Tender::try_from(&from_base64::<ScVal>(&encoded).unwrap()).unwrap().bond, None);
+    }
+
+    #[test]
+    fn test_committee_roundtrip_through_xdr() {
+        let committee = Committee {
+            members: vec![ADDRESS.to_string()],
+            share_hashes: vec!["ab".repeat(32)],
+            threshold: 1,
+        };
+        let encoded = to_base64(&ScVal::try_from(&committee).unwrap()).unwrap();
+        let decoded = Committee::try_from(&from_base64::<ScVal>(&encoded).unwrap()).unwrap();
+
+        assert_eq!(decoded.members, committee.members);
+        assert_eq!(decoded.share_hashes, committee.share_hashes);
+        assert_eq!(decoded.threshold, 1);
+    }
+
+    #[test]
+    fn test_escrow_roundtrip_through_xdr() {
+        let milestone = |status| Milestone {
+            description: "Foundations".to_string(),
+            percentage: 50,
+            due_date: 3_000,
+            amount: 500,
+            status,
+        };
+        let mut escrow = Escrow {
+            token: ADDRESS.to_string(),
+            amount: 1_000,
+            inspector: Some(ADDRESS.to_string()),
+            milestones: vec![milestone(MilestoneStatus::Released), milestone(MilestoneStatus::Pending)],
+            funded_at: 2_500,
+        };
+        for _ in 0..2 {
+            let encoded = to_base64(&ScVal::try_from(&escrow).unwrap()).unwrap();
+            assert_eq!(Escrow::try_from(&from_base64::<ScVal>(&encoded).unwrap()).unwrap(), escrow);
+            escrow.inspector = None;
+        }
+    }
+
+    #[test]
+    fn test_struct_keys_are_sorted() {
+        let ScVal::Map(Some(map)) = ScVal::try_from(&sample_tender()).unwrap() else {
+            panic!("tender should encode as a map");
+        };
+        let keys: Vec<String> = map
+            .iter()
+            .map(|entry| match &entry.key {
+                ScVal::Symbol(s) => s.0.to_utf8_string_lossy(),
+                _ => panic!("keys must be symbols"),
+            })
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn test_bid_optional_amount() {
+        let mut bid = Bid {
+            bidder: ADDRESS.to_string(),
+            tender_id: 3,
+            encrypted_amount: "abcd".to_string(),
+            commitment: "11".repeat(32),
+            revealed_amount: None,
+            is_valid: false,
+            timestamp: 10,
+            bond: 0,
+        };
+        let decoded = Bid::try_from(&ScVal::try_from(&bid).unwrap()).unwrap();
+        assert_eq!(decoded.revealed_amount, None);
+        assert_eq!(decoded.commitment, bid.commitment);
+
+        bid.revealed_amount = Some(-5);
+        let decoded = Bid::try_from(&ScVal::try_from(&bid).unwrap()).unwrap();
+        assert_eq!(decoded.revealed_amount, Some(-5));
+    }
+
+    #[test]
+    fn test_debarment_decodes() {
+        let val = struct_map(vec![
+            ("bidder", address(ADDRESS).unwrap()),
+            ("reason", string("Bid rigging").unwrap()),
+            ("start", u64(1_000)),
+            ("end", u64(5_000)),
+            ("debarred_by", address(ADDRESS).unwrap()),
+            ("lifted_at", u64(3_000)),
+        ])
+        .unwrap();
+        let encoded = to_base64(&vec(vec![val]).unwrap()).unwrap();
+
+        let debarments = to_vec(&from_base64::<ScVal>(&encoded).unwrap(), |v| Debarment::try_from(v)).unwrap();
+        assert_eq!(debarments.len(), 1);
+        assert_eq!(debarments[0].reason, "Bid rigging");
+        assert_eq!((debarments[0].start, debarments[0].end), (1_000, 5_000));
+        assert_eq!(debarments[0].lifted_at, Some(3_000));
+    }
+
+    #[test]
+    fn test_reveal_outcomes_decode() {
+        let val = struct_map(vec![
+            ("bidder", address(ADDRESS).unwrap()),
+            ("outcome", unit_enum("Unrevealed").unwrap()),
+        ])
+        .unwrap();
+
+        let outcomes = to_vec(&vec(vec![val]).unwrap(), |v| BidderOutcome::try_from(v)).unwrap();
+        assert_eq!(outcomes[0].bidder, ADDRESS);
+        assert_eq!(outcomes[0].outcome, RevealOutcome::Unrevealed);
+
+        let bad = struct_map(vec![
+            ("bidder", address(ADDRESS).unwrap()),
+            ("outcome", unit_enum("Forfeited").unwrap()),
+        ])
+        .unwrap();
+        assert!(BidderOutcome::try_from(&bad).is_err());
+    }
+
+    #[test]
+    fn test_winner_large_amount() {
+        let winner = Winner {
+            tender_id: 1,
+            bidder: ADDRESS.to_string(),
+            amount: i128::MAX - 1,
+            payable: i128::MAX - 2,
+            selected_at: 99,
+            lot: Some(2),
+        };
+        let decoded = Winner::try_from(&ScVal::try_from(&winner).unwrap()).unwrap();
+        assert_eq!(decoded.amount, winner.amount);
+        assert_eq!(decoded.bidder, winner.bidder);
+        assert_eq!(decoded.lot, Some(2));
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_shape() {
+        assert!(Tender::try_from(&ScVal::U64(1)).is_err());
+        assert!(to_u64(&ScVal::Bool(true)).is_err());
+        assert!(bytes32_hex("abcd").is_err());
+    }
+}
@@ -0,0 +1,268 @@
+// backend/src/services/sealed.rs
+
+//! Bids sealed by the bidder to the tender authority's X25519 key
+//! (ECIES: ephemeral X25519 + HKDF-SHA256 + AES-256-GCM). The backend can
+//! check an envelope's shape and recipient, but opening one needs the
+//! authority's secret key, which is only handed over after bidding closes.
+//!
+//! Envelope, base64: "OS" || version || recipient pubkey (32)
+//!     || ephemeral pubkey (32) || nonce (12) || AES-GCM(plaintext)
+//! Everything before the nonce is authenticated as associated data. The
+//! plaintext is the amount (i128 LE), followed for time-lock tenders by the
+//! 32-byte commitment salt so whoever holds the key can open the commitment.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose, Engine as _};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::services::encryption::EncryptionError;
+
+const MAGIC: [u8; 2] = *b"OS";
+const FORMAT_V1: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 32 + 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const AMOUNT_LEN: usize = 16;
+const SALT_LEN: usize = 32;
+
+/// A parsed, not yet opened, sealed amount
+pub struct SealedAmount {
+    bytes: Vec<u8>,
+}
+
+impl SealedAmount {
+    pub fn parse(sealed_base64: &str) -> Result<Self, EncryptionError> {
+        let bytes = general_purpose::STANDARD.decode(sealed_base64)?;
+        let plaintext_len = bytes.len().checked_sub(HEADER_LEN + NONCE_LEN + TAG_LEN);
+        if plaintext_len != Some(AMOUNT_LEN) && plaintext_len != Some(AMOUNT_LEN + SALT_LEN) {
+            return Err(EncryptionError::InvalidData("sealed amount has the wrong length"));
+        }
+        if bytes[..2] != MAGIC || bytes[2] != FORMAT_V1 {
+            return Err(EncryptionError::InvalidData("not a sealed amount envelope"));
+        }
+        let sealed = Self { bytes };
+        if sealed.ephemeral() == [0u8; 32] {
+            return Err(EncryptionError::InvalidData("invalid ephemeral key"));
+        }
+        Ok(sealed)
+    }
+
+    /// Authority key the amount was sealed to
+    pub fn recipient(&self) -> [u8; 32] {
+        self.bytes[3..35].try_into().expect("length checked in parse")
+    }
+
+    fn ephemeral(&self) -> [u8; 32] {
+        self.bytes[35..HEADER_LEN].try_into().expect("length checked in parse")
+    }
+
+    fn header(&self) -> &[u8] {
+        &self.bytes[..HEADER_LEN]
+    }
+
+    fn nonce(&self) -> Nonce<aes_gcm::aead::consts::U12> {
+        let nonce: [u8; NONCE_LEN] = self.bytes[HEADER_LEN..HEADER_LEN + NONCE_LEN]
+            .try_into()
+            .expect("length checked in parse");
+        Nonce::from(nonce)
+    }
+
+    fn ciphertext(&self) -> &[u8] {
+        &self.bytes[HEADER_LEN + NONCE_LEN..]
+    }
+}
+
+/// Check that `sealed_base64` is a well-formed envelope addressed to
+/// `authority_key`. This cannot prove the amount itself; that is what the
+/// commitment checked by `reveal_bid` is for.
+pub fn verify_sealed_amount(
+    sealed_base64: &str,
+    authority_key: &[u8; 32],
+) -> Result<(), EncryptionError> {
+    let sealed = SealedAmount::parse(sealed_base64)?;
+    if &sealed.recipient() != authority_key {
+        return Err(EncryptionError::InvalidData("sealed to a different authority key"));
+    }
+    Ok(())
+}
+
+/// Open a sealed amount with the authority's secret key
+pub fn open_sealed_amount(
+    sealed_base64: &str,
+    tender_id: u64,
+    authority_secret: &[u8; 32],
+) -> Result<i128, EncryptionError> {
+    open_sealed_bid(sealed_base64, tender_id, authority_secret).map(|(amount, _)| amount)
+}
+
+/// Open a sealed bid, returning the amount and, if the bidder sealed it
+/// too, the commitment salt
+pub fn open_sealed_bid(
+    sealed_base64: &str,
+    tender_id: u64,
+    authority_secret: &[u8; 32],
+) -> Result<(i128, Option<[u8; SALT_LEN]>), EncryptionError> {
+    let sealed = SealedAmount::parse(sealed_base64)?;
+    let secret = StaticSecret::from(*authority_secret);
+    if PublicKey::from(&secret).to_bytes() != sealed.recipient() {
+        return Err(EncryptionError::InvalidData("secret does not match the sealing key"));
+    }
+
+    let shared = secret.diffie_hellman(&PublicKey::from(sealed.ephemeral()));
+    if !shared.was_contributory() {
+        return Err(EncryptionError::InvalidData("invalid ephemeral key"));
+    }
+
+    let key = derive_key(shared.as_bytes(), sealed.header(), tender_id)?;
+    let plaintext = Aes256Gcm::new_from_slice(&key)?.decrypt(
+        &sealed.nonce(),
+        Payload {
+            msg: sealed.ciphertext(),
+            aad: sealed.header(),
+        },
+    )?;
+    let (amount, salt) = plaintext.split_at(AMOUNT_LEN);
+    let amount: [u8; AMOUNT_LEN] = amount
+        .try_into()
+        .map_err(|_| EncryptionError::InvalidData("invalid plaintext length"))?;
+    let salt = match salt.len() {
+        0 => None,
+        _ => Some(
+            salt.try_into()
+                .map_err(|_| EncryptionError::InvalidData("invalid plaintext length"))?,
+        ),
+    };
+    Ok((i128::from_le_bytes(amount), salt))
+}
+
+/// Fresh X25519 secret key for a tender authority
+pub fn generate_secret() -> [u8; 32] {
+    StaticSecret::random_from_rng(rand::rngs::OsRng).to_bytes()
+}
+
+/// X25519 public key for a secret, e.g. to check it against a tender
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    PublicKey::from(&StaticSecret::from(*secret)).to_bytes()
+}
+
+/// Parse a hex-encoded 32-byte X25519 key
+pub fn parse_key_hex(value: &str) -> Result<[u8; 32], EncryptionError> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(EncryptionError::InvalidData("key must be 32 hex-encoded bytes"))
+}
+
+/// AES key for one envelope. The salt binds both public keys; the info
+/// binds the tender, so an envelope cannot be replayed against another.
+fn derive_key(shared: &[u8], header: &[u8], tender_id: u64) -> Result<[u8; 32], EncryptionError> {
+    let info = format!("opentender/sealed-bid/v1/tender/{}", tender_id);
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&header[3..]), shared)
+        .expand(info.as_bytes(), &mut key)
+        .map_err(|_| EncryptionError::InvalidData("key derivation failed"))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    /// What a bidder's client does: seal `amount` to the authority key
+    fn seal(amount: i128, tender_id: u64, authority_key: &[u8; 32]) -> String {
+        seal_plaintext(&amount.to_le_bytes(), tender_id, authority_key)
+    }
+
+    fn seal_plaintext(plaintext: &[u8], tender_id: u64, authority_key: &[u8; 32]) -> String {
+        let ephemeral = StaticSecret::random_from_rng(OsRng);
+        let shared = ephemeral.diffie_hellman(&PublicKey::from(*authority_key));
+
+        let mut envelope = MAGIC.to_vec();
+        envelope.push(FORMAT_V1);
+        envelope.extend_from_slice(authority_key);
+        envelope.extend_from_slice(PublicKey::from(&ephemeral).as_bytes());
+
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let key = derive_key(shared.as_bytes(), &envelope, tender_id).unwrap();
+        let ciphertext = Aes256Gcm::new_from_slice(&key)
+            .unwrap()
+            .encrypt(
+                &Nonce::from(nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &envelope,
+                },
+            )
+            .unwrap();
+        envelope.extend_from_slice(&nonce);
+        envelope.extend_from_slice(&ciphertext);
+        general_purpose::STANDARD.encode(envelope)
+    }
+
+    fn authority() -> ([u8; 32], [u8; 32]) {
+        let secret = [42u8; 32];
+        (secret, public_key(&secret))
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let (secret, public) = authority();
+        let sealed = seal(150_000, 7, &public);
+
+        verify_sealed_amount(&sealed, &public).unwrap();
+        assert_eq!(open_sealed_amount(&sealed, 7, &secret).unwrap(), 150_000);
+    }
+
+    #[test]
+    fn test_open_time_lock_bid_with_salt() {
+        let (secret, public) = authority();
+        let mut plaintext = 150_000i128.to_le_bytes().to_vec();
+        plaintext.extend_from_slice(&[5u8; SALT_LEN]);
+        let sealed = seal_plaintext(&plaintext, 7, &public);
+
+        assert_eq!(open_sealed_bid(&sealed, 7, &secret).unwrap(), (150_000, Some([5u8; SALT_LEN])));
+        assert_eq!(open_sealed_bid(&seal(1, 7, &public), 7, &secret).unwrap(), (1, None));
+    }
+
+    #[test]
+    fn test_envelope_is_bound_to_tender_and_authority() {
+        let (secret, public) = authority();
+        let sealed = seal(150_000, 7, &public);
+
+        assert!(open_sealed_amount(&sealed, 8, &secret).is_err());
+        assert!(open_sealed_amount(&sealed, 7, &[1u8; 32]).is_err());
+        assert!(verify_sealed_amount(&sealed, &public_key(&[1u8; 32])).is_err());
+    }
+
+    #[test]
+    fn test_tampered_envelope_is_rejected() {
+        let (secret, public) = authority();
+        let mut bytes = general_purpose::STANDARD.decode(seal(150_000, 7, &public)).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let tampered = general_purpose::STANDARD.encode(&bytes);
+
+        // Still well-formed, but fails authentication when opened
+        verify_sealed_amount(&tampered, &public).unwrap();
+        assert!(open_sealed_amount(&tampered, 7, &secret).is_err());
+    }
+
+    #[test]
+    fn test_rejects_malformed_envelopes() {
+        let (_, public) = authority();
+        assert!(verify_sealed_amount("not base64!", &public).is_err());
+        assert!(verify_sealed_amount(&general_purpose::STANDARD.encode([0u8; 10]), &public).is_err());
+
+        let mut bytes = general_purpose::STANDARD.decode(seal(1, 7, &public)).unwrap();
+        bytes[35..HEADER_LEN].fill(0);
+        assert!(SealedAmount::parse(&general_purpose::STANDARD.encode(&bytes)).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+// backend/src/services/shamir.rs
+
+//! Shamir secret sharing over GF(2^8), applied byte-wise to a 32-byte
+//! secret. A share is `x || y[0..32]` with x in 1..=255; any `threshold`
+//! distinct shares recover the secret, fewer reveal nothing about it.
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use crate::services::encryption::EncryptionError;
+
+pub const SHARE_LEN: usize = 1 + 32;
+
+/// Split `secret` into `count` shares, any `threshold` of which recover it
+pub fn split(secret: &[u8; 32], threshold: u8, count: u8) -> Result<Vec<[u8; SHARE_LEN]>, EncryptionError> {
+    if threshold == 0 || threshold > count {
+        return Err(EncryptionError::InvalidData("threshold must be between 1 and the share count"));
+    }
+
+    // One random polynomial per secret byte, constant term = the byte
+    let mut coefficients = vec![[0u8; 32]; threshold as usize];
+    coefficients[0] = *secret;
+    for coefficient in coefficients.iter_mut().skip(1) {
+        OsRng.fill_bytes(coefficient);
+    }
+
+    Ok((1..=count)
+        .map(|x| {
+            let mut share = [0u8; SHARE_LEN];
+            share[0] = x;
+            for (i, y) in share[1..].iter_mut().enumerate() {
+                // Horner's rule, highest degree first
+                *y = coefficients.iter().rev().fold(0, |acc, c| gf_mul(acc, x) ^ c[i]);
+            }
+            share
+        })
+        .collect())
+}
+
+/// Recover the secret by Lagrange interpolation at x = 0. The caller is
+/// responsible for supplying at least the threshold number of shares.
+pub fn combine(shares: &[[u8; SHARE_LEN]]) -> Result<[u8; 32], EncryptionError> {
+    if shares.is_empty() {
+        return Err(EncryptionError::InvalidData("no shares"));
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if share[0] == 0 || shares[..i].iter().any(|other| other[0] == share[0]) {
+            return Err(EncryptionError::InvalidData("share indices must be distinct and non-zero"));
+        }
+    }
+
+    let mut secret = [0u8; 32];
+    for share in shares {
+        // Basis polynomial for this share evaluated at 0: prod x_j / (x_j - x_i)
+        let mut basis = 1u8;
+        for other in shares.iter().filter(|other| other[0] != share[0]) {
+            basis = gf_mul(basis, gf_div(other[0], other[0] ^ share[0]));
+        }
+        for (byte, y) in secret.iter_mut().zip(&share[1..]) {
+            *byte ^= gf_mul(*y, basis);
+        }
+    }
+    Ok(secret)
+}
+
+/// Parse a hex-encoded share
+pub fn parse_share_hex(value: &str) -> Result<[u8; SHARE_LEN], EncryptionError> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(EncryptionError::InvalidData("share must be 33 hex-encoded bytes"))
+}
+
+// GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1. Constant time:
+// no table lookups or branches on secret data.
+
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// a / b for b != 0, via b^254 = b^-1
+fn gf_div(a: u8, b: u8) -> u8 {
+    let mut inverse = 1u8;
+    let mut power = b;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+        exponent >>= 1;
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_threshold_subset_recovers() {
+        let secret = [0xA5u8; 32];
+        let shares = split(&secret, 3, 5).unwrap();
+
+        assert_eq!(combine(&shares[..3]).unwrap(), secret);
+        assert_eq!(combine(&[shares[4], shares[1], shares[3]]).unwrap(), secret);
+        assert_eq!(combine(&shares).unwrap(), secret);
+        // Below the threshold the interpolation yields an unrelated value
+        assert_ne!(combine(&shares[..2]).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_field_arithmetic() {
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        for b in 1..=255u8 {
+            assert_eq!(gf_mul(gf_div(1, b), b), 1);
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_parameters() {
+        let secret = [1u8; 32];
+        assert!(split(&secret, 0, 3).is_err());
+        assert!(split(&secret, 4, 3).is_err());
+
+        let shares = split(&secret, 2, 3).unwrap();
+        assert!(combine(&[shares[0], shares[0]]).is_err());
+        assert!(combine(&[]).is_err());
+        assert!(parse_share_hex(&hex::encode(&shares[0][..32])).is_err());
+        assert_eq!(parse_share_hex(&hex::encode(shares[1])).unwrap(), shares[1]);
+    }
+}
@@ -1,178 +1,213 @@
 use crate::config::Config;
+use crate::models::error::TenderError;
 use crate::models::types::*;
-use std::process::Command;
-use serde_json::Value;
-
-/// Execute soroban CLI commands to interact with the contract
-
-pub async fn get_all_tenders(config: &Config) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
-    log::info!("Calling contract {} to get all tenders", config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M",
-            "--network", &config.network,
-            "--",
-            "get_all_tenders",
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to get tenders: {}", error).into());
-    }
-    
-    let result = String::from_utf8_lossy(&output.stdout);
-    parse_u64_vec(&result)
+use crate::services::rpc::{RpcClient, SimulateTransactionResponse};
+use crate::services::scval;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+use std::time::Duration;
+use stellar_xdr::curr::{
+    AccountId, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, LedgerEntryData,
+    LedgerKey, LedgerKeyAccount, Limits, Memo, MuxedAccount, Operation, OperationBody,
+    Preconditions, PublicKey, ScAddress, ScSymbol, ScVal, SequenceNumber,
+    SorobanAuthorizationEntry, SorobanTransactionData, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionMeta, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TransactionV1Envelope, VecM, WriteXdr,
+};
+
+// Talk to the contract through Soroban RPC: reads are simulated, writes are
+// simulated and handed back unsigned so the acting account signs them.
+
+/// Inclusion fee offered on top of the simulated resource fee (stroops)
+const BASE_FEE: u32 = 100;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const POLL_ATTEMPTS: u32 = 30;
+
+/// One page of tenders with id >= `start` (the contract caps `limit`)
+pub async fn get_tenders(
+    config: &Config,
+    start: u64,
+    limit: u32,
+) -> Result<Vec<Tender>, Box<dyn std::error::Error>> {
+    log::info!("Getting tenders from {} on contract {}", start, config.contract_id);
+
+    let args = vec![scval::u64(start), ScVal::U32(limit)];
+    let result = simulate_read(config, "get_tenders", args).await?;
+    Ok(scval::to_vec(&result, |v| Tender::try_from(v))?)
 }
 
 pub async fn get_tender(config: &Config, tender_id: u64) -> Result<Tender, Box<dyn std::error::Error>> {
     log::info!("Getting tender {} from contract {}", tender_id, config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M",
-            "--network", &config.network,
-            "--",
-            "get_tender",
-            "--tender_id", &tender_id.to_string(),
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to get tender: {}", error).into());
-    }
-    
-    let result = String::from_utf8_lossy(&output.stdout);
-    parse_tender(&result)
+
+    let result = simulate_read(config, "get_tender", vec![scval::u64(tender_id)]).await?;
+    Ok(Tender::try_from(&result)?)
 }
 
 pub async fn create_tender(
     config: &Config,
     req: CreateTenderRequest,
-) -> Result<u64, Box<dyn std::error::Error>> {
-    log::info!("Creating tender on contract {}", config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "moon",  // Use the admin identity
-            "--network", &config.network,
-            "--",
-            "create_tender",
-            "--creator", &req.creator,
-            "--title", &req.title,
-            "--description", &req.description,
-            "--ipfs_hash", &req.ipfs_hash,
-            "--deadline", &req.deadline.to_string(),
-            "--reveal_deadline", &req.reveal_deadline.to_string(),
-            "--min_bid", &req.min_bid.to_string(),
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        log::error!("Failed to create tender: {}", error);
-        return Err(format!("Failed to create tender: {}", error).into());
-    }
-    
-    let result = String::from_utf8_lossy(&output.stdout);
-    log::info!("Create tender result: {}", result);
-    
-    // Parse the tender ID from the output
-    result.trim().parse::<u64>()
-        .map_err(|e| format!("Failed to parse tender ID: {}", e).into())
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing create_tender for {} on contract {}", req.creator, config.contract_id);
+
+    let args = vec![
+        scval::address(&req.creator)?,
+        scval::string(&req.title)?,
+        scval::string(&req.description)?,
+        scval::string(&req.ipfs_hash)?,
+        scval::u64(req.deadline),
+        scval::u64(req.reveal_deadline),
+        scval::i128(req.min_bid),
+        scval::bytes32_hex(&req.encryption_key)?,
+        scval::tender_rules(&req)?,
+    ];
+    prepare(config, &req.creator, "create_tender", args).await
 }
 
 pub async fn submit_bid(
     config: &Config,
     req: SubmitBidRequest,
-) -> Result<(), Box<dyn std::error::Error>> {
-    log::info!("Submitting bid for tender {} on contract {}", req.tender_id, config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "moon",
-            "--network", &config.network,
-            "--",
-            "submit_bid",
-            "--tender_id", &req.tender_id.to_string(),
-            "--bidder", &req.bidder,
-            "--encrypted_amount", &req.encrypted_amount,
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to submit bid: {}", error).into());
-    }
-    
-    Ok(())
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing submit_bid for tender {} on contract {}", req.tender_id, config.contract_id);
+
+    // Bids on a lot go through submit_lot_bid, which takes the lot after the tender id
+    let mut args = vec![scval::u64(req.tender_id)];
+    args.extend(req.lot.map(scval::u32));
+    args.extend([
+        scval::address(&req.bidder)?,
+        scval::string(&req.encrypted_amount)?,
+        scval::bytes32_hex(&req.commitment)?,
+    ]);
+    let function = if req.lot.is_some() { "submit_lot_bid" } else { "submit_bid" };
+    prepare(config, &req.bidder, function, args).await
+}
+
+/// Replace a sealed bid; same request as `submit_bid`
+pub async fn replace_bid(
+    config: &Config,
+    req: SubmitBidRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing replace_bid for tender {} on contract {}", req.tender_id, config.contract_id);
+
+    let mut args = vec![scval::u64(req.tender_id)];
+    args.extend(req.lot.map(scval::u32));
+    args.extend([
+        scval::address(&req.bidder)?,
+        scval::string(&req.encrypted_amount)?,
+        scval::bytes32_hex(&req.commitment)?,
+    ]);
+    let function = if req.lot.is_some() { "replace_lot_bid" } else { "replace_bid" };
+    prepare(config, &req.bidder, function, args).await
+}
+
+pub async fn withdraw_bid(
+    config: &Config,
+    req: WithdrawBidRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing withdraw_bid for tender {} on contract {}", req.tender_id, config.contract_id);
+
+    let args = vec![scval::u64(req.tender_id), scval::address(&req.bidder)?];
+    prepare(config, &req.bidder, "withdraw_bid", args).await
 }
 
 pub async fn reveal_bid(
     config: &Config,
     req: RevealBidRequest,
-) -> Result<(), Box<dyn std::error::Error>> {
-    log::info!("Revealing bid for tender {} on contract {}", req.tender_id, config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "moon",
-            "--network", &config.network,
-            "--",
-            "reveal_bid",
-            "--tender_id", &req.tender_id.to_string(),
-            "--bidder", &req.bidder,
-            "--actual_amount", &req.actual_amount.to_string(),
-            "--_decryption_key", &req.decryption_key,
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to reveal bid: {}", error).into());
-    }
-    
-    Ok(())
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing reveal_bid for tender {} on contract {}", req.tender_id, config.contract_id);
+
+    let mut args = vec![scval::u64(req.tender_id)];
+    args.extend(req.lot.map(scval::u32));
+    args.extend([
+        scval::address(&req.bidder)?,
+        scval::i128(req.actual_amount),
+        scval::bytes32_hex(&req.salt)?,
+    ]);
+    let function = if req.lot.is_some() { "reveal_lot_bid" } else { "reveal_bid" };
+    prepare(config, &req.bidder, function, args).await
 }
 
 pub async fn close_tender(
     config: &Config,
     tender_id: u64,
     caller: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    log::info!("Closing tender {} by {} on contract {}", tender_id, caller, config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "moon",
-            "--network", &config.network,
-            "--",
-            "close_tender",
-            "--tender_id", &tender_id.to_string(),
-            "--caller", caller,
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to close tender: {}", error).into());
-    }
-    
-    Ok(())
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing close_tender {} by {} on contract {}", tender_id, caller, config.contract_id);
+
+    let args = vec![scval::u64(tender_id), scval::address(caller)?];
+    prepare(config, caller, "close_tender", args).await
+}
+
+/// Amend a tender to the given terms; see `AmendTenderRequest` for filling
+/// them from the current tender
+pub async fn amend_tender(
+    config: &Config,
+    tender_id: u64,
+    creator: &str,
+    terms: &Amendment,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing amend_tender {} on contract {}", tender_id, config.contract_id);
+
+    let args = vec![
+        scval::u64(tender_id),
+        scval::address(creator)?,
+        scval::string(&terms.description)?,
+        scval::string(&terms.ipfs_hash)?,
+        scval::u64(terms.deadline),
+        scval::u64(terms.reveal_deadline),
+    ];
+    prepare(config, creator, "amend_tender", args).await
+}
+
+pub async fn get_amendments(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Vec<Amendment>, Box<dyn std::error::Error>> {
+    log::info!("Getting amendments of tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_amendments", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_vec(&result, |v| Amendment::try_from(v))?)
+}
+
+pub async fn cancel_tender(
+    config: &Config,
+    tender_id: u64,
+    req: CancelTenderRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing cancel_tender {} by {} on contract {}", tender_id, req.caller, config.contract_id);
+
+    let args = vec![scval::u64(tender_id), scval::address(&req.caller)?, scval::string(&req.reason)?];
+    prepare(config, &req.caller, "cancel_tender", args).await
+}
+
+pub async fn get_cancellation(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Option<Cancellation>, Box<dyn std::error::Error>> {
+    log::info!("Getting cancellation of tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_cancellation", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_option(&result, |v| Cancellation::try_from(v))?)
+}
+
+pub async fn award_tender(
+    config: &Config,
+    tender_id: u64,
+    req: AwardTenderRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing award_tender {} to {} on contract {}", tender_id, req.bidder, config.contract_id);
+
+    let args = vec![scval::u64(tender_id), scval::address(&req.caller)?, scval::address(&req.bidder)?];
+    prepare(config, &req.caller, "award_tender", args).await
+}
+
+pub async fn get_tied_bidders(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    log::info!("Getting tied bidders for tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_tied_bidders", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_vec(&result, scval::to_address)?)
 }
 
 pub async fn get_winner(
@@ -180,31 +215,20 @@ pub async fn get_winner(
     tender_id: u64,
 ) -> Result<Option<Winner>, Box<dyn std::error::Error>> {
     log::info!("Getting winner for tender {} from contract {}", tender_id, config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "moon",
-            "--network", &config.network,
-            "--",
-            "get_winner",
-            "--tender_id", &tender_id.to_string(),
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        // Winner might not exist yet, that's okay
-        return Ok(None);
-    }
-    
-    let result = String::from_utf8_lossy(&output.stdout);
-    if result.trim().is_empty() || result.contains("null") {
-        return Ok(None);
-    }
-    
-    let winner = parse_winner(&result)?;
-    Ok(Some(winner))
+
+    let result = simulate_read(config, "get_winner", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_option(&result, |v| Winner::try_from(v))?)
+}
+
+/// Awards of a multi-lot tender, one per lot that found a winner
+pub async fn get_lot_winners(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Vec<Winner>, Box<dyn std::error::Error>> {
+    log::info!("Getting lot winners for tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_lot_winners", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_vec(&result, |v| Winner::try_from(v))?)
 }
 
 pub async fn get_bid(
@@ -212,33 +236,12 @@ pub async fn get_bid(
     tender_id: u64,
     bidder: &str,
 ) -> Result<Option<Bid>, Box<dyn std::error::Error>> {
-    log::info!("Getting bid for tender {} from bidder {} on contract {}", 
+    log::info!("Getting bid for tender {} from bidder {} on contract {}",
         tender_id, bidder, config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "moon",
-            "--network", &config.network,
-            "--",
-            "get_bid",
-            "--tender_id", &tender_id.to_string(),
-            "--bidder", bidder,
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        return Ok(None);
-    }
-    
-    let result = String::from_utf8_lossy(&output.stdout);
-    if result.trim().is_empty() || result.contains("null") {
-        return Ok(None);
-    }
-    
-    let bid = parse_bid(&result)?;
-    Ok(Some(bid))
+
+    let args = vec![scval::u64(tender_id), scval::address(bidder)?];
+    let result = simulate_read(config, "get_bid", args).await?;
+    Ok(scval::to_option(&result, |v| Bid::try_from(v))?)
 }
 
 pub async fn get_tender_bidders(
@@ -246,115 +249,918 @@ pub async fn get_tender_bidders(
     tender_id: u64,
 ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
     log::info!("Getting bidders for tender {} from contract {}", tender_id, config.contract_id);
-    
-    let output = Command::new("soroban")
-        .args([
-            "contract", "invoke",
-            "--id", &config.contract_id,
-            "--source-account", "moon",
-            "--network", &config.network,
-            "--",
-            "get_tender_bidders",
-            "--tender_id", &tender_id.to_string(),
-        ])
-        .output()?;
-    
-    if !output.status.success() {
-        return Ok(vec![]);
-    }
-    
-    let result = String::from_utf8_lossy(&output.stdout);
-    parse_string_vec(&result)
+
+    let result = simulate_read(config, "get_tender_bidders", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_vec(&result, scval::to_address)?)
 }
 
-// Parsing helper functions
-fn parse_u64_vec(output: &str) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
-    let trimmed = output.trim();
-    if trimmed.is_empty() || trimmed == "[]" {
-        return Ok(vec![]);
-    }
-    
-    // Parse JSON array: [1, 2, 3]
-    let json: Value = serde_json::from_str(trimmed)?;
-    if let Some(arr) = json.as_array() {
-        let ids: Vec<u64> = arr.iter()
-            .filter_map(|v| v.as_u64())
-            .collect();
-        Ok(ids)
+pub async fn set_committee(
+    config: &Config,
+    tender_id: u64,
+    req: SetCommitteeRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing set_committee for tender {} on contract {}", tender_id, config.contract_id);
+
+    let args = vec![
+        scval::u64(tender_id),
+        scval::address(&req.creator)?,
+        ScVal::try_from(&req.committee)?,
+    ];
+    prepare(config, &req.creator, "set_committee", args).await
+}
+
+pub async fn submit_key_share(
+    config: &Config,
+    tender_id: u64,
+    req: SubmitKeyShareRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing submit_key_share for tender {} by {} on contract {}",
+        tender_id, req.member, config.contract_id);
+
+    let args = vec![
+        scval::u64(tender_id),
+        scval::address(&req.member)?,
+        scval::bytes_hex(&req.share)?,
+    ];
+    prepare(config, &req.member, "submit_key_share", args).await
+}
+
+pub async fn publish_opening_key(
+    config: &Config,
+    tender_id: u64,
+    req: PublishOpeningKeyRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing publish_opening_key for tender {} on contract {}", tender_id, config.contract_id);
+
+    let args = vec![
+        scval::u64(tender_id),
+        scval::address(&req.creator)?,
+        scval::bytes32_hex(&req.secret_key)?,
+    ];
+    prepare(config, &req.creator, "publish_opening_key", args).await
+}
+
+pub async fn open_bids(
+    config: &Config,
+    tender_id: u64,
+    caller: &str,
+    openings: &[BidOpening],
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing open_bids for tender {} ({} bids) on contract {}",
+        tender_id, openings.len(), config.contract_id);
+
+    let openings = openings.iter().map(ScVal::try_from).collect::<Result<_, _>>()?;
+    let args = vec![scval::u64(tender_id), scval::address(caller)?, scval::vec(openings)?];
+    prepare(config, caller, "open_bids", args).await
+}
+
+pub async fn get_opening_key(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    log::info!("Getting opening key for tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_opening_key", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_option(&result, scval::to_bytes_hex)?)
+}
+
+pub async fn get_committee(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Option<Committee>, Box<dyn std::error::Error>> {
+    log::info!("Getting committee for tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_committee", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_option(&result, |v| Committee::try_from(v))?)
+}
+
+pub async fn get_key_shares(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Vec<KeyShare>, Box<dyn std::error::Error>> {
+    log::info!("Getting key shares for tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_key_shares", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_vec(&result, |v| KeyShare::try_from(v))?)
+}
+
+pub async fn submit_scores(
+    config: &Config,
+    tender_id: u64,
+    req: SubmitScoresRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing submit_scores for tender {} bid {} on contract {}",
+        tender_id, req.bidder, config.contract_id);
+
+    let args = vec![
+        scval::u64(tender_id),
+        scval::address(&req.evaluator)?,
+        scval::address(&req.bidder)?,
+        scval::vec(req.scores.iter().map(|s| scval::u32(*s)).collect())?,
+    ];
+    prepare(config, &req.evaluator, "submit_scores", args).await
+}
+
+pub async fn get_scorecards(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Vec<Scorecard>, Box<dyn std::error::Error>> {
+    log::info!("Getting scorecards for tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_scorecards", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_vec(&result, |v| Scorecard::try_from(v))?)
+}
+
+pub async fn fund_escrow(
+    config: &Config,
+    tender_id: u64,
+    req: FundEscrowRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing fund_escrow for tender {} ({} milestones) on contract {}",
+        tender_id, req.milestones.len(), config.contract_id);
+
+    let milestones = req.milestones.iter().map(ScVal::try_from).collect::<Result<_, _>>()?;
+    let args = vec![
+        scval::u64(tender_id),
+        scval::address(&req.creator)?,
+        scval::address(&req.token)?,
+        scval::i128(req.amount),
+        req.inspector.as_deref().map_or(Ok(ScVal::Void), scval::address)?,
+        scval::vec(milestones)?,
+    ];
+    prepare(config, &req.creator, "fund_escrow", args).await
+}
+
+/// `release_milestone` or `refund_milestone`, which take the same arguments
+pub async fn settle_milestone(
+    config: &Config,
+    function: &str,
+    tender_id: u64,
+    index: u32,
+    caller: &str,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing {} of milestone {} for tender {} on contract {}",
+        function, index, tender_id, config.contract_id);
+
+    let args = vec![scval::u64(tender_id), scval::address(caller)?, scval::u32(index)];
+    prepare(config, caller, function, args).await
+}
+
+pub async fn get_escrow(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Option<Escrow>, Box<dyn std::error::Error>> {
+    log::info!("Getting escrow for tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_escrow", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_option(&result, |v| Escrow::try_from(v))?)
+}
+
+/// Prepare a register_bidder transaction for the bidder's wallet to sign
+pub async fn register_bidder(
+    config: &Config,
+    req: RegisterBidderRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing register_bidder for {} on contract {}", req.bidder, config.contract_id);
+
+    let args = vec![scval::address(&req.bidder)?, scval::string(&req.credentials_hash)?];
+    prepare(config, &req.bidder, "register_bidder", args).await
+}
+
+pub async fn approve_bidder(
+    config: &Config,
+    bidder: &str,
+    req: ApproveBidderRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing approve_bidder for {} on contract {}", bidder, config.contract_id);
+
+    let args = vec![
+        scval::address(&req.verifier)?,
+        scval::address(bidder)?,
+        scval::u64(req.expires_at),
+    ];
+    prepare(config, &req.verifier, "approve_bidder", args).await
+}
+
+pub async fn suspend_bidder(
+    config: &Config,
+    bidder: &str,
+    req: SuspendBidderRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing suspend_bidder for {} on contract {}", bidder, config.contract_id);
+
+    let args = vec![scval::address(&req.verifier)?, scval::address(bidder)?];
+    prepare(config, &req.verifier, "suspend_bidder", args).await
+}
+
+pub async fn get_registration(
+    config: &Config,
+    bidder: &str,
+) -> Result<Option<Registration>, Box<dyn std::error::Error>> {
+    let result = simulate_read(config, "get_registration", vec![scval::address(bidder)?]).await?;
+    Ok(scval::to_option(&result, |v| Registration::try_from(v))?)
+}
+
+/// Strikes on record against a bidder; zero for a clean record
+pub async fn get_strikes(config: &Config, bidder: &str) -> Result<StrikeRecord, Box<dyn std::error::Error>> {
+    let result = simulate_read(config, "get_strikes", vec![scval::address(bidder)?]).await?;
+    Ok(scval::to_option(&result, |v| StrikeRecord::try_from(v))?.unwrap_or_default())
+}
+
+pub async fn get_reveal_outcomes(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Vec<BidderOutcome>, Box<dyn std::error::Error>> {
+    log::info!("Getting reveal outcomes of tender {} from contract {}", tender_id, config.contract_id);
+
+    let result = simulate_read(config, "get_reveal_outcomes", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_vec(&result, |v| BidderOutcome::try_from(v))?)
+}
+
+pub async fn debar_bidder(
+    config: &Config,
+    req: DebarBidderRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing debar_bidder for {} on contract {}", req.bidder, config.contract_id);
+
+    let args = vec![
+        scval::address(&req.caller)?,
+        scval::address(&req.bidder)?,
+        scval::string(&req.reason)?,
+        scval::u64(req.start),
+        scval::u64(req.end),
+    ];
+    prepare(config, &req.caller, "debar_bidder", args).await
+}
+
+pub async fn lift_debarment(
+    config: &Config,
+    bidder: &str,
+    req: LiftDebarmentRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing lift_debarment for {} on contract {}", bidder, config.contract_id);
+
+    let args = vec![scval::address(&req.caller)?, scval::address(bidder)?];
+    prepare(config, &req.caller, "lift_debarment", args).await
+}
+
+pub async fn get_debarment_status(
+    config: &Config,
+    bidder: &str,
+) -> Result<DebarmentStatus, Box<dyn std::error::Error>> {
+    let debarred = simulate_read(config, "is_debarred", vec![scval::address(bidder)?]).await?;
+    let debarment = simulate_read(config, "get_debarment", vec![scval::address(bidder)?]).await?;
+    Ok(DebarmentStatus {
+        debarred: scval::to_bool(&debarred)?,
+        debarment: scval::to_option(&debarment, |v| Debarment::try_from(v))?,
+    })
+}
+
+/// Page through every debarment on record, lifted or not, in the order imposed
+pub async fn get_debarments(
+    config: &Config,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<Debarment>, Box<dyn std::error::Error>> {
+    log::info!("Getting debarments from contract {}", config.contract_id);
+
+    let result = simulate_read(config, "get_debarments", vec![scval::u32(start), scval::u32(limit)]).await?;
+    Ok(scval::to_vec(&result, |v| Debarment::try_from(v))?)
+}
+
+pub async fn set_approval_policy(
+    config: &Config,
+    entity: &str,
+    req: SetApprovalPolicyRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!("Preparing set_approval_policy for {} on contract {}", entity, config.contract_id);
+
+    let args = vec![
+        scval::address(&req.caller)?,
+        scval::address(entity)?,
+        ScVal::try_from(&req.policy)?,
+    ];
+    prepare(config, &req.caller, "set_approval_policy", args).await
+}
+
+pub async fn get_approval_policy(
+    config: &Config,
+    entity: &str,
+) -> Result<Option<ApprovalPolicy>, Box<dyn std::error::Error>> {
+    let result = simulate_read(config, "get_approval_policy", vec![scval::address(entity)?]).await?;
+    Ok(scval::to_option(&result, |v| ApprovalPolicy::try_from(v))?)
+}
+
+/// Approve a draft's publication or a provisional award, by stage
+pub async fn approve(
+    config: &Config,
+    tender_id: u64,
+    stage: ApprovalStage,
+    approver: &str,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    let function = match stage {
+        ApprovalStage::Publication => "approve_tender",
+        ApprovalStage::Award => "approve_award",
+    };
+    log::info!("Preparing {} {} on contract {}", function, tender_id, config.contract_id);
+
+    let args = vec![scval::u64(tender_id), scval::address(approver)?];
+    prepare(config, approver, function, args).await
+}
+
+pub async fn get_approvals(
+    config: &Config,
+    tender_id: u64,
+    stage: ApprovalStage,
+) -> Result<Vec<Approval>, Box<dyn std::error::Error>> {
+    let args = vec![scval::u64(tender_id), scval::unit_enum(stage.as_str())?];
+    let result = simulate_read(config, "get_approvals", args).await?;
+    Ok(scval::to_vec(&result, |v| Approval::try_from(v))?)
+}
+
+pub async fn get_tender_approvers(
+    config: &Config,
+    tender_id: u64,
+) -> Result<Option<ApprovalPolicy>, Box<dyn std::error::Error>> {
+    let result = simulate_read(config, "get_tender_approvers", vec![scval::u64(tender_id)]).await?;
+    Ok(scval::to_option(&result, |v| ApprovalPolicy::try_from(v))?)
+}
+
+pub async fn grant_role(config: &Config, req: RoleRequest) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    set_role(config, "grant_role", req).await
+}
+
+pub async fn revoke_role(config: &Config, req: RoleRequest) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    set_role(config, "revoke_role", req).await
+}
+
+async fn set_role(
+    config: &Config,
+    function: &str,
+    req: RoleRequest,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    log::info!(
+        "Preparing {} {} for {} on contract {}",
+        function,
+        req.role.as_str(),
+        req.account,
+        config.contract_id
+    );
+
+    let args = vec![
+        scval::address(&req.caller)?,
+        scval::address(&req.account)?,
+        scval::unit_enum(req.role.as_str())?,
+    ];
+    prepare(config, &req.caller, function, args).await
+}
+
+pub async fn has_role(config: &Config, account: &str, role: Role) -> Result<bool, Box<dyn std::error::Error>> {
+    let args = vec![scval::address(account)?, scval::unit_enum(role.as_str())?];
+    let result = simulate_read(config, "has_role", args).await?;
+    Ok(scval::to_bool(&result)?)
+}
+
+/// Fail with the contract's `Unauthorized` unless `account` holds `role`,
+/// so routes can turn callers away before preparing a transaction
+pub async fn require_role(config: &Config, account: &str, role: Role) -> Result<(), Box<dyn std::error::Error>> {
+    if has_role(config, account, role).await? {
+        Ok(())
     } else {
-        Ok(vec![])
+        Err(Box::new(TenderError::Unauthorized))
     }
 }
 
-fn parse_string_vec(output: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let trimmed = output.trim();
-    if trimmed.is_empty() || trimmed == "[]" {
-        return Ok(vec![]);
+/// Run a read-only contract call through `simulateTransaction`
+async fn simulate_read(
+    config: &Config,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<ScVal, Box<dyn std::error::Error>> {
+    let rpc = RpcClient::new(&config.rpc_url);
+    let source = MuxedAccount::from_str(&config.source_account)?;
+    let tx = build_transaction(config, source, 0, function, args)?;
+
+    let simulation = simulate(&rpc, function, &tx).await?;
+    decode_return_value(&simulation)
+}
+
+/// Build and simulate a state-changing contract call with `source` as the
+/// transaction source, returning the unsigned envelope for the wallet to sign.
+/// The source account's signature also covers its own `require_auth`.
+async fn prepare(
+    config: &Config,
+    source: &str,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<PreparedTransaction, Box<dyn std::error::Error>> {
+    let account_id = AccountId::from_str(source)?;
+    let PublicKey::PublicKeyTypeEd25519(public_key) = &account_id.0;
+
+    let rpc = RpcClient::new(&config.rpc_url);
+    let sequence = load_sequence(&rpc, account_id.clone()).await?;
+    let source = MuxedAccount::Ed25519(public_key.clone());
+    let mut tx = build_transaction(config, source, sequence + 1, function, args)?;
+
+    let simulation = simulate(&rpc, function, &tx).await?;
+    assemble(&mut tx, &simulation)?;
+
+    let hash = hex::encode(transaction_hash(config, &tx)?);
+    let fee = tx.fee;
+    let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: VecM::default(),
+    });
+    Ok(PreparedTransaction {
+        xdr: scval::to_base64(&envelope)?,
+        hash,
+        network_passphrase: config.network_passphrase.clone(),
+        fee,
+    })
+}
+
+/// Submit a wallet-signed envelope and poll until it reaches a final status
+pub async fn submit_transaction(
+    config: &Config,
+    xdr: &str,
+) -> Result<TransactionResult, Box<dyn std::error::Error>> {
+    let envelope: TransactionEnvelope = scval::from_base64(xdr)?;
+    let signed = match &envelope {
+        TransactionEnvelope::Tx(v1) => !v1.signatures.is_empty(),
+        TransactionEnvelope::TxV0(v0) => !v0.signatures.is_empty(),
+        TransactionEnvelope::TxFeeBump(bump) => !bump.signatures.is_empty(),
+    };
+    if !signed {
+        return Err("Transaction envelope is not signed".into());
     }
-    
-    let json: Value = serde_json::from_str(trimmed)?;
-    if let Some(arr) = json.as_array() {
-        let strings: Vec<String> = arr.iter()
-            .filter_map(|v| v.as_str())
-            .map(|s| s.to_string())
-            .collect();
-        Ok(strings)
-    } else {
-        Ok(vec![])
+
+    let rpc = RpcClient::new(&config.rpc_url);
+    let sent = rpc.send_transaction(xdr).await?;
+    log::info!("Submitted transaction {} ({})", sent.hash, sent.status);
+    if sent.status == "ERROR" || sent.status == "TRY_AGAIN_LATER" {
+        return Err(format!(
+            "Transaction {} rejected: {} {}",
+            sent.hash,
+            sent.status,
+            sent.error_result_xdr.unwrap_or_default()
+        )
+        .into());
+    }
+
+    for _ in 0..POLL_ATTEMPTS {
+        let status = rpc.get_transaction(&sent.hash).await?;
+        match status.status.as_str() {
+            "SUCCESS" => {
+                let return_value = match status.result_meta_xdr {
+                    Some(meta) => match scval::from_base64::<TransactionMeta>(&meta)? {
+                        TransactionMeta::V3(v3) => v3
+                            .soroban_meta
+                            .map(|m| scval::to_base64(&m.return_value))
+                            .transpose()?,
+                        _ => None,
+                    },
+                    None => None,
+                };
+                return Ok(TransactionResult {
+                    hash: sent.hash,
+                    return_value,
+                });
+            }
+            "FAILED" => return Err(format!("Transaction {} failed", sent.hash).into()),
+            _ => tokio::time::sleep(POLL_INTERVAL).await,
+        }
     }
+
+    Err(format!("Timed out waiting for transaction {}", sent.hash).into())
 }
 
-fn parse_tender(output: &str) -> Result<Tender, Box<dyn std::error::Error>> {
-    let json: Value = serde_json::from_str(output.trim())?;
-    
-    Ok(Tender {
-        id: json["id"].as_u64().unwrap_or(0),
-        creator: json["creator"].as_str().unwrap_or("").to_string(),
-        title: json["title"].as_str().unwrap_or("").to_string(),
-        description: json["description"].as_str().unwrap_or("").to_string(),
-        ipfs_hash: json["ipfs_hash"].as_str().unwrap_or("").to_string(),
-        deadline: json["deadline"].as_u64().unwrap_or(0),
-        reveal_deadline: json["reveal_deadline"].as_u64().unwrap_or(0),
-        min_bid: json["min_bid"].as_str()
-            .and_then(|s| s.parse::<i128>().ok())
-            .or_else(|| json["min_bid"].as_i64().map(|i| i as i128))
-            .unwrap_or(0),
-        is_closed: json["is_closed"].as_bool().unwrap_or(false),
-        created_at: json["created_at"].as_u64().unwrap_or(0),
+fn build_transaction(
+    config: &Config,
+    source: MuxedAccount,
+    sequence: i64,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<Transaction, Box<dyn std::error::Error>> {
+    let invoke = InvokeContractArgs {
+        contract_address: ScAddress::from_str(&config.contract_id)?,
+        function_name: ScSymbol(function.try_into()?),
+        args: args.try_into()?,
+    };
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(invoke),
+            auth: VecM::default(),
+        }),
+    };
+
+    Ok(Transaction {
+        source_account: source,
+        fee: BASE_FEE,
+        seq_num: SequenceNumber(sequence),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation].try_into()?,
+        ext: TransactionExt::V0,
     })
 }
 
-fn parse_winner(output: &str) -> Result<Winner, Box<dyn std::error::Error>> {
-    let json: Value = serde_json::from_str(output.trim())?;
-    
-    Ok(Winner {
-        tender_id: json["tender_id"].as_u64().unwrap_or(0),
-        bidder: json["bidder"].as_str().unwrap_or("").to_string(),
-        amount: json["amount"].as_str()
-            .and_then(|s| s.parse::<i128>().ok())
-            .or_else(|| json["amount"].as_i64().map(|i| i as i128))
-            .unwrap_or(0),
-        selected_at: json["selected_at"].as_u64().unwrap_or(0),
-    })
+async fn simulate(
+    rpc: &RpcClient,
+    function: &str,
+    tx: &Transaction,
+) -> Result<SimulateTransactionResponse, Box<dyn std::error::Error>> {
+    let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: tx.clone(),
+        signatures: VecM::default(),
+    });
+    let simulation = rpc.simulate_transaction(&scval::to_base64(&envelope)?).await?;
+
+    if let Some(error) = &simulation.error {
+        return Err(simulation_error(function, error));
+    }
+    Ok(simulation)
 }
 
-fn parse_bid(output: &str) -> Result<Bid, Box<dyn std::error::Error>> {
-    let json: Value = serde_json::from_str(output.trim())?;
-    
-    let revealed_amount = if json["revealed_amount"].is_null() {
-        None
-    } else {
-        json["revealed_amount"].as_str()
-            .and_then(|s| s.parse::<i128>().ok())
-            .or_else(|| json["revealed_amount"].as_i64().map(|i| i as i128))
+fn decode_return_value(
+    simulation: &SimulateTransactionResponse,
+) -> Result<ScVal, Box<dyn std::error::Error>> {
+    let result = simulation.results.first().ok_or("Simulation returned no result")?;
+    Ok(scval::from_base64(&result.xdr)?)
+}
+
+/// Apply simulation output: footprint, resource fee and authorization entries
+fn assemble(
+    tx: &mut Transaction,
+    simulation: &SimulateTransactionResponse,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let data: SorobanTransactionData = scval::from_base64(
+        simulation
+            .transaction_data
+            .as_deref()
+            .ok_or("Simulation returned no transaction data")?,
+    )?;
+    let resource_fee: u32 = simulation
+        .min_resource_fee
+        .as_deref()
+        .unwrap_or("0")
+        .parse()?;
+    let auth = match simulation.results.first() {
+        Some(result) => result
+            .auth
+            .iter()
+            .map(|entry| scval::from_base64::<SorobanAuthorizationEntry>(entry))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => vec![],
     };
-    
-    Ok(Bid {
-        bidder: json["bidder"].as_str().unwrap_or("").to_string(),
-        tender_id: json["tender_id"].as_u64().unwrap_or(0),
-        encrypted_amount: json["encrypted_amount"].as_str().unwrap_or("").to_string(),
-        revealed_amount,
-        is_valid: json["is_valid"].as_bool().unwrap_or(false),
-        timestamp: json["timestamp"].as_u64().unwrap_or(0),
-    })
-}
\ No newline at end of file
+
+    if let Some(Operation {
+        body: OperationBody::InvokeHostFunction(op),
+        ..
+    }) = tx.operations.first().cloned()
+    {
+        let op = InvokeHostFunctionOp {
+            host_function: op.host_function,
+            auth: auth.try_into()?,
+        };
+        tx.operations = vec![Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(op),
+        }]
+        .try_into()?;
+    }
+    tx.fee = tx.fee.saturating_add(resource_fee);
+    tx.ext = TransactionExt::V1(data);
+    Ok(())
+}
+
+/// Network-bound hash that signers sign over
+fn transaction_hash(config: &Config, tx: &Transaction) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(config.network_passphrase.as_bytes()).into()),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    };
+    Ok(Sha256::digest(payload.to_xdr(Limits::none())?).into())
+}
+
+/// Current sequence number of the source account via `getLedgerEntries`
+async fn load_sequence(rpc: &RpcClient, account_id: AccountId) -> Result<i64, Box<dyn std::error::Error>> {
+    let key = LedgerKey::Account(LedgerKeyAccount { account_id });
+    let response = rpc.get_ledger_entries(&[scval::to_base64(&key)?]).await?;
+    let entry = response.entries.first().ok_or("Source account not found")?;
+
+    match scval::from_base64::<LedgerEntryData>(&entry.xdr)? {
+        LedgerEntryData::Account(account) => Ok(account.seq_num.0),
+        _ => Err("Unexpected ledger entry for source account".into()),
+    }
+}
+
+/// Turn a failed simulation into an error, preserving contract error codes
+fn simulation_error(function: &str, error: &str) -> Box<dyn std::error::Error> {
+    match parse_contract_error(error) {
+        Some(contract_error) => Box::new(contract_error),
+        None => format!("Failed to {}: {}", function, error).into(),
+    }
+}
+
+/// Extract a contract error from host output such as `Error(Contract, #3)`
+fn parse_contract_error(output: &str) -> Option<TenderError> {
+    let marker = "Error(Contract, #";
+    let start = output.find(marker)? + marker.len();
+    let digits: String = output[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    TenderError::from_code(digits.parse().ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::mock_rpc::MockRpcServer;
+    use serde_json::{json, Value};
+    use stellar_xdr::curr::{
+        AccountEntry, AccountEntryExt, DecoratedSignature, ExtensionPoint, LedgerEntryChanges,
+        LedgerFootprint, Signature, SignatureHint, SorobanResources, SorobanTransactionMeta,
+        SorobanTransactionMetaExt, String32, Thresholds, TransactionMetaV3, Uint256,
+    };
+
+    const ADDRESS: &str = "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M";
+    const CONTRACT: &str = "CD5JXQDVQJK2ZHYX7U76I45N2DKKFMVCA6WJ6ZQHO2EL6WJV2VAD6GP4";
+
+    fn test_config(rpc_url: &str) -> Config {
+        Config::for_tests(rpc_url)
+    }
+
+    fn simulation_result(value: &ScVal) -> Value {
+        let data = SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 1_000,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 5_000,
+        };
+        json!({
+            "result": {
+                "transactionData": scval::to_base64(&data).unwrap(),
+                "minResourceFee": "5000",
+                "results": [{ "auth": [], "xdr": scval::to_base64(value).unwrap() }],
+                "latestLedger": 100,
+            }
+        })
+    }
+
+    fn account_entry(sequence: i64) -> String {
+        let account = AccountEntry {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([0u8; 32]))),
+            balance: 10_000_000,
+            seq_num: SequenceNumber(sequence),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: String32::default(),
+            thresholds: Thresholds([1, 0, 0, 0]),
+            signers: VecM::default(),
+            ext: AccountEntryExt::V0,
+        };
+        scval::to_base64(&LedgerEntryData::Account(account)).unwrap()
+    }
+
+    fn transaction_meta(value: ScVal) -> String {
+        let meta = TransactionMeta::V3(TransactionMetaV3 {
+            ext: ExtensionPoint::V0,
+            tx_changes_before: LedgerEntryChanges::default(),
+            operations: VecM::default(),
+            tx_changes_after: LedgerEntryChanges::default(),
+            soroban_meta: Some(SorobanTransactionMeta {
+                ext: SorobanTransactionMetaExt::V0,
+                events: VecM::default(),
+                return_value: value,
+                diagnostic_events: VecM::default(),
+            }),
+        });
+        scval::to_base64(&meta).unwrap()
+    }
+
+    #[test]
+    fn test_parse_contract_error() {
+        let stderr = "error: transaction simulation failed: HostError: Error(Contract, #13)\n\nEvent log (newest first):";
+        assert_eq!(parse_contract_error(stderr), Some(TenderError::CommitmentMismatch));
+        assert_eq!(parse_contract_error("Error(Contract, #999)"), None);
+        assert_eq!(parse_contract_error("error: network unreachable"), None);
+    }
+
+    #[actix_rt::test]
+    async fn test_get_tender_via_simulation() {
+        let tender = Tender {
+            id: 4,
+            creator: ADDRESS.to_string(),
+            title: "Bridge".to_string(),
+            description: "Repair".to_string(),
+            ipfs_hash: "QmDocs".to_string(),
+            deadline: 1_000,
+            reveal_deadline: 2_000,
+            min_bid: 100_000,
+            is_closed: false,
+            created_at: 10,
+            encryption_key: "0707070707070707070707070707070707070707070707070707070707070707".to_string(),
+            opening_mode: OpeningMode::BidderReveal,
+            bond: None,
+            evaluation: Evaluation::LowestPrice,
+            tie_break: TieBreak::EarliestCommit,
+            lots: Vec::new(),
+            max_lots_per_bidder: 0,
+            eligibility: Eligibility::Open,
+            max_strikes: 0,
+            is_cancelled: false,
+            is_draft: false,
+            award_pending: false,
+        };
+        let value = ScVal::try_from(&tender).unwrap();
+        let server = MockRpcServer::start(move |_, _| simulation_result(&value)).await;
+
+        let fetched = get_tender(&test_config(&server.url), 4).await.unwrap();
+        assert_eq!(fetched.id, 4);
+        assert_eq!(fetched.title, "Bridge");
+        assert_eq!(fetched.creator, ADDRESS);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0]["method"], "simulateTransaction");
+
+        let envelope: TransactionEnvelope =
+            scval::from_base64(requests[0]["params"]["transaction"].as_str().unwrap()).unwrap();
+        let TransactionEnvelope::Tx(envelope) = envelope else {
+            panic!("expected a v1 envelope");
+        };
+        let OperationBody::InvokeHostFunction(op) = &envelope.tx.operations[0].body else {
+            panic!("expected invoke host function");
+        };
+        let HostFunction::InvokeContract(call) = &op.host_function else {
+            panic!("expected contract invocation");
+        };
+        assert_eq!(call.contract_address.to_string(), CONTRACT);
+        assert_eq!(call.function_name.0.to_utf8_string_lossy(), "get_tender");
+        assert_eq!(call.args.as_slice(), &[ScVal::U64(4)]);
+    }
+
+    #[actix_rt::test]
+    async fn test_simulation_error_maps_contract_code() {
+        let server = MockRpcServer::start(|_, _| {
+            json!({ "result": { "error": "HostError: Error(Contract, #3)", "latestLedger": 100 } })
+        })
+        .await;
+
+        let err = get_tender(&test_config(&server.url), 99).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<TenderError>(), Some(&TenderError::TenderNotFound));
+    }
+
+    #[actix_rt::test]
+    async fn test_optional_reads_decode_void() {
+        let server = MockRpcServer::start(|_, _| simulation_result(&ScVal::Void)).await;
+        let config = test_config(&server.url);
+
+        assert!(get_winner(&config, 1).await.unwrap().is_none());
+        assert!(get_bid(&config, 1, ADDRESS).await.unwrap().is_none());
+    }
+
+    fn unsigned_envelope(prepared: &PreparedTransaction) -> TransactionV1Envelope {
+        match scval::from_base64::<TransactionEnvelope>(&prepared.xdr).unwrap() {
+            TransactionEnvelope::Tx(envelope) => envelope,
+            _ => panic!("expected a v1 envelope"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_create_tender_returns_unsigned_envelope() {
+        let server = MockRpcServer::start(|method, _| match method {
+            "getLedgerEntries" => json!({
+                "result": { "entries": [{ "xdr": account_entry(41) }], "latestLedger": 100 }
+            }),
+            "simulateTransaction" => simulation_result(&ScVal::U64(7)),
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        })
+        .await;
+        let config = test_config(&server.url);
+
+        let req = CreateTenderRequest {
+            creator: ADDRESS.to_string(),
+            title: "School".to_string(),
+            description: "New wing".to_string(),
+            ipfs_hash: "QmPlan".to_string(),
+            deadline: 1_000,
+            reveal_deadline: 2_000,
+            min_bid: 50_000,
+            encryption_key: "0707070707070707070707070707070707070707070707070707070707070707".to_string(),
+            opening_mode: OpeningMode::BidderReveal,
+            bond: None,
+            evaluation: Evaluation::LowestPrice,
+            tie_break: TieBreak::EarliestCommit,
+            lots: Vec::new(),
+            max_lots_per_bidder: 0,
+            eligibility: Eligibility::Open,
+            max_strikes: 0,
+        };
+        let prepared = create_tender(&config, req).await.unwrap();
+
+        // Nothing is submitted: the wallet signs and hands the envelope back
+        let methods: Vec<String> = server
+            .requests()
+            .iter()
+            .map(|r| r["method"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(methods, ["getLedgerEntries", "simulateTransaction"]);
+
+        let envelope = unsigned_envelope(&prepared);
+        assert!(envelope.signatures.is_empty());
+        assert_eq!(envelope.tx.source_account.to_string(), ADDRESS);
+        assert_eq!(envelope.tx.seq_num.0, 42);
+        assert_eq!(envelope.tx.fee, BASE_FEE + 5_000);
+        assert_eq!(prepared.fee, envelope.tx.fee);
+        assert!(matches!(envelope.tx.ext, TransactionExt::V1(_)));
+        assert_eq!(prepared.hash, hex::encode(transaction_hash(&config, &envelope.tx).unwrap()));
+        assert_eq!(prepared.network_passphrase, config.network_passphrase);
+    }
+
+    #[actix_rt::test]
+    async fn test_prepare_surfaces_contract_error() {
+        let server = MockRpcServer::start(|method, _| match method {
+            "getLedgerEntries" => json!({
+                "result": { "entries": [{ "xdr": account_entry(1) }], "latestLedger": 100 }
+            }),
+            _ => json!({ "result": { "error": "HostError: Error(Contract, #15)", "latestLedger": 100 } }),
+        })
+        .await;
+
+        let err = close_tender(&test_config(&server.url), 1, ADDRESS).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<TenderError>(), Some(&TenderError::RevealNotEnded));
+    }
+
+    #[actix_rt::test]
+    async fn test_submit_transaction_polls_until_final() {
+        let polls = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counter = polls.clone();
+        let server = MockRpcServer::start(move |method, _| match method {
+            "sendTransaction" => json!({ "result": { "status": "PENDING", "hash": "abc123" } }),
+            "getTransaction" => {
+                if counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    json!({ "result": { "status": "NOT_FOUND" } })
+                } else {
+                    json!({
+                        "result": { "status": "SUCCESS", "resultMetaXdr": transaction_meta(ScVal::U64(7)) }
+                    })
+                }
+            }
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        })
+        .await;
+
+        let mut envelope = TransactionV1Envelope {
+            tx: build_transaction(
+                &test_config(&server.url),
+                MuxedAccount::from_str(ADDRESS).unwrap(),
+                1,
+                "close_tender",
+                vec![],
+            )
+            .unwrap(),
+            signatures: VecM::default(),
+        };
+        envelope.signatures = vec![DecoratedSignature {
+            hint: SignatureHint([0; 4]),
+            signature: Signature(vec![0u8; 64].try_into().unwrap()),
+        }]
+        .try_into()
+        .unwrap();
+        let xdr = scval::to_base64(&TransactionEnvelope::Tx(envelope)).unwrap();
+
+        let result = submit_transaction(&test_config(&server.url), &xdr).await.unwrap();
+        assert_eq!(result.hash, "abc123");
+        assert_eq!(polls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        let value: ScVal = scval::from_base64(&result.return_value.unwrap()).unwrap();
+        assert_eq!(value, ScVal::U64(7));
+    }
+
+    #[actix_rt::test]
+    async fn test_submit_rejects_unsigned_envelope() {
+        let server = MockRpcServer::start(|_, _| json!({ "result": {} })).await;
+        let config = test_config(&server.url);
+        let tx = build_transaction(&config, MuxedAccount::from_str(ADDRESS).unwrap(), 1, "close_tender", vec![])
+            .unwrap();
+        let xdr = scval::to_base64(&TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: VecM::default(),
+        }))
+        .unwrap();
+
+        let err = submit_transaction(&config, &xdr).await.unwrap_err();
+        assert!(err.to_string().contains("not signed"));
+        assert!(server.requests().is_empty());
+    }
+}
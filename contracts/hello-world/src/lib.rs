@@ -1,9 +1,90 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
-    Address, Env, String, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Map, String, Vec,
 };
 
+/// Contract errors. Codes are part of the public API (clients map them to
+/// user-facing errors), so never renumber existing variants. The contract
+/// spec allows at most 50 variants, so new checks reuse the closest code.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TenderError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    TenderNotFound = 3,
+    InvalidDeadline = 4,
+    InvalidRevealDeadline = 5,
+    DeadlinePassed = 6,
+    TenderClosed = 7,
+    BidAlreadySubmitted = 8,
+    RevealNotStarted = 9,
+    RevealDeadlinePassed = 10,
+    BidNotFound = 11,
+    AlreadyRevealed = 12,
+    CommitmentMismatch = 13,
+    BelowMinimum = 14,
+    RevealNotEnded = 15,
+    Unauthorized = 16,
+    InvalidEncryptionKey = 17,
+    InvalidCommittee = 18,
+    CommitteeNotFound = 19,
+    ShareAlreadySubmitted = 20,
+    ShareMismatch = 21,
+    WrongOpeningMode = 22,
+    OpeningKeyNotPublished = 23,
+    InvalidBond = 24,
+    NoWinner = 25,
+    EscrowAlreadyFunded = 26,
+    EscrowNotFound = 27,
+    InvalidEscrow = 28,
+    MilestoneNotFound = 29,
+    MilestoneSettled = 30,
+    MilestoneNotDue = 31,
+    InvalidCriteria = 32,
+    InvalidScores = 33,
+    WrongEvaluationMethod = 34,
+    BidNotRevealed = 35,
+    NoPendingAward = 36,
+    NotTied = 37,
+    InvalidLots = 38,
+    LotNotFound = 39,
+    LotRequired = 40,
+    ReasonRequired = 41,
+    DeadlineShortened = 42,
+    RoleNotHeld = 43,
+    SelfRevoke = 44,
+    InvalidApprovalPolicy = 45,
+    AlreadyApproved = 46,
+    NotPendingApproval = 47,
+    TenderNotPublished = 48,
+    AwardNotFinal = 49,
+    BidderNotEligible = 50,
+}
+
+// Ledger closes roughly every 5 seconds
+const LEDGER_SECONDS: u64 = 5;
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+// Admin and tender counter live in instance storage
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+// Tender records stay live this long past the reveal deadline so the
+// outcome can be audited; `bump_tender` restarts the window
+const AUDIT_RETENTION_LEDGERS: u32 = 180 * DAY_IN_LEDGERS;
+
+// Upper bound on a `get_tenders` page, keeping reads within budget
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Weighted evaluation: evaluators score each criterion 0..=MAX_SCORE;
+// combined scores are fixed-point with SCORE_SCALE as 1.0
+const MAX_SCORE: u32 = 100;
+const SCORE_SCALE: i128 = 1_000_000;
+
+/// Admin, TenderCount and DebarmentCount are instance entries; everything keyed by a
+/// tender id is a persistent entry with its own TTL.
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -13,9 +94,140 @@ pub enum DataKey {
     TenderBidders(u64), // List of bidders for a tender
     Winner(u64),
     Admin,
+    Committee(u64),  // Key holders for a tender's sealed bids
+    KeyShares(u64),  // Shares submitted after the reveal deadline
+    OpeningKey(u64), // Time-lock tenders: authority secret published at the deadline
+    Escrow(u64),     // Milestone payments to the winner
+    Scores(u64, Address), // Weighted tenders: evaluator's technical scores for a bid
+    Scorecards(u64),      // Weighted tenders: combined scores computed at close
+    TiedBidders(u64),     // Manual tie-break: bidders awaiting `award_tender`
+    LotBid(u64, u32, Address), // Multi-lot tenders: a bid on one lot
+    LotBidders(u64, u32),      // Multi-lot tenders: bidders on one lot, in bidding order
+    LotWinner(u64, u32),       // Multi-lot tenders: award of one lot
+    Cancellation(u64),         // Why and by whom a tender was cancelled
+    Amendments(u64),           // Changes to a published tender, oldest first
+    Role(Address, Role),       // Present while the address holds the role
+    ApprovalPolicy(Address),   // M-of-N approvers for a procuring entity's tenders
+    TenderApprovers(u64),      // The creator's policy as it stood at creation
+    Approvals(u64, ApprovalStage),
+    Registration(Address),     // Bidder registry entry
+    Debarment(Address),        // Position of a bidder's latest debarment in the record
+    DebarmentCount,
+    DebarredAt(u32),           // Every debarment ever imposed, by position from 0
+    Strikes(Address),          // Tenders a bidder committed to but never revealed
+    RevealOutcomes(u64),       // How each bidder fared at the reveal, recorded at close
 }
 
-#[derive(Clone)]
+/// Privileges granted by a SuperAdmin with `grant_role`. The address passed
+/// to `initialize` starts out as the only SuperAdmin.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Role {
+    /// Grants and revokes roles; may close, award or cancel any tender
+    SuperAdmin,
+    /// May create tenders, and close, award or cancel its own
+    ProcuringEntity,
+    /// May score bids on weighted tenders it is named evaluator of
+    Evaluator,
+    /// Read-only oversight; for off-chain services to gate audit access
+    Auditor,
+    /// Granted by `approve_bidder`; revoked when the bidder re-registers,
+    /// by `suspend_bidder` or by a SuperAdmin. It outlives an expired
+    /// approval, so tenders open to registered bidders check both.
+    Bidder,
+    /// Approves and suspends bidder registrations
+    Verifier,
+}
+
+/// A bidder's entry in the registry. Bidders register themselves with
+/// their credentials; a Verifier approves the registration until an expiry
+/// date, or suspends it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Registration {
+    pub credentials_hash: String, // IPFS hash of the credential documents
+    pub status: RegistrationStatus,
+    pub registered_at: u64,
+    pub verified_by: Option<Address>,
+    pub expires_at: u64, // 0 until approved
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum RegistrationStatus {
+    Pending,
+    Approved,
+    /// Suspended or debarred by a Verifier; only a Verifier's approval
+    /// reinstates the bidder
+    Suspended,
+}
+
+/// Exclusion of a bidder from all tenders between `start` and `end`,
+/// imposed by a SuperAdmin with `debar_bidder`. Debarments stay on the
+/// public record once lifted or replaced.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Debarment {
+    pub bidder: Address,
+    pub reason: String,
+    pub start: u64,
+    pub end: u64,
+    pub debarred_by: Address,
+    pub lifted_at: Option<u64>, // Set when lifted or replaced before `end`
+}
+
+/// Strikes a bidder has collected by committing to a bid and never
+/// revealing it; tenders with `max_strikes` turn away repeat offenders
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StrikeRecord {
+    pub count: u32,
+    pub last_tender: u64,
+    pub last_struck_at: u64,
+}
+
+/// How a bidder's sealed bids stood when the tender closed. A bidder with
+/// several lot bids takes the worst outcome among them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[contracttype]
+pub enum RevealOutcome {
+    Revealed,
+    /// Revealed, but below the minimum bid
+    Invalid,
+    /// Never revealed; earns a strike on bidder-reveal tenders
+    Unrevealed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BidderOutcome {
+    pub bidder: Address,
+    pub outcome: RevealOutcome,
+}
+
+/// Who may bid on a tender
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Eligibility {
+    Open,
+    /// Bidders with an approved, unexpired registration and the Bidder role
+    Registered,
+    /// Only the listed bidders
+    InviteOnly(Vec<Address>),
+}
+
+/// How sealed bids are opened after the bidding deadline
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum OpeningMode {
+    /// Each bidder reveals their own amount and salt
+    BidderReveal,
+    /// The authority publishes its decryption key at the deadline and opens
+    /// every bid itself with `open_bids`; bidders need not take part
+    TimeLock,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Tender {
     pub id: u64,
@@ -26,28 +238,269 @@ pub struct Tender {
     pub deadline: u64,
     pub reveal_deadline: u64,
     pub min_bid: i128,
-    pub is_closed: bool,
+    pub is_closed: bool, // Also set when the tender is cancelled
     pub created_at: u64,
+    /// X25519 public key of the tender authority. Bidders seal their amount
+    /// to it; only the authority can open the sealed bids.
+    pub encryption_key: BytesN<32>,
+    pub opening_mode: OpeningMode,
+    pub bond: BondPolicy,
+    pub evaluation: Evaluation,
+    pub tie_break: TieBreak,
+    /// Empty for a single-lot tender, which takes bids through `submit_bid`
+    pub lots: Vec<Lot>,
+    /// Most lots one bidder can be awarded; 0 for no limit
+    pub max_lots_per_bidder: u32,
+    pub eligibility: Eligibility,
+    /// Bidders with more strikes than this are turned away; 0 for no limit
+    pub max_strikes: u32,
+    pub is_cancelled: bool,
+    /// Awaiting publication approvals; no bids are taken until published
+    pub is_draft: bool,
+    /// Closed with a provisional award that awaits award approvals
+    pub award_pending: bool,
 }
 
-#[derive(Clone)]
+/// M-of-N approval required of a procuring entity's tenders, set by a
+/// SuperAdmin with `set_approval_policy`. Each tender keeps the policy in
+/// force when it was created.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ApprovalPolicy {
+    pub approvers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Decision a tender's approvers sign off on
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ApprovalStage {
+    /// The draft becomes open for bids with `approve_tender`
+    Publication,
+    /// The winners selected at close become final with `approve_award`
+    Award,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Approval {
+    pub approver: Address,
+    pub approved_at: u64,
+}
+
+/// Terms of a tender as set by one `amend_tender` call
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Amendment {
+    pub description: String,
+    pub ipfs_hash: String, // Amended tender documents
+    pub deadline: u64,
+    pub reveal_deadline: u64,
+    pub amended_at: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Cancellation {
+    pub cancelled_by: Address,
+    pub reason: String,
+    pub cancelled_at: u64,
+}
+
+/// Per-tender policies chosen at creation. Passed to `create_tender` as one
+/// argument (contract functions take at most 10) and stored flat on `Tender`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TenderRules {
+    pub opening_mode: OpeningMode,
+    pub bond: BondPolicy,
+    pub evaluation: Evaluation,
+    pub tie_break: TieBreak,
+    pub lots: Vec<Lot>,
+    pub max_lots_per_bidder: u32,
+    pub eligibility: Eligibility,
+    pub max_strikes: u32,
+}
+
+/// Independently awarded part of a tender, e.g. the materials, labour and
+/// equipment of a road project. Bidders commit and reveal per lot with
+/// `submit_lot_bid` and `reveal_lot_bid`; `Tender::min_bid` does not apply.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Lot {
+    pub title: String,
+    pub min_bid: i128,
+}
+
+/// How `close_tender` chooses among bids that rank equal under the award rule
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TieBreak {
+    /// Earliest `Bid::timestamp`; equal timestamps fall back to bidding order
+    EarliestCommit,
+    /// Uniform draw from a fresh `env.prng()` seed, published in a
+    /// ("tie", "lottery") event so the draw can be replayed
+    Lottery,
+    /// No winner at close; the creator or admin picks one of the tied
+    /// bidders with `award_tender`
+    Manual,
+}
+
+/// Award rule: how `close_tender` ranks the valid bids. Under every rule a
+/// bid below `min_bid` is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Evaluation {
+    /// Reverse auction: the cheapest offer wins
+    LowestPrice,
+    /// Sales and concessions: the highest offer wins, `min_bid` is the
+    /// reserve price
+    HighestPrice,
+    /// Second-price sale: the highest offer wins but pays the runner-up's
+    /// offer, or the reserve price when unopposed
+    Vickrey,
+    /// Highest combined score of price and evaluator-scored criteria wins
+    Weighted(WeightedCriteria),
+}
+
+/// Weights are percentages; `price_weight` plus every criterion weight
+/// must sum to 100. The lowest valid price earns the full price score and
+/// other bids a share proportional to lowest / amount.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct WeightedCriteria {
+    pub price_weight: u32,
+    pub criteria: Vec<Criterion>,
+    pub evaluator: Address,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Criterion {
+    pub name: String,
+    pub weight: u32,
+}
+
+/// Score breakdown of one bid, fixed-point with `SCORE_SCALE` as 1.0.
+/// `criteria_scores` are unweighted, in criterion order; `total` is the
+/// weighted sum of price and criteria scores.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Scorecard {
+    pub bidder: Address,
+    pub amount: i128,
+    pub price_score: i128,
+    pub criteria_scores: Vec<i128>,
+    pub total: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum BondPolicy {
+    NotRequired,
+    Required(BidBond),
+}
+
+/// Security deposit every bidder escrows with their bid. Refunded at close
+/// for valid bids; forfeited to `beneficiary` (the creator or a treasury)
+/// for bids that were never validly revealed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BidBond {
+    pub token: Address,
+    pub amount: i128,
+    pub beneficiary: Address,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Bid {
     pub bidder: Address,
     pub tender_id: u64,
-    pub encrypted_amount: String, // User sends this
+    pub encrypted_amount: String, // Amount sealed to `Tender::encryption_key`, base64
+    pub commitment: BytesN<32>,   // sha256(amount || salt || bidder || tender_id)
     pub revealed_amount: Option<i128>,
     pub is_valid: bool,
     pub timestamp: u64,
+    pub bond: i128, // Amount held in escrow for this bid; 0 once settled
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Winner {
     pub tender_id: u64,
     pub bidder: Address,
-    pub amount: i128,
+    pub amount: i128,  // Winning bid
+    pub payable: i128, // Price due under the award rule; differs from `amount` for Vickrey
     pub selected_at: u64,
+    pub lot: Option<u32>, // Lot index on multi-lot tenders
+}
+
+/// Evaluation committee holding Shamir shares of the tender's decryption
+/// key. `share_hashes[i]` is sha256 of the share dealt to `members[i]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Committee {
+    pub members: Vec<Address>,
+    pub share_hashes: Vec<BytesN<32>>,
+    pub threshold: u32,
+}
+
+/// One entry of an `open_bids` batch: the opening of a bidder's commitment
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BidOpening {
+    pub bidder: Address,
+    pub amount: i128,
+    pub salt: BytesN<32>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct KeyShare {
+    pub member: Address,
+    pub share: Bytes,
+    pub submitted_at: u64,
+}
+
+/// Payment for the awarded contract, held by this contract and paid out to
+/// the winner milestone by milestone
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Escrow {
+    pub token: Address,
+    pub amount: i128,
+    /// May sign off milestones in addition to the creator
+    pub inspector: Option<Address>,
+    pub milestones: Vec<Milestone>,
+    pub funded_at: u64,
+}
+
+/// Milestone as defined by the creator when funding the escrow
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MilestoneTerms {
+    pub description: String,
+    pub percentage: u32, // Share of the escrow; all milestones sum to 100
+    pub due_date: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Milestone {
+    pub description: String,
+    pub percentage: u32,
+    pub due_date: u64,
+    pub amount: i128, // The last milestone also takes the rounding remainder
+    pub status: MilestoneStatus,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum MilestoneStatus {
+    Pending,
+    Released, // Paid to the winner
+    Refunded, // Returned to the creator after the due date
 }
 
 #[contract]
@@ -57,16 +510,289 @@ pub struct OpenTenderContract;
 impl OpenTenderContract {
     
     /// Initialize contract with admin
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), TenderError> {
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            return Err(TenderError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
+        set_role(&env, &admin, Role::SuperAdmin);
         env.storage().instance().set(&DataKey::TenderCount, &0u64);
+        extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Grant a role to an address. SuperAdmin only; granting a role the
+    /// address already holds is a no-op.
+    pub fn grant_role(env: Env, caller: Address, account: Address, role: Role) -> Result<(), TenderError> {
+        caller.require_auth();
+        require_role(&env, &caller, Role::SuperAdmin)?;
+
+        if has_role(&env, &account, role) {
+            return Ok(());
+        }
+        set_role(&env, &account, role);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("role"), symbol_short!("granted")),
+            (account, role, caller)
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a role from an address. SuperAdmin only; a SuperAdmin cannot
+    /// revoke its own SuperAdmin role, so the contract always keeps one.
+    pub fn revoke_role(env: Env, caller: Address, account: Address, role: Role) -> Result<(), TenderError> {
+        caller.require_auth();
+        require_role(&env, &caller, Role::SuperAdmin)?;
+
+        if role == Role::SuperAdmin && account == caller {
+            return Err(TenderError::SelfRevoke);
+        }
+        if !has_role(&env, &account, role) {
+            return Err(TenderError::RoleNotHeld);
+        }
+        env.storage().persistent().remove(&DataKey::Role(account.clone(), role));
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("role"), symbol_short!("revoked")),
+            (account, role, caller)
+        );
+
+        Ok(())
+    }
+
+    /// Register, or re-register with new credentials, in the bidder
+    /// registry. The registration is pending until a Verifier approves it;
+    /// suspended bidders cannot re-register.
+    pub fn register_bidder(env: Env, bidder: Address, credentials_hash: String) -> Result<(), TenderError> {
+        bidder.require_auth();
+
+        let key = DataKey::Registration(bidder.clone());
+        let existing: Option<Registration> = env.storage().persistent().get(&key);
+        if matches!(existing, Some(r) if r.status == RegistrationStatus::Suspended) {
+            return Err(TenderError::BidderNotEligible);
+        }
+        // New credentials go back to a Verifier before the bidder may bid
+        revoke_bidder_role(&env, &bidder, &bidder);
+
+        let registration = Registration {
+            credentials_hash: credentials_hash.clone(),
+            status: RegistrationStatus::Pending,
+            registered_at: env.ledger().timestamp(),
+            verified_by: None,
+            expires_at: 0,
+        };
+        env.storage().persistent().set(&key, &registration);
+        extend_registry_ttl(&env, &key);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("bidder"), symbol_short!("applied")),
+            (bidder, credentials_hash)
+        );
+
+        Ok(())
+    }
+
+    /// Approve a registration until `expires_at`, or reinstate a suspended
+    /// one. Verifier only; unregistered bidders are not eligible.
+    pub fn approve_bidder(
+        env: Env,
+        verifier: Address,
+        bidder: Address,
+        expires_at: u64,
+    ) -> Result<(), TenderError> {
+        verifier.require_auth();
+        require_role(&env, &verifier, Role::Verifier)?;
+
+        let key = DataKey::Registration(bidder.clone());
+        let mut registration: Registration = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(TenderError::BidderNotEligible)?;
+
+        if expires_at <= env.ledger().timestamp() {
+            return Err(TenderError::InvalidDeadline);
+        }
+
+        registration.status = RegistrationStatus::Approved;
+        registration.verified_by = Some(verifier.clone());
+        registration.expires_at = expires_at;
+        env.storage().persistent().set(&key, &registration);
+        extend_registry_ttl(&env, &key);
+
+        if !has_role(&env, &bidder, Role::Bidder) {
+            set_role(&env, &bidder, Role::Bidder);
+            env.events().publish(
+                (symbol_short!("role"), symbol_short!("granted")),
+                (bidder.clone(), Role::Bidder, verifier.clone())
+            );
+        }
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("bidder"), symbol_short!("approved")),
+            (bidder, verifier, expires_at)
+        );
+
+        Ok(())
+    }
+
+    /// Suspend a registered bidder. Verifier only; bids already placed
+    /// stand, but the bidder cannot bid on tenders that require
+    /// registration until reinstated.
+    pub fn suspend_bidder(env: Env, verifier: Address, bidder: Address) -> Result<(), TenderError> {
+        verifier.require_auth();
+        require_role(&env, &verifier, Role::Verifier)?;
+
+        let key = DataKey::Registration(bidder.clone());
+        let mut registration: Registration = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(TenderError::BidderNotEligible)?;
+
+        registration.status = RegistrationStatus::Suspended;
+        registration.verified_by = Some(verifier.clone());
+        env.storage().persistent().set(&key, &registration);
+        extend_registry_ttl(&env, &key);
+
+        revoke_bidder_role(&env, &bidder, &verifier);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("bidder"), symbol_short!("suspended")),
+            (bidder, verifier)
+        );
+
+        Ok(())
+    }
+
+    /// Debar a bidder from every tender between `start` and `end`,
+    /// replacing any earlier debarment, which is marked lifted. SuperAdmin
+    /// only, with a reason for the record. While debarred the bidder cannot
+    /// bid, and tenders closing in the period pass over bids it already
+    /// placed.
+    pub fn debar_bidder(
+        env: Env,
+        caller: Address,
+        bidder: Address,
+        reason: String,
+        start: u64,
+        end: u64,
+    ) -> Result<(), TenderError> {
+        caller.require_auth();
+        require_role(&env, &caller, Role::SuperAdmin)?;
+
+        if reason.is_empty() {
+            return Err(TenderError::ReasonRequired);
+        }
+
+        if end <= start || end <= env.ledger().timestamp() {
+            return Err(TenderError::InvalidDeadline);
+        }
+
+        end_debarment(&env, &bidder);
+
+        let position: u32 = env.storage()
+            .instance()
+            .get(&DataKey::DebarmentCount)
+            .unwrap_or(0);
+        env.storage().instance().set(&DataKey::DebarmentCount, &(position + 1));
+
+        let debarment = Debarment {
+            bidder: bidder.clone(),
+            reason,
+            start,
+            end,
+            debarred_by: caller,
+            lifted_at: None,
+        };
+        let key = DataKey::DebarredAt(position);
+        env.storage().persistent().set(&key, &debarment);
+        extend_registry_ttl(&env, &key);
+
+        let latest = DataKey::Debarment(bidder);
+        env.storage().persistent().set(&latest, &position);
+        extend_registry_ttl(&env, &latest);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("debarment"), symbol_short!("imposed")),
+            debarment
+        );
+
+        Ok(())
+    }
+
+    /// Lift a bidder's debarment early; it stays on the record, marked
+    /// lifted. SuperAdmin only; a no-op for a bidder with no debarment
+    /// still to run.
+    pub fn lift_debarment(env: Env, caller: Address, bidder: Address) -> Result<(), TenderError> {
+        caller.require_auth();
+        require_role(&env, &caller, Role::SuperAdmin)?;
+
+        if !end_debarment(&env, &bidder) {
+            return Ok(());
+        }
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("debarment"), symbol_short!("lifted")),
+            (bidder, caller)
+        );
+
+        Ok(())
+    }
+
+    /// Require M-of-N approval of an entity's tender publications and
+    /// awards from now on. SuperAdmin only; an empty approver list removes
+    /// the policy. Existing tenders keep the policy they were created under.
+    pub fn set_approval_policy(
+        env: Env,
+        caller: Address,
+        entity: Address,
+        policy: ApprovalPolicy,
+    ) -> Result<(), TenderError> {
+        caller.require_auth();
+        require_role(&env, &caller, Role::SuperAdmin)?;
+
+        let key = DataKey::ApprovalPolicy(entity.clone());
+        if policy.approvers.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            let mut distinct: Vec<Address> = Vec::new(&env);
+            for approver in policy.approvers.iter() {
+                if !distinct.contains(&approver) {
+                    distinct.push_back(approver);
+                }
+            }
+            if distinct.len() != policy.approvers.len()
+                || policy.threshold == 0
+                || policy.threshold > policy.approvers.len()
+            {
+                return Err(TenderError::InvalidApprovalPolicy);
+            }
+            env.storage().persistent().set(&key, &policy);
+            extend_registry_ttl(&env, &key);
+        }
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("policy"), symbol_short!("set")),
+            (entity, policy)
+        );
+
+        Ok(())
     }
 
-    /// Create a new tender (simplified - admin or verified users only)
+    /// Create a new tender. The creator must hold the ProcuringEntity role.
+    /// Under an approval policy the tender starts as a draft that opens for
+    /// bids once enough approvers call `approve_tender`.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_tender(
         env: Env,
         creator: Address,
@@ -76,18 +802,54 @@ impl OpenTenderContract {
         deadline: u64,
         reveal_deadline: u64,
         min_bid: i128,
-    ) -> u64 {
-        // creator.require_auth();
+        encryption_key: BytesN<32>,
+        rules: TenderRules,
+    ) -> Result<u64, TenderError> {
+        creator.require_auth();
+        require_role(&env, &creator, Role::ProcuringEntity)?;
+
+        let TenderRules { opening_mode, bond, evaluation, tie_break, lots, max_lots_per_bidder, eligibility, max_strikes } = rules;
         
         let current_time = env.ledger().timestamp();
+
+        // An all-zero X25519 key yields an all-zero shared secret
+        if encryption_key.to_array() == [0u8; 32] {
+            return Err(TenderError::InvalidEncryptionKey);
+        }
         
         // Validate deadlines
         if deadline <= current_time {
-            panic!("Deadline must be in future");
+            return Err(TenderError::InvalidDeadline);
         }
         
         if reveal_deadline <= deadline {
-            panic!("Reveal deadline must be after bidding deadline");
+            return Err(TenderError::InvalidRevealDeadline);
+        }
+
+        if matches!(&bond, BondPolicy::Required(b) if b.amount <= 0) {
+            return Err(TenderError::InvalidBond);
+        }
+
+        if let Evaluation::Weighted(weighted) = &evaluation {
+            let total = weighted.criteria
+                .iter()
+                .try_fold(weighted.price_weight, |total, c| total.checked_add(c.weight));
+            if total != Some(100) || weighted.criteria.is_empty() {
+                return Err(TenderError::InvalidCriteria);
+            }
+        }
+
+        // Lots are revealed by their bidders and awarded on price alone,
+        // all at close
+        if lots.is_empty() {
+            if max_lots_per_bidder != 0 {
+                return Err(TenderError::InvalidLots);
+            }
+        } else if opening_mode != OpeningMode::BidderReveal
+            || matches!(evaluation, Evaluation::Weighted(_))
+            || tie_break == TieBreak::Manual
+        {
+            return Err(TenderError::InvalidLots);
         }
 
         let mut count: u64 = env.storage()
@@ -97,6 +859,13 @@ impl OpenTenderContract {
         
         count += 1;
 
+        let policy_key = DataKey::ApprovalPolicy(creator.clone());
+        let policy: Option<ApprovalPolicy> = env.storage().persistent().get(&policy_key);
+        if let Some(policy) = &policy {
+            extend_registry_ttl(&env, &policy_key);
+            env.storage().persistent().set(&DataKey::TenderApprovers(count), policy);
+        }
+
         let tender = Tender {
             id: count,
             creator,
@@ -108,406 +877,2194 @@ impl OpenTenderContract {
             min_bid,
             is_closed: false,
             created_at: current_time,
+            encryption_key,
+            opening_mode,
+            bond,
+            evaluation,
+            tie_break,
+            lots,
+            max_lots_per_bidder,
+            eligibility,
+            max_strikes,
+            is_cancelled: false,
+            is_draft: policy.is_some(),
+            award_pending: false,
         };
 
-        env.storage().instance().set(&DataKey::Tender(count), &tender);
+        env.storage().persistent().set(&DataKey::Tender(count), &tender);
         env.storage().instance().set(&DataKey::TenderCount, &count);
         
         // Initialize empty bidders list
         let bidders: Vec<Address> = Vec::new(&env);
-        env.storage().instance().set(&DataKey::TenderBidders(count), &bidders);
+        env.storage().persistent().set(&DataKey::TenderBidders(count), &bidders);
+
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
 
         env.events().publish(
             (symbol_short!("tender"), symbol_short!("created")),
             count
         );
 
-        count
+        Ok(count)
     }
 
-    /// Submit bid - User just sends their bid amount as encrypted string
-    /// The encryption happens client-side, contract just stores it
+    /// Submit bid - User sends their bid amount sealed to the tender's
+    /// encryption key together with a commitment that binds the amount
+    /// revealed later. Sealing happens client-side, contract just stores it
     pub fn submit_bid(
         env: Env,
         tender_id: u64,
         bidder: Address,
         encrypted_amount: String, // Client sends encrypted bid
-    ) {
-        // bidder.require_auth();
+        commitment: BytesN<32>,   // See `bid_commitment` for the preimage layout
+    ) -> Result<(), TenderError> {
+        bidder.require_auth();
 
         let tender: Tender = env.storage()
-            .instance()
+            .persistent()
             .get(&DataKey::Tender(tender_id))
-            .expect("Tender not found");
-
-        let current_time = env.ledger().timestamp();
-        
-        if current_time >= tender.deadline {
-            panic!("Bidding deadline has passed");
-        }
+            .ok_or(TenderError::TenderNotFound)?;
 
-        if tender.is_closed {
-            panic!("Tender is closed");
+        if !tender.lots.is_empty() {
+            return Err(TenderError::LotRequired);
         }
 
-        // Check if bidder already submitted
         let bid_key = DataKey::Bid(tender_id, bidder.clone());
-        if env.storage().instance().has(&bid_key) {
-            panic!("Bid already submitted. Cannot modify bid.");
-        }
-
-        let bid = Bid {
-            bidder: bidder.clone(),
-            tender_id,
-            encrypted_amount,
-            revealed_amount: None,
-            is_valid: false,
-            timestamp: current_time,
-        };
-
-        env.storage().instance().set(&bid_key, &bid);
+        commit_bid(&env, &tender, &bid_key, &bidder, encrypted_amount, commitment)?;
         
         // Add bidder to list
         let mut bidders: Vec<Address> = env.storage()
-            .instance()
+            .persistent()
             .get(&DataKey::TenderBidders(tender_id))
             .unwrap_or(Vec::new(&env));
         bidders.push_back(bidder.clone());
-        env.storage().instance().set(&DataKey::TenderBidders(tender_id), &bidders);
+        env.storage().persistent().set(&DataKey::TenderBidders(tender_id), &bidders);
+
+        extend_tender_ttl(&env, &tender);
+        extend_bid_ttl(&env, &tender, &bid_key);
+        extend_instance_ttl(&env);
 
         env.events().publish(
             (symbol_short!("bid"), symbol_short!("submitted")),
             (tender_id, bidder)
         );
+
+        Ok(())
     }
 
-    /// Reveal bid - User provides decryption key and actual amount
-    pub fn reveal_bid(
+    /// Multi-lot tenders: submit a sealed bid on one lot. Works like
+    /// `submit_bid`, with one bid (and bond) per lot; the commitment has the
+    /// same layout and is only ever checked against this lot's reveal.
+    pub fn submit_lot_bid(
         env: Env,
         tender_id: u64,
+        lot: u32,
         bidder: Address,
-        actual_amount: i128,
-        _decryption_key: String,
-    ) {
-        // bidder.require_auth();
+        encrypted_amount: String,
+        commitment: BytesN<32>,
+    ) -> Result<(), TenderError> {
+        bidder.require_auth();
 
         let tender: Tender = env.storage()
-            .instance()
+            .persistent()
             .get(&DataKey::Tender(tender_id))
-            .expect("Tender not found");
-
-        let current_time = env.ledger().timestamp();
-        
-        if current_time < tender.deadline {
-            panic!("Cannot reveal before bidding deadline");
-        }
+            .ok_or(TenderError::TenderNotFound)?;
 
-        if current_time >= tender.reveal_deadline {
-            panic!("Reveal deadline has passed");
+        if lot >= tender.lots.len() {
+            return Err(TenderError::LotNotFound);
         }
 
-        let mut bid: Bid = env.storage()
-            .instance()
-            .get(&DataKey::Bid(tender_id, bidder.clone()))
-            .expect("No bid found for this bidder");
+        let bid_key = DataKey::LotBid(tender_id, lot, bidder.clone());
+        commit_bid(&env, &tender, &bid_key, &bidder, encrypted_amount, commitment)?;
 
-        if bid.revealed_amount.is_some() {
-            panic!("Bid already revealed");
-        }
+        let storage = env.storage().persistent();
+        let mut lot_bidders: Vec<Address> = storage
+            .get(&DataKey::LotBidders(tender_id, lot))
+            .unwrap_or(Vec::new(&env));
+        lot_bidders.push_back(bidder.clone());
+        storage.set(&DataKey::LotBidders(tender_id, lot), &lot_bidders);
 
-        // Verify amount meets minimum
-        if actual_amount < tender.min_bid {
-            panic!("Bid amount below minimum requirement");
+        // The tender's bidder list holds each bidder once, whatever they bid on
+        let mut bidders: Vec<Address> = storage
+            .get(&DataKey::TenderBidders(tender_id))
+            .unwrap_or(Vec::new(&env));
+        if !bidders.contains(&bidder) {
+            bidders.push_back(bidder.clone());
+            storage.set(&DataKey::TenderBidders(tender_id), &bidders);
         }
 
-        // In production, verify decryption_key matches encrypted_amount
-        // For now, we trust the reveal (frontend handles encryption/decryption)
-        
-        bid.revealed_amount = Some(actual_amount);
-        bid.is_valid = true;
-
-        env.storage()
-            .instance()
-            .set(&DataKey::Bid(tender_id, bidder.clone()), &bid);
+        extend_tender_ttl(&env, &tender);
+        extend_bid_ttl(&env, &tender, &bid_key);
+        extend_instance_ttl(&env);
 
         env.events().publish(
-            (symbol_short!("bid"), symbol_short!("revealed")),
-            (tender_id, bidder, actual_amount)
+            (symbol_short!("lot"), symbol_short!("bid")),
+            (tender_id, lot, bidder)
         );
+
+        Ok(())
     }
 
-    /// Close tender and automatically select winner (lowest valid bid)
-    pub fn close_tender(env: Env, tender_id: u64, caller: Address) {
-        // caller.require_auth();
+    /// Reveal bid - User provides actual amount and the salt used in the commitment
+    pub fn reveal_bid(
+        env: Env,
+        tender_id: u64,
+        bidder: Address,
+        actual_amount: i128,
+        salt: BytesN<32>,
+    ) -> Result<(), TenderError> {
+        bidder.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        let bid_key = DataKey::Bid(tender_id, bidder.clone());
+        open_bid(&env, &tender, &bid_key, &bidder, actual_amount, &salt, tender.min_bid)?;
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("bid"), symbol_short!("revealed")),
+            (tender_id, bidder, actual_amount)
+        );
+
+        Ok(())
+    }
+
+    /// Multi-lot tenders: reveal the amount and salt of a bid on one lot
+    pub fn reveal_lot_bid(
+        env: Env,
+        tender_id: u64,
+        lot: u32,
+        bidder: Address,
+        actual_amount: i128,
+        salt: BytesN<32>,
+    ) -> Result<(), TenderError> {
+        bidder.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        let min_bid = tender.lots.get(lot).ok_or(TenderError::LotNotFound)?.min_bid;
+        let bid_key = DataKey::LotBid(tender_id, lot, bidder.clone());
+        open_bid(&env, &tender, &bid_key, &bidder, actual_amount, &salt, min_bid)?;
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("lot"), symbol_short!("revealed")),
+            (tender_id, lot, bidder, actual_amount)
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a bid before the bidding deadline and get its bond back.
+    /// On multi-lot tenders this withdraws the bidder's bids on every lot.
+    pub fn withdraw_bid(env: Env, tender_id: u64, bidder: Address) -> Result<(), TenderError> {
+        bidder.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        bidding_open(&env, &tender)?;
+
+        let storage = env.storage().persistent();
+        let mut bid_keys = Vec::new(&env);
+        if tender.lots.is_empty() {
+            bid_keys.push_back(DataKey::Bid(tender_id, bidder.clone()));
+        }
+        for lot in 0..tender.lots.len() {
+            bid_keys.push_back(DataKey::LotBid(tender_id, lot, bidder.clone()));
+        }
+
+        let mut withdrawn = false;
+        for bid_key in bid_keys.iter() {
+            let Some(bid) = storage.get::<DataKey, Bid>(&bid_key) else {
+                continue;
+            };
+            if let BondPolicy::Required(bond) = &tender.bond {
+                refund_bond(&env, tender_id, bond, &bid);
+            }
+            storage.remove(&bid_key);
+            if let DataKey::LotBid(_, lot, _) = bid_key {
+                remove_bidder(&env, &DataKey::LotBidders(tender_id, lot), &bidder);
+            }
+            withdrawn = true;
+        }
+
+        if !withdrawn {
+            return Err(TenderError::BidNotFound);
+        }
+
+        remove_bidder(&env, &DataKey::TenderBidders(tender_id), &bidder);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("bid"), symbol_short!("withdrawn")),
+            (tender_id, bidder)
+        );
+
+        Ok(())
+    }
+
+    /// Replace the sealed amount and commitment of a bid before the bidding
+    /// deadline. The bond stays escrowed; the commit time restarts.
+    pub fn replace_bid(
+        env: Env,
+        tender_id: u64,
+        bidder: Address,
+        encrypted_amount: String,
+        commitment: BytesN<32>,
+    ) -> Result<(), TenderError> {
+        bidder.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        if !tender.lots.is_empty() {
+            return Err(TenderError::LotRequired);
+        }
+
+        let bid_key = DataKey::Bid(tender_id, bidder.clone());
+        reseal_bid(&env, &tender, &bid_key, encrypted_amount, commitment)?;
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("bid"), symbol_short!("replaced")),
+            (tender_id, bidder)
+        );
+
+        Ok(())
+    }
+
+    /// Multi-lot tenders: replace a bid on one lot before the bidding deadline
+    pub fn replace_lot_bid(
+        env: Env,
+        tender_id: u64,
+        lot: u32,
+        bidder: Address,
+        encrypted_amount: String,
+        commitment: BytesN<32>,
+    ) -> Result<(), TenderError> {
+        bidder.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        if lot >= tender.lots.len() {
+            return Err(TenderError::LotNotFound);
+        }
+
+        let bid_key = DataKey::LotBid(tender_id, lot, bidder.clone());
+        reseal_bid(&env, &tender, &bid_key, encrypted_amount, commitment)?;
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("lot"), symbol_short!("replaced")),
+            (tender_id, lot, bidder)
+        );
+
+        Ok(())
+    }
+
+    /// Time-lock tenders: the creator publishes the secret key matching
+    /// `Tender::encryption_key` once bidding is over, so anyone can open the
+    /// sealed bids and check the `open_bids` batch against them.
+    pub fn publish_opening_key(
+        env: Env,
+        tender_id: u64,
+        creator: Address,
+        secret_key: BytesN<32>,
+    ) -> Result<(), TenderError> {
+        creator.require_auth();
+
+        let tender = opening_tender(&env, tender_id)?;
+
+        if creator != tender.creator {
+            return Err(TenderError::Unauthorized);
+        }
+
+        env.storage().persistent().set(&DataKey::OpeningKey(tender_id), &secret_key);
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("key"), symbol_short!("published")),
+            (tender_id, secret_key)
+        );
+
+        Ok(())
+    }
+
+    /// Time-lock tenders: record bids' amounts, opened with the published
+    /// key. Anyone may submit openings, in as many batches as they like,
+    /// until the reveal deadline. Each entry must open the bidder's
+    /// commitment, so no one can misstate an amount; bids already revealed
+    /// are left untouched. Bids still sealed at close are passed over and
+    /// their bonds forfeited.
+    pub fn open_bids(
+        env: Env,
+        tender_id: u64,
+        caller: Address,
+        openings: Vec<BidOpening>,
+    ) -> Result<(), TenderError> {
+        caller.require_auth();
+
+        let tender = opening_tender(&env, tender_id)?;
+
+        if !env.storage().persistent().has(&DataKey::OpeningKey(tender_id)) {
+            return Err(TenderError::OpeningKeyNotPublished);
+        }
+
+        for opening in openings.iter() {
+            let bid_key = DataKey::Bid(tender_id, opening.bidder.clone());
+            let mut bid: Bid = env.storage()
+                .persistent()
+                .get(&bid_key)
+                .ok_or(TenderError::BidNotFound)?;
+
+            if bid.revealed_amount.is_some() {
+                continue;
+            }
+
+            let expected = bid_commitment(&env, tender_id, &opening.bidder, opening.amount, &opening.salt);
+            if expected != bid.commitment {
+                return Err(TenderError::CommitmentMismatch);
+            }
+
+            // Below-minimum bids are recorded but can never win
+            bid.revealed_amount = Some(opening.amount);
+            bid.is_valid = opening.amount >= tender.min_bid;
+
+            env.storage().persistent().set(&bid_key, &bid);
+            extend_bid_ttl(&env, &tender, &bid_key);
+
+            if bid.is_valid {
+                env.events().publish(
+                    (symbol_short!("bid"), symbol_short!("revealed")),
+                    (tender_id, opening.bidder, opening.amount)
+                );
+            }
+        }
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Close tender and select the winner under its award rule. Tenders
+    /// with a key committee need its quorum of shares first.
+    pub fn close_tender(env: Env, tender_id: u64, caller: Address) -> Result<(), TenderError> {
+        caller.require_auth();
 
         let mut tender: Tender = env.storage()
-            .instance()
+            .persistent()
             .get(&DataKey::Tender(tender_id))
-            .expect("Tender not found");
+            .ok_or(TenderError::TenderNotFound)?;
 
         let current_time = env.ledger().timestamp();
 
         if current_time < tender.reveal_deadline {
-            panic!("Cannot close before reveal deadline");
+            return Err(TenderError::RevealNotEnded);
         }
 
         if tender.is_closed {
-            panic!("Tender already closed");
+            return Err(TenderError::TenderClosed);
         }
 
-        // Verify caller is admin or creator
-        let admin: Address = env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+        if tender.is_draft {
+            return Err(TenderError::TenderNotPublished);
+        }
+
+        authorize_creator(&env, &tender, &caller)?;
 
-        if caller != admin && caller != tender.creator {
-            panic!("Only admin or creator can close tender");
+        // Under a key committee the sealed bids are only openable, and the
+        // reveal phase only over, once a quorum has handed in its shares
+        if let Some(committee) = env.storage()
+            .persistent()
+            .get::<_, Committee>(&DataKey::Committee(tender_id))
+        {
+            let shares: Vec<KeyShare> = env.storage()
+                .persistent()
+                .get(&DataKey::KeyShares(tender_id))
+                .unwrap_or(Vec::new(&env));
+            if shares.len() < committee.threshold {
+                return Err(TenderError::RevealNotEnded);
+            }
         }
 
-        // Find winner (lowest valid bid)
         let bidders: Vec<Address> = env.storage()
-            .instance()
+            .persistent()
             .get(&DataKey::TenderBidders(tender_id))
             .unwrap_or(Vec::new(&env));
 
-        let mut lowest_amount: Option<i128> = None;
-        let mut winner_address: Option<Address> = None;
-
-        // Iterate through all bidders to find lowest valid bid
-        for i in 0..bidders.len() {
-            if let Some(bidder_addr) = bidders.get(i) {
-                if let Some(bid) = env.storage()
-                    .instance()
-                    .get::<DataKey, Bid>(&DataKey::Bid(tender_id, bidder_addr.clone())) {
-                    
-                    if bid.is_valid {
-                        if let Some(amount) = bid.revealed_amount {
-                            if lowest_amount.is_none() || amount < lowest_amount.unwrap() {
-                                lowest_amount = Some(amount);
-                                winner_address = Some(bidder_addr);
-                            }
-                        }
-                    }
-                }
-            }
+        if tender.lots.is_empty() {
+            award_tender_outright(&env, &tender, &bidders);
+        } else {
+            award_lots(&env, &tender);
         }
 
-        // Store winner if found
-        if let (Some(amount), Some(winner)) = (lowest_amount, winner_address) {
-            let winner_data = Winner {
-                tender_id,
-                bidder: winner.clone(),
-                amount,
-                selected_at: current_time,
-            };
-            env.storage().instance().set(&DataKey::Winner(tender_id), &winner_data);
-            
-            env.events().publish(
-                (symbol_short!("winner"), symbol_short!("selected")),
-                (tender_id, winner, amount)
-            );
+        let bid_keys = bid_keys(&env, &tender, &bidders);
+        if let BondPolicy::Required(bond) = &tender.bond {
+            settle_bonds(&env, tender_id, bond, &bid_keys, false);
         }
+        record_reveal_outcomes(&env, &tender, &bidders);
+
+        // Under an approval policy any award made here is provisional
+        let storage = env.storage().persistent();
+        tender.award_pending = storage.has(&DataKey::TenderApprovers(tender_id))
+            && (storage.has(&DataKey::Winner(tender_id))
+                || storage.has(&DataKey::TiedBidders(tender_id))
+                || (0..tender.lots.len()).any(|lot| storage.has(&DataKey::LotWinner(tender_id, lot))));
 
         tender.is_closed = true;
-        env.storage().instance().set(&DataKey::Tender(tender_id), &tender);
+        env.storage().persistent().set(&DataKey::Tender(tender_id), &tender);
+
+        // Closing starts the audit retention window for the whole record
+        extend_tender_ttl(&env, &tender);
+        for bid_key in bid_keys.iter() {
+            extend_bid_ttl(&env, &tender, &bid_key);
+        }
+        for bidder_addr in bidders.iter() {
+            extend_bid_ttl(&env, &tender, &DataKey::Scores(tender_id, bidder_addr));
+        }
+        extend_instance_ttl(&env);
 
         env.events().publish(
             (symbol_short!("tender"), symbol_short!("closed")),
             tender_id
         );
+        if tender.award_pending {
+            env.events().publish(
+                (symbol_short!("award"), symbol_short!("pending")),
+                tender_id
+            );
+        }
+
+        Ok(())
     }
+    /// Manual tie-break: after closing, the creator or admin awards the
+    /// tender to one of the tied bidders
+    pub fn award_tender(
+        env: Env,
+        tender_id: u64,
+        caller: Address,
+        bidder: Address,
+    ) -> Result<(), TenderError> {
+        caller.require_auth();
 
-    /// Get tender details
-    pub fn get_tender(env: Env, tender_id: u64) -> Tender {
-        env.storage()
-            .instance()
+        let tender: Tender = env.storage()
+            .persistent()
             .get(&DataKey::Tender(tender_id))
-            .expect("Tender not found")
-    }
+            .ok_or(TenderError::TenderNotFound)?;
 
-    /// Get all tenders (returns list of IDs)
-    pub fn get_all_tenders(env: Env) -> Vec<u64> {
-        let count = env.storage()
-            .instance()
-            .get(&DataKey::TenderCount)
-            .unwrap_or(0u64);
-        
-        let mut tender_ids = Vec::new(&env);
-        for i in 1..=count {
-            tender_ids.push_back(i);
+        authorize_creator(&env, &tender, &caller)?;
+
+        let tied: Vec<Address> = env.storage()
+            .persistent()
+            .get(&DataKey::TiedBidders(tender_id))
+            .ok_or(TenderError::NoPendingAward)?;
+
+        if !tied.contains(&bidder) {
+            return Err(TenderError::NotTied);
         }
-        tender_ids
-    }
 
-    /// Get total tender count
-    pub fn get_tender_count(env: Env) -> u64 {
-        env.storage()
-            .instance()
-            .get(&DataKey::TenderCount)
-            .unwrap_or(0)
-    }
+        if is_debarred(&env, &bidder) {
+            return Err(TenderError::BidderNotEligible);
+        }
 
-    /// Get bid details (only bidder can see their own bid before reveal)
-    pub fn get_bid(env: Env, tender_id: u64, bidder: Address) -> Option<Bid> {
-        env.storage()
-            .instance()
-            .get(&DataKey::Bid(tender_id, bidder))
-    }
+        let bidders: Vec<Address> = env.storage()
+            .persistent()
+            .get(&DataKey::TenderBidders(tender_id))
+            .unwrap_or(Vec::new(&env));
+        let valid_bids = valid_bids(&env, tender_id, None, &bidders);
 
-    /// Get winner details
-    pub fn get_winner(env: Env, tender_id: u64) -> Option<Winner> {
-        env.storage()
-            .instance()
-            .get(&DataKey::Winner(tender_id))
+        env.storage().persistent().remove(&DataKey::TiedBidders(tender_id));
+        record_winner(&env, &tender, None, &valid_bids, bidder, tied);
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
-    /// Get all bidders for a tender (public after reveal deadline)
-    pub fn get_tender_bidders(env: Env, tender_id: u64) -> Vec<Address> {
-        env.storage()
-            .instance()
+    /// Amend a published tender: new documents or description, and later
+    /// deadlines. Creator only, strictly before the bidding deadline;
+    /// timelines can be extended but never shortened.
+    pub fn amend_tender(
+        env: Env,
+        tender_id: u64,
+        creator: Address,
+        description: String,
+        ipfs_hash: String,
+        deadline: u64,
+        reveal_deadline: u64,
+    ) -> Result<(), TenderError> {
+        creator.require_auth();
+
+        let mut tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        if creator != tender.creator {
+            return Err(TenderError::Unauthorized);
+        }
+
+        if !tender.is_draft {
+            bidding_open(&env, &tender)?;
+        } else if tender.is_closed {
+            return Err(TenderError::TenderClosed);
+        } else if deadline <= env.ledger().timestamp() {
+            // A draft can still be amended once approvals overran its
+            // deadline, but only onto a timeline in the future
+            return Err(TenderError::InvalidDeadline);
+        }
+
+        if deadline < tender.deadline || reveal_deadline < tender.reveal_deadline {
+            return Err(TenderError::DeadlineShortened);
+        }
+
+        if reveal_deadline <= deadline {
+            return Err(TenderError::InvalidRevealDeadline);
+        }
+
+        tender.description = description.clone();
+        tender.ipfs_hash = ipfs_hash.clone();
+        tender.deadline = deadline;
+        tender.reveal_deadline = reveal_deadline;
+        env.storage().persistent().set(&DataKey::Tender(tender_id), &tender);
+
+        let amendment = Amendment {
+            description,
+            ipfs_hash,
+            deadline,
+            reveal_deadline,
+            amended_at: env.ledger().timestamp(),
+        };
+        let mut amendments: Vec<Amendment> = env.storage()
+            .persistent()
+            .get(&DataKey::Amendments(tender_id))
+            .unwrap_or(Vec::new(&env));
+        amendments.push_back(amendment.clone());
+        env.storage().persistent().set(&DataKey::Amendments(tender_id), &amendments);
+
+        // A later reveal deadline moves the whole retention window
+        let bidders: Vec<Address> = env.storage()
+            .persistent()
             .get(&DataKey::TenderBidders(tender_id))
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        extend_tender_ttl(&env, &tender);
+        for bid_key in bid_keys(&env, &tender, &bidders).iter() {
+            extend_bid_ttl(&env, &tender, &bid_key);
+        }
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("tender"), symbol_short!("amended")),
+            (tender_id, amendment)
+        );
+
+        Ok(())
     }
 
-    /// Check if tender is closed
-    pub fn is_tender_closed(env: Env, tender_id: u64) -> bool {
-        let tender: Tender = env.storage()
-            .instance()
+    /// Approve publication of a draft tender. Approvers are those of the
+    /// tender's policy; the approval that reaches the threshold opens the
+    /// tender for bids, provided its deadline is still ahead.
+    pub fn approve_tender(env: Env, tender_id: u64, approver: Address) -> Result<(), TenderError> {
+        approver.require_auth();
+
+        let mut tender: Tender = env.storage()
+            .persistent()
             .get(&DataKey::Tender(tender_id))
-            .expect("Tender not found");
-        tender.is_closed
-    }
+            .ok_or(TenderError::TenderNotFound)?;
 
-    /// Get contract admin
-    pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
-    }
-}
+        if !tender.is_draft || tender.is_closed {
+            return Err(TenderError::NotPendingApproval);
+        }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger};
+        if record_approval(&env, &tender, ApprovalStage::Publication, approver)? {
+            if env.ledger().timestamp() >= tender.deadline {
+                return Err(TenderError::DeadlinePassed);
+            }
+            tender.is_draft = false;
+            env.storage().persistent().set(&DataKey::Tender(tender_id), &tender);
 
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, OpenTenderContract);
-        let client = OpenTenderContractClient::new(&env, &contract_id);
-        
-        let admin = Address::generate(&env);
-        env.mock_all_auths();
-        
-        client.initialize(&admin);
-        
-        let count = client.get_tender_count();
-        assert_eq!(count, 0);
-        
-        let stored_admin = client.get_admin();
-        assert_eq!(stored_admin, admin);
-    }
+            env.events().publish(
+                (symbol_short!("tender"), symbol_short!("published")),
+                tender_id
+            );
+        }
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
 
-    #[test]
-    fn test_create_and_get_tender() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, OpenTenderContract);
-        let client = OpenTenderContractClient::new(&env, &contract_id);
-        
-        let admin = Address::generate(&env);
-        let creator = Address::generate(&env);
-        env.mock_all_auths();
-        
-        client.initialize(&admin);
-        
-        let tender_id = client.create_tender(
-            &creator,
-            &String::from_str(&env, "Road Construction"),
-            &String::from_str(&env, "Build 10km road"),
-            &String::from_str(&env, "QmHash123"),
-            &1000u64,
-            &2000u64,
-            &100000i128,
-        );
-        
-        assert_eq!(tender_id, 1);
-        
-        let tender = client.get_tender(&tender_id);
-        assert_eq!(tender.id, 1);
-        assert_eq!(tender.min_bid, 100000i128);
-        assert!(!tender.is_closed);
+        Ok(())
     }
 
-    #[test]
-    fn test_complete_bidding_flow() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, OpenTenderContract);
-        let client = OpenTenderContractClient::new(&env, &contract_id);
-        
-        let admin = Address::generate(&env);
-        let creator = Address::generate(&env);
-        let bidder1 = Address::generate(&env);
-        let bidder2 = Address::generate(&env);
-        
-        env.mock_all_auths();
-        
-        // Initialize
-        client.initialize(&admin);
-        
-        // Create tender
-        let tender_id = client.create_tender(
-            &creator,
-            &String::from_str(&env, "Test Project"),
-            &String::from_str(&env, "Description"),
-            &String::from_str(&env, "QmHash"),
-            &1000u64,
-            &2000u64,
-            &100000i128,
+    /// Approve the provisional award of a closed tender. The approval that
+    /// reaches the threshold makes the award final, after which the winner's
+    /// escrow can be funded.
+    pub fn approve_award(env: Env, tender_id: u64, approver: Address) -> Result<(), TenderError> {
+        approver.require_auth();
+
+        let mut tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        if !tender.award_pending {
+            return Err(TenderError::NotPendingApproval);
+        }
+
+        // A manual tie-break must pick the winner before it is approved
+        if env.storage().persistent().has(&DataKey::TiedBidders(tender_id)) {
+            return Err(TenderError::NoWinner);
+        }
+
+        if record_approval(&env, &tender, ApprovalStage::Award, approver)? {
+            tender.award_pending = false;
+            env.storage().persistent().set(&DataKey::Tender(tender_id), &tender);
+
+            env.events().publish(
+                (symbol_short!("award"), symbol_short!("final")),
+                tender_id
+            );
+        }
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Cancel a tender before it closes, e.g. one created in error. Creator
+    /// or admin only, with a reason for the record; every escrowed bid bond
+    /// is refunded and no winner is selected.
+    pub fn cancel_tender(
+        env: Env,
+        tender_id: u64,
+        caller: Address,
+        reason: String,
+    ) -> Result<(), TenderError> {
+        caller.require_auth();
+
+        let mut tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        if tender.is_closed {
+            return Err(TenderError::TenderClosed);
+        }
+
+        authorize_creator(&env, &tender, &caller)?;
+
+        if reason.is_empty() {
+            return Err(TenderError::ReasonRequired);
+        }
+
+        let bidders: Vec<Address> = env.storage()
+            .persistent()
+            .get(&DataKey::TenderBidders(tender_id))
+            .unwrap_or(Vec::new(&env));
+
+        let bid_keys = bid_keys(&env, &tender, &bidders);
+        if let BondPolicy::Required(bond) = &tender.bond {
+            settle_bonds(&env, tender_id, bond, &bid_keys, true);
+        }
+
+        tender.is_closed = true;
+        tender.is_cancelled = true;
+        env.storage().persistent().set(&DataKey::Tender(tender_id), &tender);
+
+        let cancellation = Cancellation {
+            cancelled_by: caller.clone(),
+            reason: reason.clone(),
+            cancelled_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&DataKey::Cancellation(tender_id), &cancellation);
+
+        extend_tender_ttl(&env, &tender);
+        for bid_key in bid_keys.iter() {
+            extend_bid_ttl(&env, &tender, &bid_key);
+        }
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("tender"), symbol_short!("cancelled")),
+            (tender_id, caller, reason)
         );
-        
-        // Submit bids
-        client.submit_bid(
-            &tender_id,
-            &bidder1,
-            &String::from_str(&env, "encrypted_150000"),
+
+        Ok(())
+    }
+
+    /// Register the committee that must jointly open the sealed bids.
+    /// Creator only, and only while bidding is open; replaces any earlier one.
+    pub fn set_committee(
+        env: Env,
+        tender_id: u64,
+        creator: Address,
+        committee: Committee,
+    ) -> Result<(), TenderError> {
+        creator.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        if creator != tender.creator {
+            return Err(TenderError::Unauthorized);
+        }
+
+        if env.ledger().timestamp() >= tender.deadline {
+            return Err(TenderError::DeadlinePassed);
+        }
+
+        let size = committee.members.len();
+        if committee.threshold == 0
+            || committee.threshold > size
+            || committee.share_hashes.len() != size
+        {
+            return Err(TenderError::InvalidCommittee);
+        }
+        for i in 0..size {
+            let member = committee.members.get_unchecked(i);
+            if committee.members.first_index_of(&member) != Some(i) {
+                return Err(TenderError::InvalidCommittee);
+            }
+        }
+
+        env.storage().persistent().set(&DataKey::Committee(tender_id), &committee);
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("committee"), symbol_short!("set")),
+            (tender_id, committee.threshold, size)
         );
-        
-        client.submit_bid(
-            &tender_id,
-            &bidder2,
-            &String::from_str(&env, "encrypted_120000"),
+
+        Ok(())
+    }
+
+    /// Committee member hands in their key share once the reveal phase is
+    /// over. The share must hash to the value registered for that member.
+    pub fn submit_key_share(
+        env: Env,
+        tender_id: u64,
+        member: Address,
+        share: Bytes,
+    ) -> Result<(), TenderError> {
+        member.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        let current_time = env.ledger().timestamp();
+        if current_time < tender.reveal_deadline {
+            return Err(TenderError::RevealNotEnded);
+        }
+
+        let committee: Committee = env.storage()
+            .persistent()
+            .get(&DataKey::Committee(tender_id))
+            .ok_or(TenderError::CommitteeNotFound)?;
+
+        let index = committee.members
+            .first_index_of(&member)
+            .ok_or(TenderError::Unauthorized)?;
+
+        let mut shares: Vec<KeyShare> = env.storage()
+            .persistent()
+            .get(&DataKey::KeyShares(tender_id))
+            .unwrap_or(Vec::new(&env));
+
+        if shares.iter().any(|s| s.member == member) {
+            return Err(TenderError::ShareAlreadySubmitted);
+        }
+
+        if env.crypto().sha256(&share).to_bytes() != committee.share_hashes.get_unchecked(index) {
+            return Err(TenderError::ShareMismatch);
+        }
+
+        shares.push_back(KeyShare {
+            member: member.clone(),
+            share,
+            submitted_at: current_time,
+        });
+        env.storage().persistent().set(&DataKey::KeyShares(tender_id), &shares);
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("share"), symbol_short!("submitted")),
+            (tender_id, member)
         );
-        
-        // Advance time past bidding deadline
-        env.ledger().set_timestamp(1500);
-        
-        // Reveal bids
-        client.reveal_bid(
-            &tender_id,
-            &bidder1,
-            &150000i128,
-            &String::from_str(&env, "key1"),
+        if shares.len() == committee.threshold {
+            env.events().publish(
+                (symbol_short!("committee"), symbol_short!("quorum")),
+                tender_id
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Creator funds the winner's payment in `token` and splits it into
+    /// milestones. Only possible once the tender is closed with a winner.
+    pub fn fund_escrow(
+        env: Env,
+        tender_id: u64,
+        creator: Address,
+        token: Address,
+        amount: i128,
+        inspector: Option<Address>,
+        milestones: Vec<MilestoneTerms>,
+    ) -> Result<(), TenderError> {
+        creator.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        if creator != tender.creator {
+            return Err(TenderError::Unauthorized);
+        }
+
+        if !env.storage().persistent().has(&DataKey::Winner(tender_id)) {
+            return Err(TenderError::NoWinner);
+        }
+
+        if tender.award_pending {
+            return Err(TenderError::AwardNotFinal);
+        }
+
+        if env.storage().persistent().has(&DataKey::Escrow(tender_id)) {
+            return Err(TenderError::EscrowAlreadyFunded);
+        }
+
+        let total = milestones.iter().try_fold(0u32, |total, m| total.checked_add(m.percentage));
+        if amount <= 0 || milestones.is_empty() || total != Some(100)
+            || milestones.iter().any(|m| m.percentage == 0)
+        {
+            return Err(TenderError::InvalidEscrow);
+        }
+
+        let mut scheduled = Vec::new(&env);
+        let mut allocated: i128 = 0;
+        for (i, terms) in milestones.iter().enumerate() {
+            let share = if i as u32 == milestones.len() - 1 {
+                amount - allocated
+            } else {
+                amount.checked_mul(terms.percentage as i128).ok_or(TenderError::InvalidEscrow)? / 100
+            };
+            allocated += share;
+            scheduled.push_back(Milestone {
+                description: terms.description,
+                percentage: terms.percentage,
+                due_date: terms.due_date,
+                amount: share,
+                status: MilestoneStatus::Pending,
+            });
+        }
+
+        token::Client::new(&env, &token).transfer(&creator, &env.current_contract_address(), &amount);
+
+        let escrow = Escrow {
+            token: token.clone(),
+            amount,
+            inspector,
+            milestones: scheduled,
+            funded_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&DataKey::Escrow(tender_id), &escrow);
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("escrow"), symbol_short!("funded")),
+            (tender_id, token, amount)
         );
-        
-        client.reveal_bid(
-            &tender_id,
-            &bidder2,
-            &120000i128,
-            &String::from_str(&env, "key2"),
+
+        Ok(())
+    }
+
+    /// Pay a milestone to the winner, on the creator's approval or the
+    /// inspector's sign-off
+    pub fn release_milestone(
+        env: Env,
+        tender_id: u64,
+        approver: Address,
+        index: u32,
+    ) -> Result<(), TenderError> {
+        approver.require_auth();
+
+        let (tender, mut escrow, mut milestone) = pending_milestone(&env, tender_id, index)?;
+
+        if approver != tender.creator && escrow.inspector.as_ref() != Some(&approver) {
+            return Err(TenderError::Unauthorized);
+        }
+
+        let winner: Winner = env.storage()
+            .persistent()
+            .get(&DataKey::Winner(tender_id))
+            .ok_or(TenderError::NoWinner)?;
+
+        token::Client::new(&env, &escrow.token).transfer(
+            &env.current_contract_address(),
+            &winner.bidder,
+            &milestone.amount,
         );
+
+        milestone.status = MilestoneStatus::Released;
+        escrow.milestones.set(index, milestone.clone());
+        env.storage().persistent().set(&DataKey::Escrow(tender_id), &escrow);
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("milestone"), symbol_short!("released")),
+            (tender_id, index, winner.bidder, milestone.amount)
+        );
+
+        Ok(())
+    }
+
+    /// Return an unreleased milestone to the creator once its due date has
+    /// passed without sign-off
+    pub fn refund_milestone(
+        env: Env,
+        tender_id: u64,
+        creator: Address,
+        index: u32,
+    ) -> Result<(), TenderError> {
+        creator.require_auth();
+
+        let (tender, mut escrow, mut milestone) = pending_milestone(&env, tender_id, index)?;
+
+        if creator != tender.creator {
+            return Err(TenderError::Unauthorized);
+        }
+
+        if env.ledger().timestamp() <= milestone.due_date {
+            return Err(TenderError::MilestoneNotDue);
+        }
+
+        token::Client::new(&env, &escrow.token).transfer(
+            &env.current_contract_address(),
+            &creator,
+            &milestone.amount,
+        );
+
+        milestone.status = MilestoneStatus::Refunded;
+        escrow.milestones.set(index, milestone.clone());
+        env.storage().persistent().set(&DataKey::Escrow(tender_id), &escrow);
+        extend_tender_ttl(&env, &tender);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("milestone"), symbol_short!("refunded")),
+            (tender_id, index, milestone.amount)
+        );
+
+        Ok(())
+    }
+
+    /// Weighted tenders: the evaluator scores a revealed bid on each
+    /// criterion, in criterion order, from 0 to `MAX_SCORE`. Scores can be
+    /// revised until the tender is closed.
+    pub fn submit_scores(
+        env: Env,
+        tender_id: u64,
+        evaluator: Address,
+        bidder: Address,
+        scores: Vec<u32>,
+    ) -> Result<(), TenderError> {
+        evaluator.require_auth();
+
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        let Evaluation::Weighted(weighted) = &tender.evaluation else {
+            return Err(TenderError::WrongEvaluationMethod);
+        };
+
+        if evaluator != weighted.evaluator {
+            return Err(TenderError::Unauthorized);
+        }
+        require_role(&env, &evaluator, Role::Evaluator)?;
+
+        if tender.is_closed {
+            return Err(TenderError::TenderClosed);
+        }
+
+        let bid: Bid = env.storage()
+            .persistent()
+            .get(&DataKey::Bid(tender_id, bidder.clone()))
+            .ok_or(TenderError::BidNotFound)?;
+
+        if !bid.is_valid {
+            return Err(TenderError::BidNotRevealed);
+        }
+
+        if scores.len() != weighted.criteria.len() || scores.iter().any(|s| s > MAX_SCORE) {
+            return Err(TenderError::InvalidScores);
+        }
+
+        let scores_key = DataKey::Scores(tender_id, bidder.clone());
+        env.storage().persistent().set(&scores_key, &scores);
+        extend_bid_ttl(&env, &tender, &scores_key);
+        extend_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("scores"), symbol_short!("submitted")),
+            (tender_id, bidder, scores)
+        );
+
+        Ok(())
+    }
+
+    /// Extend the TTL of a tender and all of its bids so the record stays
+    /// live for another audit retention period. Anyone may pay for this.
+    pub fn bump_tender(env: Env, tender_id: u64) -> Result<(), TenderError> {
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        let bidders: Vec<Address> = env.storage()
+            .persistent()
+            .get(&DataKey::TenderBidders(tender_id))
+            .unwrap_or(Vec::new(&env));
+
+        extend_tender_ttl(&env, &tender);
+        for bid_key in bid_keys(&env, &tender, &bidders).iter() {
+            extend_bid_ttl(&env, &tender, &bid_key);
+        }
+        for bidder_addr in bidders.iter() {
+            extend_bid_ttl(&env, &tender, &DataKey::Scores(tender_id, bidder_addr));
+        }
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Get tender details
+    pub fn get_tender(env: Env, tender_id: u64) -> Result<Tender, TenderError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)
+    }
+
+    /// Get all tenders (returns list of IDs)
+    pub fn get_all_tenders(env: Env) -> Vec<u64> {
+        let count = env.storage()
+            .instance()
+            .get(&DataKey::TenderCount)
+            .unwrap_or(0u64);
         
-        // Advance past reveal deadline
-        env.ledger().set_timestamp(2500);
-        
-        // Close tender
-        client.close_tender(&tender_id, &admin);
-        
-        // Check winner (should be bidder2 with 120000)
-        let winner = client.get_winner(&tender_id);
-        assert!(winner.is_some());
-        assert_eq!(winner.unwrap().amount, 120000i128);
+        let mut tender_ids = Vec::new(&env);
+        for i in 1..=count {
+            tender_ids.push_back(i);
+        }
+        tender_ids
+    }
+
+    /// Get up to `limit` tenders with id >= `start`, in id order.
+    /// `limit` is capped at `MAX_PAGE_SIZE`; pass the last id + 1 as the next `start`.
+    pub fn get_tenders(env: Env, start: u64, limit: u32) -> Vec<Tender> {
+        let count: u64 = env.storage()
+            .instance()
+            .get(&DataKey::TenderCount)
+            .unwrap_or(0);
+        let limit = limit.min(MAX_PAGE_SIZE);
+
+        let mut tenders = Vec::new(&env);
+        let mut id = start.max(1);
+        while id <= count && tenders.len() < limit {
+            if let Some(tender) = env.storage().persistent().get::<_, Tender>(&DataKey::Tender(id)) {
+                tenders.push_back(tender);
+            }
+            id += 1;
+        }
+        tenders
+    }
+
+    /// Get total tender count
+    pub fn get_tender_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TenderCount)
+            .unwrap_or(0)
+    }
+
+    /// Get bid details (only bidder can see their own bid before reveal)
+    pub fn get_bid(env: Env, tender_id: u64, bidder: Address) -> Option<Bid> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Bid(tender_id, bidder))
+    }
+
+    /// Get winner details. Always `None` on multi-lot tenders, which award
+    /// each lot separately: read those results with `get_lot_winners`.
+    pub fn get_winner(env: Env, tender_id: u64) -> Option<Winner> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Winner(tender_id))
+    }
+
+    /// Get all bidders for a tender (public after reveal deadline)
+    pub fn get_tender_bidders(env: Env, tender_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TenderBidders(tender_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check if tender is closed
+    pub fn is_tender_closed(env: Env, tender_id: u64) -> Result<bool, TenderError> {
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+        Ok(tender.is_closed)
+    }
+
+    /// Get the key committee of a tender
+    pub fn get_committee(env: Env, tender_id: u64) -> Option<Committee> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Committee(tender_id))
+    }
+
+    /// Key shares submitted so far, in submission order
+    pub fn get_key_shares(env: Env, tender_id: u64) -> Vec<KeyShare> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::KeyShares(tender_id))
+            .unwrap_or(Vec::new(&env))
     }
-}
\ No newline at end of file
+
+    /// Opening key of a time-lock tender, once published
+    pub fn get_opening_key(env: Env, tender_id: u64) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpeningKey(tender_id))
+    }
+
+    /// Escrow of a tender and the status of each milestone
+    pub fn get_escrow(env: Env, tender_id: u64) -> Option<Escrow> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Escrow(tender_id))
+    }
+
+    /// Technical scores the evaluator gave a bid
+    pub fn get_scores(env: Env, tender_id: u64, bidder: Address) -> Option<Vec<u32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scores(tender_id, bidder))
+    }
+
+    /// Combined scores of a closed weighted tender, in bidding order
+    pub fn get_scorecards(env: Env, tender_id: u64) -> Vec<Scorecard> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorecards(tender_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Manual tie-break: bidders tied for the award, until `award_tender`
+    pub fn get_tied_bidders(env: Env, tender_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TiedBidders(tender_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Multi-lot tenders: bid on one lot
+    pub fn get_lot_bid(env: Env, tender_id: u64, lot: u32, bidder: Address) -> Option<Bid> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LotBid(tender_id, lot, bidder))
+    }
+
+    /// Multi-lot tenders: bidders on one lot, in bidding order
+    pub fn get_lot_bidders(env: Env, tender_id: u64, lot: u32) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LotBidders(tender_id, lot))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Multi-lot tenders: award of every lot that found a winner, in lot order
+    pub fn get_lot_winners(env: Env, tender_id: u64) -> Result<Vec<Winner>, TenderError> {
+        let tender: Tender = env.storage()
+            .persistent()
+            .get(&DataKey::Tender(tender_id))
+            .ok_or(TenderError::TenderNotFound)?;
+
+        let mut winners = Vec::new(&env);
+        for lot in 0..tender.lots.len() {
+            if let Some(winner) = env.storage().persistent().get(&DataKey::LotWinner(tender_id, lot)) {
+                winners.push_back(winner);
+            }
+        }
+        Ok(winners)
+    }
+
+    /// Amendments of a tender, oldest first
+    pub fn get_amendments(env: Env, tender_id: u64) -> Vec<Amendment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Amendments(tender_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Reason and author of a tender's cancellation
+    pub fn get_cancellation(env: Env, tender_id: u64) -> Option<Cancellation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Cancellation(tender_id))
+    }
+
+    /// Latest debarment of a bidder, if any; it may lie in the past or
+    /// future, or have been lifted, see `is_debarred`
+    pub fn get_debarment(env: Env, bidder: Address) -> Option<Debarment> {
+        latest_debarment(&env, &bidder).map(|(_, debarment)| debarment)
+    }
+
+    /// Whether a bidder is debarred right now
+    pub fn is_debarred(env: Env, bidder: Address) -> bool {
+        is_debarred(&env, &bidder)
+    }
+
+    /// Every debarment on record, lifted or not, in the order imposed,
+    /// starting at position `start`; at most `MAX_PAGE_SIZE` per call
+    pub fn get_debarments(env: Env, start: u32, limit: u32) -> Vec<Debarment> {
+        let count: u32 = env.storage()
+            .instance()
+            .get(&DataKey::DebarmentCount)
+            .unwrap_or(0);
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+
+        let mut debarments = Vec::new(&env);
+        for position in start..end {
+            if let Some(debarment) = env.storage().persistent().get(&DataKey::DebarredAt(position)) {
+                debarments.push_back(debarment);
+            }
+        }
+        debarments
+    }
+
+    /// Strikes a bidder has collected, if any
+    pub fn get_strikes(env: Env, bidder: Address) -> Option<StrikeRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Strikes(bidder))
+    }
+
+    /// How each bidder fared at the reveal, in bidding order; empty until
+    /// the tender is closed
+    pub fn get_reveal_outcomes(env: Env, tender_id: u64) -> Vec<BidderOutcome> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RevealOutcomes(tender_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Registry entry of a bidder, if it ever registered
+    pub fn get_registration(env: Env, bidder: Address) -> Option<Registration> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Registration(bidder))
+    }
+
+    /// Whether a bidder holds an approved, unexpired registration
+    pub fn is_registered(env: Env, bidder: Address) -> bool {
+        is_registered(&env, &bidder)
+    }
+
+    /// Approval policy for an entity's new tenders, if any
+    pub fn get_approval_policy(env: Env, entity: Address) -> Option<ApprovalPolicy> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ApprovalPolicy(entity))
+    }
+
+    /// Approval policy a tender was created under, if any
+    pub fn get_tender_approvers(env: Env, tender_id: u64) -> Option<ApprovalPolicy> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TenderApprovers(tender_id))
+    }
+
+    /// Approvals of one stage of a tender, in the order given
+    pub fn get_approvals(env: Env, tender_id: u64, stage: ApprovalStage) -> Vec<Approval> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Approvals(tender_id, stage))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Whether an address currently holds a role
+    pub fn has_role(env: Env, account: Address, role: Role) -> bool {
+        has_role(&env, &account, role)
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Result<Address, TenderError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(TenderError::NotInitialized)
+    }
+}
+
+fn is_debarred(env: &Env, bidder: &Address) -> bool {
+    let now = env.ledger().timestamp();
+    matches!(latest_debarment(env, bidder), Some((_, d))
+        if d.lifted_at.is_none() && d.start <= now && now < d.end)
+}
+
+/// A bidder's latest debarment and its position in the record
+fn latest_debarment(env: &Env, bidder: &Address) -> Option<(u32, Debarment)> {
+    let position: u32 = env.storage()
+        .persistent()
+        .get(&DataKey::Debarment(bidder.clone()))?;
+    let debarment = env.storage()
+        .persistent()
+        .get(&DataKey::DebarredAt(position))?;
+    Some((position, debarment))
+}
+
+/// Mark a bidder's latest debarment lifted if it has yet to run out;
+/// returns whether there was one
+fn end_debarment(env: &Env, bidder: &Address) -> bool {
+    let Some((position, mut debarment)) = latest_debarment(env, bidder) else {
+        return false;
+    };
+    let now = env.ledger().timestamp();
+    if debarment.lifted_at.is_some() || now >= debarment.end {
+        return false;
+    }
+
+    debarment.lifted_at = Some(now);
+    let key = DataKey::DebarredAt(position);
+    env.storage().persistent().set(&key, &debarment);
+    extend_registry_ttl(env, &key);
+    true
+}
+
+fn exceeds_strikes(env: &Env, tender: &Tender, bidder: &Address) -> bool {
+    if tender.max_strikes == 0 {
+        return false;
+    }
+    let strikes: Option<StrikeRecord> = env.storage()
+        .persistent()
+        .get(&DataKey::Strikes(bidder.clone()));
+    matches!(strikes, Some(s) if s.count > tender.max_strikes)
+}
+
+fn is_registered(env: &Env, bidder: &Address) -> bool {
+    let registration: Option<Registration> = env.storage()
+        .persistent()
+        .get(&DataKey::Registration(bidder.clone()));
+    matches!(registration, Some(r)
+        if r.status == RegistrationStatus::Approved && env.ledger().timestamp() < r.expires_at)
+}
+
+fn has_role(env: &Env, account: &Address, role: Role) -> bool {
+    env.storage().persistent().has(&DataKey::Role(account.clone(), role))
+}
+
+/// Fail with `Unauthorized` unless the address holds the role. Exercising a
+/// role keeps its grant live.
+fn require_role(env: &Env, account: &Address, role: Role) -> Result<(), TenderError> {
+    if !has_role(env, account, role) {
+        return Err(TenderError::Unauthorized);
+    }
+    extend_registry_ttl(env, &DataKey::Role(account.clone(), role));
+    Ok(())
+}
+
+fn set_role(env: &Env, account: &Address, role: Role) {
+    let key = DataKey::Role(account.clone(), role);
+    env.storage().persistent().set(&key, &true);
+    extend_registry_ttl(env, &key);
+}
+
+/// Withdraw the Bidder role that came with an approved registration
+fn revoke_bidder_role(env: &Env, bidder: &Address, caller: &Address) {
+    if has_role(env, bidder, Role::Bidder) {
+        env.storage().persistent().remove(&DataKey::Role(bidder.clone(), Role::Bidder));
+        env.events().publish(
+            (symbol_short!("role"), symbol_short!("revoked")),
+            (bidder.clone(), Role::Bidder, caller.clone())
+        );
+    }
+}
+
+/// Record an approver's sign-off on a stage of a tender and report whether
+/// the policy's threshold is now met
+fn record_approval(
+    env: &Env,
+    tender: &Tender,
+    stage: ApprovalStage,
+    approver: Address,
+) -> Result<bool, TenderError> {
+    let policy: ApprovalPolicy = env.storage()
+        .persistent()
+        .get(&DataKey::TenderApprovers(tender.id))
+        .ok_or(TenderError::NotPendingApproval)?;
+
+    if !policy.approvers.contains(&approver) {
+        return Err(TenderError::Unauthorized);
+    }
+
+    let key = DataKey::Approvals(tender.id, stage);
+    let mut approvals: Vec<Approval> = env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    if approvals.iter().any(|a| a.approver == approver) {
+        return Err(TenderError::AlreadyApproved);
+    }
+
+    approvals.push_back(Approval {
+        approver: approver.clone(),
+        approved_at: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &approvals);
+
+    let topic = match stage {
+        ApprovalStage::Publication => symbol_short!("tender"),
+        ApprovalStage::Award => symbol_short!("award"),
+    };
+    env.events().publish((topic, symbol_short!("approved")), (tender.id, approver));
+
+    Ok(approvals.len() >= policy.threshold)
+}
+
+/// Closing, awarding and cancelling are open to any SuperAdmin, and to the
+/// creator while it still holds the ProcuringEntity role
+fn authorize_creator(env: &Env, tender: &Tender, caller: &Address) -> Result<(), TenderError> {
+    if has_role(env, caller, Role::SuperAdmin) {
+        return Ok(());
+    }
+    if *caller != tender.creator {
+        return Err(TenderError::Unauthorized);
+    }
+    require_role(env, caller, Role::ProcuringEntity)
+}
+
+/// Load a time-lock tender during the opening window (between the bidding
+/// and reveal deadlines)
+fn opening_tender(env: &Env, tender_id: u64) -> Result<Tender, TenderError> {
+    let tender: Tender = env.storage()
+        .persistent()
+        .get(&DataKey::Tender(tender_id))
+        .ok_or(TenderError::TenderNotFound)?;
+
+    if tender.opening_mode != OpeningMode::TimeLock {
+        return Err(TenderError::WrongOpeningMode);
+    }
+
+    if tender.is_closed {
+        return Err(TenderError::TenderClosed);
+    }
+
+    let current_time = env.ledger().timestamp();
+    if current_time < tender.deadline {
+        return Err(TenderError::RevealNotStarted);
+    }
+
+    if current_time >= tender.reveal_deadline {
+        return Err(TenderError::RevealDeadlinePassed);
+    }
+
+    Ok(tender)
+}
+
+/// Load a tender's escrow and one of its milestones that is still pending
+fn pending_milestone(
+    env: &Env,
+    tender_id: u64,
+    index: u32,
+) -> Result<(Tender, Escrow, Milestone), TenderError> {
+    let tender: Tender = env.storage()
+        .persistent()
+        .get(&DataKey::Tender(tender_id))
+        .ok_or(TenderError::TenderNotFound)?;
+
+    let escrow: Escrow = env.storage()
+        .persistent()
+        .get(&DataKey::Escrow(tender_id))
+        .ok_or(TenderError::EscrowNotFound)?;
+
+    let milestone = escrow.milestones.get(index).ok_or(TenderError::MilestoneNotFound)?;
+    if milestone.status != MilestoneStatus::Pending {
+        return Err(TenderError::MilestoneSettled);
+    }
+
+    Ok((tender, escrow, milestone))
+}
+
+/// Bids may be placed, replaced or withdrawn strictly before the deadline
+/// of a tender that is neither closed nor cancelled
+fn bidding_open(env: &Env, tender: &Tender) -> Result<(), TenderError> {
+    if env.ledger().timestamp() >= tender.deadline {
+        return Err(TenderError::DeadlinePassed);
+    }
+
+    if tender.is_closed {
+        return Err(TenderError::TenderClosed);
+    }
+
+    if tender.is_draft {
+        return Err(TenderError::TenderNotPublished);
+    }
+
+    Ok(())
+}
+
+/// Escrow the bid bond and store a sealed bid under `bid_key` while bidding is open
+fn commit_bid(
+    env: &Env,
+    tender: &Tender,
+    bid_key: &DataKey,
+    bidder: &Address,
+    encrypted_amount: String,
+    commitment: BytesN<32>,
+) -> Result<(), TenderError> {
+    bidding_open(env, tender)?;
+
+    let eligible = match &tender.eligibility {
+        Eligibility::Open => true,
+        Eligibility::Registered => is_registered(env, bidder) && has_role(env, bidder, Role::Bidder),
+        Eligibility::InviteOnly(invited) => invited.contains(bidder),
+    };
+    if !eligible || is_debarred(env, bidder) || exceeds_strikes(env, tender, bidder) {
+        return Err(TenderError::BidderNotEligible);
+    }
+
+    // Check if bidder already submitted
+    if env.storage().persistent().has(bid_key) {
+        return Err(TenderError::BidAlreadySubmitted);
+    }
+
+    // Escrow the bid bond; fails the whole call if the bidder can't pay
+    let bond = match &tender.bond {
+        BondPolicy::Required(bond) => {
+            token::Client::new(env, &bond.token).transfer(
+                bidder,
+                &env.current_contract_address(),
+                &bond.amount,
+            );
+            bond.amount
+        }
+        BondPolicy::NotRequired => 0,
+    };
+
+    let bid = Bid {
+        bidder: bidder.clone(),
+        tender_id: tender.id,
+        encrypted_amount,
+        commitment,
+        revealed_amount: None,
+        is_valid: false,
+        timestamp: env.ledger().timestamp(),
+        bond,
+    };
+
+    env.storage().persistent().set(bid_key, &bid);
+    Ok(())
+}
+
+/// Swap the sealed amount and commitment of an existing bid while bidding is open
+fn reseal_bid(
+    env: &Env,
+    tender: &Tender,
+    bid_key: &DataKey,
+    encrypted_amount: String,
+    commitment: BytesN<32>,
+) -> Result<(), TenderError> {
+    bidding_open(env, tender)?;
+
+    let mut bid: Bid = env.storage()
+        .persistent()
+        .get(bid_key)
+        .ok_or(TenderError::BidNotFound)?;
+
+    bid.encrypted_amount = encrypted_amount;
+    bid.commitment = commitment;
+    bid.timestamp = env.ledger().timestamp();
+
+    env.storage().persistent().set(bid_key, &bid);
+    extend_bid_ttl(env, tender, bid_key);
+    Ok(())
+}
+
+/// Drop a bidder from the bidder list stored under `list_key`
+fn remove_bidder(env: &Env, list_key: &DataKey, bidder: &Address) {
+    let storage = env.storage().persistent();
+    let mut bidders: Vec<Address> = storage.get(list_key).unwrap_or(Vec::new(env));
+    if let Some(index) = bidders.first_index_of(bidder) {
+        bidders.remove(index);
+        storage.set(list_key, &bidders);
+    }
+}
+
+/// Record the revealed amount of the bid under `bid_key` during the reveal
+/// window, once it opens the stored commitment. Amounts below `min_bid`
+/// are recorded but leave the bid invalid.
+fn open_bid(
+    env: &Env,
+    tender: &Tender,
+    bid_key: &DataKey,
+    bidder: &Address,
+    actual_amount: i128,
+    salt: &BytesN<32>,
+    min_bid: i128,
+) -> Result<(), TenderError> {
+    let current_time = env.ledger().timestamp();
+    
+    if current_time < tender.deadline {
+        return Err(TenderError::RevealNotStarted);
+    }
+
+    if current_time >= tender.reveal_deadline {
+        return Err(TenderError::RevealDeadlinePassed);
+    }
+
+    if tender.is_closed {
+        return Err(TenderError::TenderClosed);
+    }
+
+    let mut bid: Bid = env.storage()
+        .persistent()
+        .get(bid_key)
+        .ok_or(TenderError::BidNotFound)?;
+
+    if bid.revealed_amount.is_some() {
+        return Err(TenderError::AlreadyRevealed);
+    }
+
+    // The revealed amount must open the commitment stored at submission
+    let expected = bid_commitment(env, tender.id, bidder, actual_amount, salt);
+    if expected != bid.commitment {
+        return Err(TenderError::CommitmentMismatch);
+    }
+
+    // Below-minimum bids are recorded as revealed but can never win
+    bid.revealed_amount = Some(actual_amount);
+    bid.is_valid = actual_amount >= min_bid;
+
+    env.storage().persistent().set(bid_key, &bid);
+    extend_bid_ttl(env, tender, bid_key);
+    Ok(())
+}
+
+/// Storage key of a bid on the whole tender or on one of its lots
+fn bid_key(tender_id: u64, lot: Option<u32>, bidder: Address) -> DataKey {
+    match lot {
+        Some(lot) => DataKey::LotBid(tender_id, lot, bidder),
+        None => DataKey::Bid(tender_id, bidder),
+    }
+}
+
+/// Storage keys of every bid on a tender, lot by lot on multi-lot tenders
+fn bid_keys(env: &Env, tender: &Tender, bidders: &Vec<Address>) -> Vec<DataKey> {
+    let mut keys = Vec::new(env);
+    if tender.lots.is_empty() {
+        for bidder in bidders.iter() {
+            keys.push_back(DataKey::Bid(tender.id, bidder));
+        }
+    }
+    for lot in 0..tender.lots.len() {
+        let lot_bidders: Vec<Address> = env.storage()
+            .persistent()
+            .get(&DataKey::LotBidders(tender.id, lot))
+            .unwrap_or(Vec::new(env));
+        for bidder in lot_bidders.iter() {
+            keys.push_back(DataKey::LotBid(tender.id, lot, bidder));
+        }
+    }
+    keys
+}
+
+/// Single-lot tenders: pick the winner among all valid bids, breaking ties
+/// under the tender's tie-break rule
+fn award_tender_outright(env: &Env, tender: &Tender, bidders: &Vec<Address>) {
+    let valid_bids = valid_bids(env, tender.id, None, bidders);
+    let leaders = match &tender.evaluation {
+        Evaluation::LowestPrice => leaders(env, &valid_bids, |a, b| a < b),
+        Evaluation::HighestPrice | Evaluation::Vickrey => leaders(env, &valid_bids, |a, b| a > b),
+        Evaluation::Weighted(weighted) => {
+            let scorecards = score_bids(env, tender.id, weighted, &valid_bids);
+            env.storage().persistent().set(&DataKey::Scorecards(tender.id), &scorecards);
+            let mut totals = Vec::new(env);
+            for card in scorecards.iter() {
+                totals.push_back((card.bidder, card.total));
+            }
+            leaders(env, &totals, |a, b| a > b)
+        }
+    };
+
+    let winner = match (leaders.len(), tender.tie_break) {
+        (0, _) => None,
+        (1, _) => leaders.first(),
+        (_, TieBreak::EarliestCommit) => earliest_commit(env, tender.id, None, &leaders),
+        (_, TieBreak::Lottery) => Some(lottery(env, tender.id, &leaders)),
+        (_, TieBreak::Manual) => {
+            env.storage().persistent().set(&DataKey::TiedBidders(tender.id), &leaders);
+            env.events().publish(
+                (symbol_short!("tie"), symbol_short!("manual")),
+                (tender.id, leaders.clone())
+            );
+            None
+        }
+    };
+
+    if let Some(winner) = winner {
+        let tied = if leaders.len() > 1 { leaders } else { Vec::new(env) };
+        record_winner(env, tender, None, &valid_bids, winner, tied);
+    }
+}
+
+/// Multi-lot tenders: award each lot, in order, to its best valid bid.
+/// Bidders already awarded `max_lots_per_bidder` lots are passed over in
+/// later lots.
+fn award_lots(env: &Env, tender: &Tender) {
+    let mut awarded: Map<Address, u32> = Map::new(env);
+    let lowest_wins = tender.evaluation == Evaluation::LowestPrice;
+
+    for lot in 0..tender.lots.len() {
+        let bidders: Vec<Address> = env.storage()
+            .persistent()
+            .get(&DataKey::LotBidders(tender.id, lot))
+            .unwrap_or(Vec::new(env));
+
+        let mut eligible = Vec::new(env);
+        for (bidder, amount) in valid_bids(env, tender.id, Some(lot), &bidders).iter() {
+            let wins = awarded.get(bidder.clone()).unwrap_or(0);
+            if tender.max_lots_per_bidder == 0 || wins < tender.max_lots_per_bidder {
+                eligible.push_back((bidder, amount));
+            }
+        }
+
+        let leaders = leaders(env, &eligible, |a, b| if lowest_wins { a < b } else { a > b });
+        // Manual tie-breaks are rejected for multi-lot tenders at creation
+        let winner = match (leaders.len(), tender.tie_break) {
+            (0, _) => continue,
+            (1, _) => leaders.first(),
+            (_, TieBreak::Lottery) => Some(lottery(env, tender.id, &leaders)),
+            _ => earliest_commit(env, tender.id, Some(lot), &leaders),
+        };
+
+        if let Some(winner) = winner {
+            awarded.set(winner.clone(), awarded.get(winner.clone()).unwrap_or(0) + 1);
+            let tied = if leaders.len() > 1 { leaders } else { Vec::new(env) };
+            record_winner(env, tender, Some(lot), &eligible, winner, tied);
+        }
+    }
+}
+
+/// Bidders with a valid revealed bid and their amounts, in bidding order.
+/// Bidders debarred since they bid are passed over.
+fn valid_bids(env: &Env, tender_id: u64, lot: Option<u32>, bidders: &Vec<Address>) -> Vec<(Address, i128)> {
+    let mut bids = Vec::new(env);
+    for bidder_addr in bidders.iter() {
+        if is_debarred(env, &bidder_addr) {
+            continue;
+        }
+        let bid_key = bid_key(tender_id, lot, bidder_addr.clone());
+        if let Some(bid) = env.storage().persistent().get::<DataKey, Bid>(&bid_key) {
+            if let (true, Some(amount)) = (bid.is_valid, bid.revealed_amount) {
+                bids.push_back((bidder_addr, amount));
+            }
+        }
+    }
+    bids
+}
+
+/// Bidders whose value ranks first under `better`, in bidding order;
+/// more than one means a tie
+fn leaders(
+    env: &Env,
+    ranked: &Vec<(Address, i128)>,
+    better: impl Fn(i128, i128) -> bool,
+) -> Vec<Address> {
+    let mut best: Option<i128> = None;
+    let mut leaders = Vec::new(env);
+    for (bidder, value) in ranked.iter() {
+        match best {
+            Some(top) if value == top => leaders.push_back(bidder),
+            Some(top) if !better(value, top) => {}
+            _ => {
+                best = Some(value);
+                leaders = Vec::from_array(env, [bidder]);
+            }
+        }
+    }
+    leaders
+}
+
+/// Tied bidder with the earliest commit; the first in bidding order on equal timestamps
+fn earliest_commit(env: &Env, tender_id: u64, lot: Option<u32>, tied: &Vec<Address>) -> Option<Address> {
+    let mut earliest: Option<(Address, u64)> = None;
+    for bidder in tied.iter() {
+        let Some(bid) = env.storage().persistent().get::<DataKey, Bid>(&bid_key(tender_id, lot, bidder.clone())) else {
+            continue;
+        };
+        if earliest.as_ref().is_none_or(|(_, at)| bid.timestamp < *at) {
+            earliest = Some((bidder, bid.timestamp));
+        }
+    }
+    earliest.map(|(bidder, _)| bidder)
+}
+
+/// Draw a tied bidder with a fresh seed, published so the draw is auditable
+fn lottery(env: &Env, tender_id: u64, tied: &Vec<Address>) -> Address {
+    let seed: BytesN<32> = env.prng().gen();
+    env.prng().seed(seed.clone().into());
+    let winner = tied.get_unchecked(env.prng().gen_range::<u64>(0..tied.len() as u64) as u32);
+
+    env.events().publish(
+        (symbol_short!("tie"), symbol_short!("lottery")),
+        (tender_id, seed, tied.clone(), winner.clone())
+    );
+    winner
+}
+
+/// Store the winner of the tender or of one lot and publish the award,
+/// listing the tied bidders if any
+fn record_winner(
+    env: &Env,
+    tender: &Tender,
+    lot: Option<u32>,
+    valid_bids: &Vec<(Address, i128)>,
+    winner: Address,
+    tied: Vec<Address>,
+) {
+    let amount = valid_bids
+        .iter()
+        .find(|(bidder, _)| *bidder == winner)
+        .map(|(_, amount)| amount)
+        .expect("winner holds a valid bid");
+
+    let reserve = match lot {
+        Some(lot) => tender.lots.get_unchecked(lot).min_bid,
+        None => tender.min_bid,
+    };
+
+    // Second-price awards pay the runner-up's offer, or the reserve when unopposed
+    let payable = match tender.evaluation {
+        Evaluation::Vickrey => valid_bids
+            .iter()
+            .filter(|(bidder, _)| *bidder != winner)
+            .map(|(_, amount)| amount)
+            .max()
+            .unwrap_or(reserve),
+        _ => amount,
+    };
+
+    let winner_data = Winner {
+        tender_id: tender.id,
+        bidder: winner.clone(),
+        amount,
+        payable,
+        selected_at: env.ledger().timestamp(),
+        lot,
+    };
+
+    match lot {
+        Some(lot) => {
+            env.storage().persistent().set(&DataKey::LotWinner(tender.id, lot), &winner_data);
+            env.events().publish(
+                (symbol_short!("lot"), symbol_short!("awarded")),
+                (tender.id, lot, winner, amount, payable, tied)
+            );
+        }
+        None => {
+            env.storage().persistent().set(&DataKey::Winner(tender.id), &winner_data);
+            env.events().publish(
+                (symbol_short!("winner"), symbol_short!("selected")),
+                (tender.id, winner, amount, payable, tied)
+            );
+        }
+    }
+}
+
+/// Combine price and technical scores of every valid bid and publish each
+/// breakdown. Bids the evaluator never scored get 0 on every criterion.
+fn score_bids(
+    env: &Env,
+    tender_id: u64,
+    weighted: &WeightedCriteria,
+    bids: &Vec<(Address, i128)>,
+) -> Vec<Scorecard> {
+    let lowest = bids.iter().map(|(_, amount)| amount).min().unwrap_or(0);
+    let mut scorecards = Vec::new(env);
+
+    for (bidder, amount) in bids.iter() {
+        let price_score = price_score(lowest, amount);
+        let mut total = price_score * weighted.price_weight as i128;
+
+        let scores: Vec<u32> = env.storage()
+            .persistent()
+            .get(&DataKey::Scores(tender_id, bidder.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut criteria_scores = Vec::new(env);
+        for (i, criterion) in weighted.criteria.iter().enumerate() {
+            let raw = scores.get(i as u32).unwrap_or(0) as i128;
+            let score = raw * SCORE_SCALE / MAX_SCORE as i128;
+            total += score * criterion.weight as i128;
+            criteria_scores.push_back(score);
+        }
+
+        let card = Scorecard {
+            bidder,
+            amount,
+            price_score,
+            criteria_scores,
+            total: total / 100,
+        };
+        env.events().publish(
+            (symbol_short!("score"), symbol_short!("computed")),
+            (tender_id, card.clone())
+        );
+        scorecards.push_back(card);
+    }
+    scorecards
+}
+
+/// lowest / amount in fixed point. Amounts at or below zero (possible
+/// when `min_bid` allows them) count as the best price.
+fn price_score(lowest: i128, amount: i128) -> i128 {
+    if amount <= 0 || lowest >= amount {
+        return SCORE_SCALE;
+    }
+    if lowest <= 0 {
+        return 0;
+    }
+    match lowest.checked_mul(SCORE_SCALE) {
+        Some(scaled) => scaled / amount,
+        // Too large to scale first; amount >= SCORE_SCALE here
+        None => lowest / (amount / SCORE_SCALE),
+    }
+}
+
+/// Refund the bonds of valid bids (of every bid when `refund_all`, as on
+/// cancellation) and forfeit the rest to the beneficiary
+fn settle_bonds(env: &Env, tender_id: u64, bond: &BidBond, bid_keys: &Vec<DataKey>, refund_all: bool) {
+    let mut forfeited: i128 = 0;
+
+    for bid_key in bid_keys.iter() {
+        let Some(mut bid) = env.storage().persistent().get::<DataKey, Bid>(&bid_key) else {
+            continue;
+        };
+        if bid.bond == 0 {
+            continue;
+        }
+
+        if bid.is_valid || refund_all {
+            refund_bond(env, tender_id, bond, &bid);
+        } else {
+            forfeited += bid.bond;
+            env.events().publish(
+                (symbol_short!("bond"), symbol_short!("forfeited")),
+                (tender_id, bid.bidder.clone(), bid.bond)
+            );
+        }
+
+        bid.bond = 0;
+        env.storage().persistent().set(&bid_key, &bid);
+    }
+
+    if forfeited > 0 {
+        token::Client::new(env, &bond.token).transfer(
+            &env.current_contract_address(),
+            &bond.beneficiary,
+            &forfeited,
+        );
+    }
+}
+
+/// Classify every bidder by the bids they left sealed and, on bidder-reveal
+/// tenders, strike those who never revealed. Time-lock bids are opened by
+/// whoever holds the published key, so bidders there are never struck.
+fn record_reveal_outcomes(env: &Env, tender: &Tender, bidders: &Vec<Address>) {
+    let mut outcomes: Vec<BidderOutcome> = Vec::new(env);
+    for bidder in bidders.iter() {
+        let mut bid_keys = Vec::new(env);
+        if tender.lots.is_empty() {
+            bid_keys.push_back(DataKey::Bid(tender.id, bidder.clone()));
+        }
+        for lot in 0..tender.lots.len() {
+            bid_keys.push_back(DataKey::LotBid(tender.id, lot, bidder.clone()));
+        }
+
+        let mut worst: Option<RevealOutcome> = None;
+        for bid_key in bid_keys.iter() {
+            let Some(bid) = env.storage().persistent().get::<DataKey, Bid>(&bid_key) else {
+                continue;
+            };
+            let outcome = match bid.revealed_amount {
+                None => RevealOutcome::Unrevealed,
+                Some(_) if !bid.is_valid => RevealOutcome::Invalid,
+                Some(_) => RevealOutcome::Revealed,
+            };
+            worst = worst.max(Some(outcome));
+        }
+
+        if let Some(outcome) = worst {
+            outcomes.push_back(BidderOutcome { bidder, outcome });
+        }
+    }
+    env.storage().persistent().set(&DataKey::RevealOutcomes(tender.id), &outcomes);
+
+    if tender.opening_mode != OpeningMode::BidderReveal {
+        return;
+    }
+    for entry in outcomes.iter() {
+        if entry.outcome == RevealOutcome::Unrevealed {
+            issue_strike(env, tender.id, entry.bidder);
+        }
+    }
+}
+
+fn issue_strike(env: &Env, tender_id: u64, bidder: Address) {
+    let key = DataKey::Strikes(bidder.clone());
+    let mut record: StrikeRecord = env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(StrikeRecord { count: 0, last_tender: tender_id, last_struck_at: 0 });
+    record.count += 1;
+    record.last_tender = tender_id;
+    record.last_struck_at = env.ledger().timestamp();
+
+    env.storage().persistent().set(&key, &record);
+    extend_registry_ttl(env, &key);
+
+    env.events().publish(
+        (symbol_short!("strike"), symbol_short!("issued")),
+        (bidder, tender_id, record.count)
+    );
+}
+
+/// Return a bid's escrowed bond to its bidder
+fn refund_bond(env: &Env, tender_id: u64, bond: &BidBond, bid: &Bid) {
+    if bid.bond == 0 {
+        return;
+    }
+    token::Client::new(env, &bond.token).transfer(&env.current_contract_address(), &bid.bidder, &bid.bond);
+    env.events().publish(
+        (symbol_short!("bond"), symbol_short!("refunded")),
+        (tender_id, bid.bidder.clone(), bid.bond)
+    );
+}
+
+/// Number of ledgers a tender's entries must stay live: until the reveal
+/// deadline plus the audit retention window, capped at the network maximum
+fn tender_ttl(env: &Env, tender: &Tender) -> u32 {
+    let now = env.ledger().timestamp();
+    let until_reveal = tender.reveal_deadline.saturating_sub(now) / LEDGER_SECONDS;
+    let ttl = until_reveal.saturating_add(AUDIT_RETENTION_LEDGERS as u64);
+    ttl.min(env.storage().max_ttl() as u64) as u32
+}
+
+fn extend_tender_ttl(env: &Env, tender: &Tender) {
+    let extend_to = tender_ttl(env, tender);
+    let threshold = extend_to.saturating_sub(DAY_IN_LEDGERS);
+    let storage = env.storage().persistent();
+
+    storage.extend_ttl(&DataKey::Tender(tender.id), threshold, extend_to);
+    storage.extend_ttl(&DataKey::TenderBidders(tender.id), threshold, extend_to);
+    for key in [
+        DataKey::Winner(tender.id),
+        DataKey::Committee(tender.id),
+        DataKey::KeyShares(tender.id),
+        DataKey::OpeningKey(tender.id),
+        DataKey::Escrow(tender.id),
+        DataKey::Scorecards(tender.id),
+        DataKey::TiedBidders(tender.id),
+        DataKey::Cancellation(tender.id),
+        DataKey::Amendments(tender.id),
+        DataKey::RevealOutcomes(tender.id),
+        DataKey::TenderApprovers(tender.id),
+        DataKey::Approvals(tender.id, ApprovalStage::Publication),
+        DataKey::Approvals(tender.id, ApprovalStage::Award),
+    ] {
+        if storage.has(&key) {
+            storage.extend_ttl(&key, threshold, extend_to);
+        }
+    }
+    for lot in 0..tender.lots.len() {
+        for key in [DataKey::LotBidders(tender.id, lot), DataKey::LotWinner(tender.id, lot)] {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, threshold, extend_to);
+            }
+        }
+    }
+
+    // The contract instance must outlive every record it serves
+    env.storage().instance().extend_ttl(threshold, extend_to);
+}
+
+fn extend_bid_ttl(env: &Env, tender: &Tender, bid_key: &DataKey) {
+    let extend_to = tender_ttl(env, tender);
+    let threshold = extend_to.saturating_sub(DAY_IN_LEDGERS);
+    let storage = env.storage().persistent();
+
+    if storage.has(bid_key) {
+        storage.extend_ttl(bid_key, threshold, extend_to);
+    }
+}
+
+/// Role grants and approval policies are not tied to one tender; they stay
+/// live for an audit retention period from when they were last set or used
+fn extend_registry_ttl(env: &Env, key: &DataKey) {
+    env.storage().persistent().extend_ttl(
+        key,
+        AUDIT_RETENTION_LEDGERS - DAY_IN_LEDGERS,
+        AUDIT_RETENTION_LEDGERS,
+    );
+}
+
+fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Compute the sealed-bid commitment:
+/// sha256(amount as i128 BE || salt || bidder as ScVal XDR || tender_id as u64 BE)
+fn bid_commitment(
+    env: &Env,
+    tender_id: u64,
+    bidder: &Address,
+    amount: i128,
+    salt: &BytesN<32>,
+) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &amount.to_be_bytes());
+    preimage.append(&Bytes::from(salt.clone()));
+    preimage.append(&bidder.clone().to_xdr(env));
+    preimage.extend_from_array(&tender_id.to_be_bytes());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+mod test;
@@ -1,801 +1,3088 @@
 #![cfg(test)]
 
+use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
-    Address, Env, String, BytesN,
+    testutils::{storage::Persistent as _, Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    Address, BytesN, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec,
 };
 
-mod contract {
-    soroban_sdk::contractimport!(
-        file = "../target/wasm32-unknown-unknown/release/opentender_contract.wasm"
-    );
-}
-
-use contract::{OpenTenderContractClient, Tender, Bid};
-
-fn create_test_contract(env: &Env) -> (OpenTenderContractClient, Address) {
-    let contract_id = env.register_contract(None, contract::WASM);
+fn create_test_contract(env: &Env) -> (OpenTenderContractClient<'_>, Address) {
+    let contract_id = env.register_contract(None, OpenTenderContract);
     let client = OpenTenderContractClient::new(env, &contract_id);
     let admin = Address::generate(env);
-    
+
     client.initialize(&admin);
-    
+
     (client, admin)
 }
 
+fn grant_role(client: &OpenTenderContractClient, account: &Address, role: Role) {
+    client.grant_role(&client.get_admin(), account, &role);
+}
+
+/// Create a tender whose bidding phase ends in 1000s and reveal phase in 2000s
+fn create_test_tender(env: &Env, client: &OpenTenderContractClient, creator: &Address) -> u64 {
+    create_tender_with_mode(env, client, creator, OpeningMode::BidderReveal)
+}
+
+fn create_tender_with_mode(
+    env: &Env,
+    client: &OpenTenderContractClient,
+    creator: &Address,
+    opening_mode: OpeningMode,
+) -> u64 {
+    create_tender_with_rules(env, client, creator, &TenderRules { opening_mode, ..default_rules(env) })
+}
+
+fn create_tender_with_rules(
+    env: &Env,
+    client: &OpenTenderContractClient,
+    creator: &Address,
+    rules: &TenderRules,
+) -> u64 {
+    grant_role(client, creator, Role::ProcuringEntity);
+    let current_time = env.ledger().timestamp();
+    client.create_tender(
+        creator,
+        &String::from_str(env, "Test Project"),
+        &String::from_str(env, "Description"),
+        &String::from_str(env, "QmHash"),
+        &(current_time + 1000),
+        &(current_time + 2000),
+        &100000i128,
+        &authority_key(env),
+        rules,
+    )
+}
+
+/// Bidder-revealed, unbonded, open to all, lowest price wins, earliest
+/// commit breaks ties
+fn default_rules(env: &Env) -> TenderRules {
+    TenderRules {
+        opening_mode: OpeningMode::BidderReveal,
+        bond: BondPolicy::NotRequired,
+        evaluation: Evaluation::LowestPrice,
+        tie_break: TieBreak::EarliestCommit,
+        lots: Vec::new(env),
+        max_lots_per_bidder: 0,
+        eligibility: Eligibility::Open,
+        max_strikes: 0,
+    }
+}
+
+/// Stand-in X25519 public key for the tender authority
+fn authority_key(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[7u8; 32])
+}
+
 fn advance_ledger_time(env: &Env, seconds: u64) {
-    let current = env.ledger().timestamp();
-    env.ledger().set(LedgerInfo {
-        timestamp: current + seconds,
-        protocol_version: 20,
-        sequence_number: env.ledger().sequence(),
-        network_id: Default::default(),
-        base_reserve: 10,
-        min_temp_entry_ttl: 16,
-        min_persistent_entry_ttl: 16,
-        max_entry_ttl: 6312000,
-    });
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+fn seal(env: &Env, tender_id: u64, bidder: &Address, amount: i128, seed: u8) -> (BytesN<32>, BytesN<32>) {
+    let salt = BytesN::from_array(env, &[seed; 32]);
+    let commitment = bid_commitment(env, tender_id, bidder, amount, &salt);
+    (commitment, salt)
+}
+
+fn submit_sealed_bid(
+    env: &Env,
+    client: &OpenTenderContractClient,
+    tender_id: u64,
+    bidder: &Address,
+    amount: i128,
+    seed: u8,
+) -> BytesN<32> {
+    let (commitment, salt) = seal(env, tender_id, bidder, amount, seed);
+    client.submit_bid(
+        &tender_id,
+        bidder,
+        &String::from_str(env, "encrypted"),
+        &commitment,
+    );
+    salt
+}
+
+fn invoke<'a>(contract: &'a Address, fn_name: &'a str, args: Vec<Val>) -> MockAuthInvoke<'a> {
+    MockAuthInvoke {
+        contract,
+        fn_name,
+        args,
+        sub_invokes: &[],
+    }
 }
 
 #[test]
 fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
-    
-    let contract_id = env.register_contract(None, contract::WASM);
-    let client = OpenTenderContractClient::new(&env, &contract_id);
-    let admin = Address::generate(&env);
-    
-    client.initialize(&admin);
-    
-    // Verify initialization by checking tender count
-    let count = client.get_tender_count();
-    assert_eq!(count, 0);
+
+    let (client, admin) = create_test_contract(&env);
+
+    assert_eq!(client.get_tender_count(), 0);
+    assert_eq!(client.get_admin(), admin);
 }
 
 #[test]
-#[should_panic(expected = "Admin not set")]
 fn test_initialize_only_once() {
     let env = Env::default();
     env.mock_all_auths();
-    
-    let (client, admin) = create_test_contract(&env);
-    
-    // Try to initialize again - should panic
+
+    let (client, _admin) = create_test_contract(&env);
+
+    // Try to initialize again
     let another_admin = Address::generate(&env);
-    client.initialize(&another_admin);
+    assert_eq!(
+        client.try_initialize(&another_admin),
+        Err(Ok(TenderError::AlreadyInitialized))
+    );
 }
 
 #[test]
-fn test_create_tender() {
+fn test_create_and_get_tender() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 86400; // 1 day
-    let reveal_deadline = deadline + 86400; // 2 days
-    
+    grant_role(&client, &creator, Role::ProcuringEntity);
+
     let tender_id = client.create_tender(
         &creator,
-        &String::from_str(&env, "Road Construction Project"),
-        &String::from_str(&env, "QmTestHash123"),
-        &deadline,
-        &reveal_deadline,
+        &String::from_str(&env, "Road Construction"),
+        &String::from_str(&env, "Build 10km road"),
+        &String::from_str(&env, "QmHash123"),
+        &1000u64,
+        &2000u64,
         &100000i128,
+        &authority_key(&env),
+        &default_rules(&env),
     );
-    
+
     assert_eq!(tender_id, 1);
-    
-    // Verify tender was created
+
     let tender = client.get_tender(&tender_id);
     assert_eq!(tender.id, 1);
     assert_eq!(tender.creator, creator);
-    assert_eq!(tender.deadline, deadline);
-    assert_eq!(tender.reveal_deadline, reveal_deadline);
+    assert_eq!(tender.deadline, 1000);
+    assert_eq!(tender.reveal_deadline, 2000);
     assert_eq!(tender.min_bid, 100000i128);
-    assert_eq!(tender.is_closed, false);
+    assert_eq!(tender.encryption_key, authority_key(&env));
+    assert!(!tender.is_closed);
 }
 
 #[test]
-fn test_create_multiple_tenders() {
+fn test_create_tender_rejects_zero_encryption_key() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 86400;
-    let reveal_deadline = deadline + 86400;
-    
-    // Create first tender
-    let tender_id1 = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Project 1"),
-        &String::from_str(&env, "QmHash1"),
-        &deadline,
-        &reveal_deadline,
-        &50000i128,
-    );
-    
-    // Create second tender
-    let tender_id2 = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Project 2"),
-        &String::from_str(&env, "QmHash2"),
-        &deadline,
-        &reveal_deadline,
-        &75000i128,
+    grant_role(&client, &creator, Role::ProcuringEntity);
+
+    assert_eq!(
+        client.try_create_tender(
+            &creator,
+            &String::from_str(&env, "Unsealable Project"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "QmHash"),
+            &1000u64,
+            &2000u64,
+            &100000i128,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &default_rules(&env),
+        ),
+        Err(Ok(TenderError::InvalidEncryptionKey))
     );
-    
+    assert_eq!(client.get_tender_count(), 0);
+}
+
+#[test]
+fn test_create_multiple_tenders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+
+    assert_eq!(client.get_tender_count(), 0);
+
+    let tender_id1 = create_test_tender(&env, &client, &creator);
+    let tender_id2 = create_test_tender(&env, &client, &creator);
+    let tender_id3 = create_test_tender(&env, &client, &creator);
+
     assert_eq!(tender_id1, 1);
     assert_eq!(tender_id2, 2);
-    
-    let count = client.get_tender_count();
-    assert_eq!(count, 2);
+    assert_eq!(tender_id3, 3);
+    assert_eq!(client.get_tender_count(), 3);
+    assert_eq!(client.get_all_tenders().len(), 3);
 }
 
 #[test]
-#[should_panic(expected = "Invalid deadlines")]
-fn test_create_tender_invalid_deadline() {
+fn test_get_tenders_paginates() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time - 1000; // Past deadline
-    let reveal_deadline = deadline + 86400;
-    
-    // Should panic due to invalid deadline
-    client.create_tender(
-        &creator,
-        &String::from_str(&env, "Invalid Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
+    for _ in 0..5 {
+        create_test_tender(&env, &client, &creator);
+    }
+
+    let first = client.get_tenders(&0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().id, 1);
+    assert_eq!(first.get(1).unwrap().id, 2);
+
+    let last = client.get_tenders(&5, &10);
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.get(0).unwrap().id, 5);
+
+    assert_eq!(client.get_tenders(&6, &10).len(), 0);
+    assert_eq!(client.get_tenders(&1, &0).len(), 0);
 }
 
 #[test]
-#[should_panic(expected = "Invalid deadlines")]
-fn test_create_tender_reveal_before_deadline() {
+fn test_get_tenders_caps_page_size() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 86400;
-    let reveal_deadline = deadline - 1000; // Before bidding deadline
-    
-    // Should panic due to reveal deadline before bidding deadline
-    client.create_tender(
-        &creator,
-        &String::from_str(&env, "Invalid Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
+    // Setup only; the capped read itself is what must fit the budget
+    env.budget().reset_unlimited();
+    for _ in 0..(MAX_PAGE_SIZE + 2) {
+        create_test_tender(&env, &client, &creator);
+    }
+    env.budget().reset_default();
+
+    assert_eq!(client.get_tenders(&1, &u32::MAX).len(), MAX_PAGE_SIZE);
 }
 
 #[test]
-fn test_commit_bid() {
+fn test_create_tender_invalid_deadline() {
     let env = Env::default();
     env.mock_all_auths();
-    
+    env.ledger().set_timestamp(10_000);
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 86400;
-    let reveal_deadline = deadline + 86400;
-    
-    // Create tender
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    grant_role(&client, &creator, Role::ProcuringEntity);
+
+    // Past deadline
+    assert_eq!(
+        client.try_create_tender(
+            &creator,
+            &String::from_str(&env, "Invalid Project"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "QmHash"),
+            &9_000u64,
+            &20_000u64,
+            &100000i128,
+            &authority_key(&env),
+            &default_rules(&env),
+        ),
+        Err(Ok(TenderError::InvalidDeadline))
     );
-    
-    // Create bid hash (simplified - in production use proper SHA-256)
-    let bid_hash = BytesN::from_array(&env, &[1u8; 32]);
-    
-    // Commit bid
-    client.commit_bid(&tender_id, &bidder, &bid_hash);
-    
-    // Verify bid was committed
-    let bid = client.get_bid(&tender_id, &bidder);
-    assert_eq!(bid.bidder, bidder);
-    assert_eq!(bid.tender_id, tender_id);
-    assert_eq!(bid.commit_hash, bid_hash);
-    assert_eq!(bid.is_valid, false); // Not yet revealed
 }
 
 #[test]
-fn test_commit_multiple_bids() {
+fn test_create_tender_reveal_before_deadline() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 86400;
-    let reveal_deadline = deadline + 86400;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    grant_role(&client, &creator, Role::ProcuringEntity);
+
+    assert_eq!(
+        client.try_create_tender(
+            &creator,
+            &String::from_str(&env, "Invalid Project"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "QmHash"),
+            &2000u64,
+            &1000u64,
+            &100000i128,
+            &authority_key(&env),
+            &default_rules(&env),
+        ),
+        Err(Ok(TenderError::InvalidRevealDeadline))
     );
-    
-    // Create multiple bidders
+}
+
+#[test]
+fn test_submit_multiple_bids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
     let bidder1 = Address::generate(&env);
     let bidder2 = Address::generate(&env);
-    let bidder3 = Address::generate(&env);
-    
-    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
-    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
-    let hash3 = BytesN::from_array(&env, &[3u8; 32]);
-    
-    // Commit bids
-    client.commit_bid(&tender_id, &bidder1, &hash1);
-    client.commit_bid(&tender_id, &bidder2, &hash2);
-    client.commit_bid(&tender_id, &bidder3, &hash3);
-    
-    // Verify all bids
-    let bid1 = client.get_bid(&tender_id, &bidder1);
-    let bid2 = client.get_bid(&tender_id, &bidder2);
-    let bid3 = client.get_bid(&tender_id, &bidder3);
-    
-    assert_eq!(bid1.commit_hash, hash1);
-    assert_eq!(bid2.commit_hash, hash2);
-    assert_eq!(bid3.commit_hash, hash3);
+    let (commitment1, _) = seal(&env, tender_id, &bidder1, 150000, 1);
+    let (commitment2, _) = seal(&env, tender_id, &bidder2, 120000, 2);
+
+    client.submit_bid(&tender_id, &bidder1, &String::from_str(&env, "enc1"), &commitment1);
+    client.submit_bid(&tender_id, &bidder2, &String::from_str(&env, "enc2"), &commitment2);
+
+    let bid1 = client.get_bid(&tender_id, &bidder1).unwrap();
+    assert_eq!(bid1.commitment, commitment1);
+    assert!(!bid1.is_valid); // Not yet revealed
+    assert_eq!(client.get_bid(&tender_id, &bidder2).unwrap().commitment, commitment2);
+
+    let bidders = client.get_tender_bidders(&tender_id);
+    assert_eq!(bidders, Vec::from_array(&env, [bidder1, bidder2]));
 }
 
 #[test]
-#[should_panic(expected = "Bidding deadline passed")]
-fn test_commit_bid_after_deadline() {
+fn test_submit_bid_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
     let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    let (commitment, _salt) = seal(&env, tender_id, &bidder, 140000, 2);
+    assert_eq!(
+        client.try_submit_bid(&tender_id, &bidder, &String::from_str(&env, "encrypted"), &commitment),
+        Err(Ok(TenderError::BidAlreadySubmitted))
     );
-    
-    // Advance time past deadline
+}
+
+#[test]
+fn test_submit_bid_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
     advance_ledger_time(&env, 1500);
-    
-    let bid_hash = BytesN::from_array(&env, &[1u8; 32]);
-    
-    // Should panic - deadline passed
-    client.commit_bid(&tender_id, &bidder, &bid_hash);
+
+    let (commitment, _salt) = seal(&env, tender_id, &bidder, 150000, 1);
+    assert_eq!(
+        client.try_submit_bid(&tender_id, &bidder, &String::from_str(&env, "encrypted"), &commitment),
+        Err(Ok(TenderError::DeadlinePassed))
+    );
 }
 
 #[test]
 fn test_reveal_bid() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
     let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
-    
-    // Commit bid
-    let bid_hash = BytesN::from_array(&env, &[1u8; 32]);
-    client.commit_bid(&tender_id, &bidder, &bid_hash);
-    
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
     // Advance time past bidding deadline but before reveal deadline
     advance_ledger_time(&env, 1200);
-    
-    // Reveal bid
-    let bid_amount = 150000i128;
-    let secret = String::from_str(&env, "mysecret123");
-    
-    client.reveal_bid(&tender_id, &bidder, &bid_amount, &secret);
-    
-    // Verify bid was revealed
-    let bid = client.get_bid(&tender_id, &bidder);
-    assert_eq!(bid.revealed_amount, Some(bid_amount));
-    assert_eq!(bid.is_valid, true);
+
+    client.reveal_bid(&tender_id, &bidder, &150000i128, &salt);
+
+    let bid = client.get_bid(&tender_id, &bidder).unwrap();
+    assert_eq!(bid.revealed_amount, Some(150000i128));
+    assert!(bid.is_valid);
 }
 
 #[test]
-#[should_panic(expected = "Reveal phase not started")]
 fn test_reveal_bid_too_early() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
     let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    assert_eq!(
+        client.try_reveal_bid(&tender_id, &bidder, &150000i128, &salt),
+        Err(Ok(TenderError::RevealNotStarted))
     );
-    
-    let bid_hash = BytesN::from_array(&env, &[1u8; 32]);
-    client.commit_bid(&tender_id, &bidder, &bid_hash);
-    
-    // Try to reveal before deadline - should panic
-    let bid_amount = 150000i128;
-    let secret = String::from_str(&env, "mysecret123");
-    
-    client.reveal_bid(&tender_id, &bidder, &bid_amount, &secret);
 }
 
 #[test]
-#[should_panic(expected = "Reveal deadline passed")]
 fn test_reveal_bid_too_late() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
     let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
-    
-    let bid_hash = BytesN::from_array(&env, &[1u8; 32]);
-    client.commit_bid(&tender_id, &bidder, &bid_hash);
-    
-    // Advance time past reveal deadline
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
     advance_ledger_time(&env, 2500);
-    
-    // Try to reveal after deadline - should panic
-    let bid_amount = 150000i128;
-    let secret = String::from_str(&env, "mysecret123");
-    
-    client.reveal_bid(&tender_id, &bidder, &bid_amount, &secret);
+
+    assert_eq!(
+        client.try_reveal_bid(&tender_id, &bidder, &150000i128, &salt),
+        Err(Ok(TenderError::RevealDeadlinePassed))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Bid below minimum")]
 fn test_reveal_bid_below_minimum() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
     let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    // Below min_bid of 100000: recorded, but the bid can never win
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 50000, 1);
+
+    advance_ledger_time(&env, 1200);
+    client.reveal_bid(&tender_id, &bidder, &50000i128, &salt);
+
+    let bid = client.get_bid(&tender_id, &bidder).unwrap();
+    assert_eq!(bid.revealed_amount, Some(50000));
+    assert!(!bid.is_valid);
+    assert_eq!(
+        client.try_reveal_bid(&tender_id, &bidder, &50000i128, &salt),
+        Err(Ok(TenderError::AlreadyRevealed))
+    );
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+    assert_eq!(client.get_winner(&tender_id), None);
+}
+
+#[test]
+fn test_reveal_with_wrong_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 7);
+
+    advance_ledger_time(&env, 1200);
+
+    // Revealing a lower amount than committed must be rejected
+    assert_eq!(
+        client.try_reveal_bid(&tender_id, &bidder, &110000i128, &salt),
+        Err(Ok(TenderError::CommitmentMismatch))
     );
-    
-    let bid_hash = BytesN::from_array(&env, &[1u8; 32]);
-    client.commit_bid(&tender_id, &bidder, &bid_hash);
-    
+}
+
+#[test]
+fn test_reveal_with_wrong_salt() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 7);
+
     advance_ledger_time(&env, 1200);
-    
-    // Try to reveal with bid below minimum - should panic
-    let bid_amount = 50000i128; // Below min_bid of 100000
-    let secret = String::from_str(&env, "mysecret123");
-    
-    client.reveal_bid(&tender_id, &bidder, &bid_amount, &secret);
+
+    assert_eq!(
+        client.try_reveal_bid(&tender_id, &bidder, &150000i128, &BytesN::from_array(&env, &[8; 32])),
+        Err(Ok(TenderError::CommitmentMismatch))
+    );
+}
+
+#[test]
+fn test_commitment_binds_bidder_and_tender() {
+    let env = Env::default();
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[3; 32]);
+
+    let base = bid_commitment(&env, 1, &bidder1, 150000, &salt);
+    assert_ne!(base, bid_commitment(&env, 1, &bidder2, 150000, &salt));
+    assert_ne!(base, bid_commitment(&env, 2, &bidder1, 150000, &salt));
+    assert_ne!(base, bid_commitment(&env, 1, &bidder1, 150001, &salt));
+    assert_eq!(base, bid_commitment(&env, 1, &bidder1, 150000, &salt));
+}
+
+#[test]
+fn test_commitment_known_vector() {
+    // Off-chain clients (backend, wallets) must reproduce this exact layout
+    let env = Env::default();
+    let bidder = Address::from_string(&String::from_str(
+        &env,
+        "GBF5GDLTILW6WKTMKXWCG23BNPQRIQJ7OKEAXVPSUGFP4HIPE3CEA77M",
+    ));
+    let salt = BytesN::from_array(&env, &[0x11; 32]);
+    let expected = BytesN::from_array(&env, &[
+        0x1c, 0x9f, 0x76, 0x54, 0xa4, 0x71, 0xc9, 0x4c,
+        0xfd, 0x9e, 0xdf, 0x0f, 0x7f, 0xfe, 0xb4, 0xdc,
+        0x0a, 0x15, 0x9c, 0xce, 0x27, 0xcf, 0x84, 0x44,
+        0x75, 0x23, 0x7a, 0xf7, 0x86, 0xbc, 0x9d, 0x2b,
+    ]);
+    assert_eq!(bid_commitment(&env, 42, &bidder, 150000, &salt), expected);
 }
 
 #[test]
 fn test_close_tender_by_admin() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
-    
-    // Advance time past reveal deadline
+    let tender_id = create_test_tender(&env, &client, &creator);
+
     advance_ledger_time(&env, 2500);
-    
-    // Close tender as admin
+
     client.close_tender(&tender_id, &admin);
-    
-    // Verify tender is closed
-    let is_closed = client.is_tender_closed(&tender_id);
-    assert_eq!(is_closed, true);
-    
-    let tender = client.get_tender(&tender_id);
-    assert_eq!(tender.is_closed, true);
+
+    assert!(client.is_tender_closed(&tender_id));
+    assert!(client.get_winner(&tender_id).is_none());
 }
 
 #[test]
 fn test_close_tender_by_creator() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
-    
-    // Advance time past reveal deadline
+    let tender_id = create_test_tender(&env, &client, &creator);
+
     advance_ledger_time(&env, 2500);
-    
-    // Close tender as creator
+
     client.close_tender(&tender_id, &creator);
-    
-    // Verify tender is closed
-    let is_closed = client.is_tender_closed(&tender_id);
-    assert_eq!(is_closed, true);
+
+    assert!(client.is_tender_closed(&tender_id));
 }
 
 #[test]
-#[should_panic(expected = "Reveal phase not ended")]
 fn test_close_tender_too_early() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    assert_eq!(
+        client.try_close_tender(&tender_id, &admin),
+        Err(Ok(TenderError::RevealNotEnded))
     );
-    
-    // Try to close before reveal deadline - should panic
-    client.close_tender(&tender_id, &admin);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
 fn test_close_tender_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    let unauthorized = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
-    
+    let outsider = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
     advance_ledger_time(&env, 2500);
-    
-    // Try to close as unauthorized user - should panic
-    client.close_tender(&tender_id, &unauthorized);
+
+    assert_eq!(
+        client.try_close_tender(&tender_id, &outsider),
+        Err(Ok(TenderError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Tender already closed")]
 fn test_close_tender_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test Project"),
-        &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
-    
+    let tender_id = create_test_tender(&env, &client, &creator);
+
     advance_ledger_time(&env, 2500);
-    
-    // Close tender
+
     client.close_tender(&tender_id, &admin);
-    
-    // Try to close again - should panic
+    assert_eq!(
+        client.try_close_tender(&tender_id, &admin),
+        Err(Ok(TenderError::TenderClosed))
+    );
+}
+
+#[test]
+fn test_complete_bidding_flow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let bidder3 = Address::generate(&env);
+
+    let salt1 = submit_sealed_bid(&env, &client, tender_id, &bidder1, 150000, 1);
+    let salt2 = submit_sealed_bid(&env, &client, tender_id, &bidder2, 120000, 2);
+    let salt3 = submit_sealed_bid(&env, &client, tender_id, &bidder3, 180000, 3);
+
+    // Advance time past bidding deadline
+    advance_ledger_time(&env, 1200);
+
+    client.reveal_bid(&tender_id, &bidder1, &150000i128, &salt1);
+    client.reveal_bid(&tender_id, &bidder2, &120000i128, &salt2);
+    client.reveal_bid(&tender_id, &bidder3, &180000i128, &salt3);
+
+    // Advance past reveal deadline
+    advance_ledger_time(&env, 1000);
+
     client.close_tender(&tender_id, &admin);
+
+    // Lowest valid bid wins
+    let winner = client.get_winner(&tender_id).unwrap();
+    assert_eq!(winner.bidder, bidder2);
+    assert_eq!(winner.amount, 120000i128);
+    assert_eq!(winner.payable, 120000i128);
+    assert!(client.get_tender(&tender_id).is_closed);
+}
+
+#[test]
+fn test_get_nonexistent_tender() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+
+    assert_eq!(
+        client.try_get_tender(&999),
+        Err(Ok(TenderError::TenderNotFound))
+    );
+}
+
+#[test]
+fn test_get_nonexistent_bid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    assert!(client.get_bid(&tender_id, &bidder).is_none());
+}
+
+// Authorization matrix: each entry point must only accept the signature of
+// the address it acts for. `mock_auths` authorizes exactly one invocation,
+// so impersonation attempts surface as host auth errors.
+
+#[test]
+fn test_auth_create_tender() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    grant_role(&client, &creator, Role::ProcuringEntity);
+    let attacker = Address::generate(&env);
+
+    let title = String::from_str(&env, "Test Project");
+    let description = String::from_str(&env, "Description");
+    let ipfs_hash = String::from_str(&env, "QmHash");
+    let args: Vec<Val> = (
+        creator.clone(),
+        title.clone(),
+        description.clone(),
+        ipfs_hash.clone(),
+        1000u64,
+        2000u64,
+        100000i128,
+        authority_key(&env),
+        default_rules(&env),
+    )
+        .into_val(&env);
+
+    // Attacker signs a create_tender in the creator's name
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &invoke(&client.address, "create_tender", args.clone()),
+        }])
+        .try_create_tender(
+            &creator,
+            &title,
+            &description,
+            &ipfs_hash,
+            &1000,
+            &2000,
+            &100000,
+            &authority_key(&env),
+            &default_rules(&env),
+        );
+    assert!(result.is_err());
+    assert_eq!(client.get_tender_count(), 0);
+
+    // The creator's own signature is accepted
+    let tender_id = client
+        .mock_auths(&[MockAuth {
+            address: &creator,
+            invoke: &invoke(&client.address, "create_tender", args),
+        }])
+        .create_tender(
+            &creator,
+            &title,
+            &description,
+            &ipfs_hash,
+            &1000,
+            &2000,
+            &100000,
+            &authority_key(&env),
+            &default_rules(&env),
+        );
+    assert_eq!(client.get_tender(&tender_id).creator, creator);
+}
+
+#[test]
+fn test_auth_submit_bid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    let encrypted = String::from_str(&env, "encrypted");
+    let (commitment, _salt) = seal(&env, tender_id, &bidder, 150000, 1);
+    let args: Vec<Val> =
+        (tender_id, bidder.clone(), encrypted.clone(), commitment.clone()).into_val(&env);
+
+    // Attacker tries to place a bid in the bidder's name
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &invoke(&client.address, "submit_bid", args.clone()),
+        }])
+        .try_submit_bid(&tender_id, &bidder, &encrypted, &commitment);
+    assert!(result.is_err());
+    assert!(client.get_bid(&tender_id, &bidder).is_none());
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &bidder,
+            invoke: &invoke(&client.address, "submit_bid", args),
+        }])
+        .submit_bid(&tender_id, &bidder, &encrypted, &commitment);
+    assert!(client.get_bid(&tender_id, &bidder).is_some());
+}
+
+#[test]
+fn test_auth_reveal_bid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    advance_ledger_time(&env, 1200);
+
+    let args: Vec<Val> = (tender_id, bidder.clone(), 150000i128, salt.clone()).into_val(&env);
+
+    // Even with the right salt, only the bidder may reveal
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &invoke(&client.address, "reveal_bid", args.clone()),
+        }])
+        .try_reveal_bid(&tender_id, &bidder, &150000, &salt);
+    assert!(result.is_err());
+    assert!(client.get_bid(&tender_id, &bidder).unwrap().revealed_amount.is_none());
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &bidder,
+            invoke: &invoke(&client.address, "reveal_bid", args),
+        }])
+        .reveal_bid(&tender_id, &bidder, &150000, &salt);
+    assert_eq!(client.get_bid(&tender_id, &bidder).unwrap().revealed_amount, Some(150000));
 }
 
 #[test]
-#[should_panic(expected = "Tender is closed")]
-fn test_commit_bid_on_closed_tender() {
+fn test_auth_close_tender() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    advance_ledger_time(&env, 2500);
+
+    // Attacker passes the admin (and then the creator) as caller
+    for victim in [&admin, &creator] {
+        let result = client
+            .mock_auths(&[MockAuth {
+                address: &attacker,
+                invoke: &invoke(&client.address, "close_tender", (tender_id, victim.clone()).into_val(&env)),
+            }])
+            .try_close_tender(&tender_id, victim);
+        assert!(result.is_err());
+    }
+    assert!(!client.is_tender_closed(&tender_id));
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &invoke(&client.address, "close_tender", (tender_id, admin.clone()).into_val(&env)),
+        }])
+        .close_tender(&tender_id, &admin);
+    assert!(client.is_tender_closed(&tender_id));
+}
+
+#[test]
+fn test_auth_initialize() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, OpenTenderContract);
+    let client = OpenTenderContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &invoke(&contract_id, "initialize", (admin.clone(),).into_val(&env)),
+        }])
+        .try_initialize(&admin);
+    assert!(result.is_err());
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &invoke(&contract_id, "initialize", (admin.clone(),).into_val(&env)),
+        }])
+        .initialize(&admin);
+    assert_eq!(client.get_admin(), admin);
+}
+
+// Storage and TTL
+
+fn tender_entry_ttl(env: &Env, client: &OpenTenderContractClient, key: &DataKey) -> u32 {
+    env.as_contract(&client.address, || env.storage().persistent().get_ttl(key))
+}
+
+#[test]
+fn test_tender_entries_are_persistent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
     let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
+    let tender_id = create_test_tender(&env, &client, &creator);
+    submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        let instance = env.storage().instance();
+        for key in [
+            DataKey::Tender(tender_id),
+            DataKey::TenderBidders(tender_id),
+            DataKey::Bid(tender_id, bidder.clone()),
+        ] {
+            assert!(persistent.has(&key));
+            assert!(!instance.has(&key));
+        }
+        assert!(instance.has(&DataKey::TenderCount));
+    });
+}
+
+#[test]
+fn test_ttl_covers_reveal_deadline_and_audit_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    grant_role(&client, &creator, Role::ProcuringEntity);
+    let bidder = Address::generate(&env);
+
+    // Reveal deadline 30 days out
+    let day = 86_400u64;
     let tender_id = client.create_tender(
         &creator,
-        &String::from_str(&env, "Test Project"),
+        &String::from_str(&env, "Long Project"),
+        &String::from_str(&env, "Description"),
         &String::from_str(&env, "QmHash"),
-        &deadline,
-        &reveal_deadline,
+        &(20 * day),
+        &(30 * day),
         &100000i128,
+        &authority_key(&env),
+        &default_rules(&env),
+    );
+    submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    let expected = (30 * day / LEDGER_SECONDS) as u32 + AUDIT_RETENTION_LEDGERS;
+    assert_eq!(tender_entry_ttl(&env, &client, &DataKey::Tender(tender_id)), expected);
+    assert_eq!(tender_entry_ttl(&env, &client, &DataKey::TenderBidders(tender_id)), expected);
+    assert_eq!(
+        tender_entry_ttl(&env, &client, &DataKey::Bid(tender_id, bidder)),
+        expected
     );
-    
-    advance_ledger_time(&env, 2500);
-    client.close_tender(&tender_id, &admin);
-    
-    // Try to commit bid on closed tender - should panic
-    let bid_hash = BytesN::from_array(&env, &[1u8; 32]);
-    client.commit_bid(&tender_id, &bidder, &bid_hash);
 }
 
 #[test]
-fn test_complete_tender_workflow() {
+fn test_bump_tender_restores_ttl() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    // Step 1: Create tender
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Complete Test Project"),
-        &String::from_str(&env, "QmTestHash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
-    );
-    
-    assert_eq!(tender_id, 1);
-    
-    // Step 2: Multiple bidders commit bids
-    let bidder1 = Address::generate(&env);
-    let bidder2 = Address::generate(&env);
-    let bidder3 = Address::generate(&env);
-    
-    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
-    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
-    let hash3 = BytesN::from_array(&env, &[3u8; 32]);
-    
-    client.commit_bid(&tender_id, &bidder1, &hash1);
-    client.commit_bid(&tender_id, &bidder2, &hash2);
-    client.commit_bid(&tender_id, &bidder3, &hash3);
-    
-    // Step 3: Advance time to reveal phase
+    let bidder = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
     advance_ledger_time(&env, 1200);
-    
-    // Step 4: Bidders reveal their bids
-    client.reveal_bid(
+    client.reveal_bid(&tender_id, &bidder, &150000i128, &salt);
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &admin);
+
+    let winner_key = DataKey::Winner(tender_id);
+    assert_eq!(tender_entry_ttl(&env, &client, &winner_key), AUDIT_RETENTION_LEDGERS);
+
+    // Most of the audit window elapses
+    env.ledger().with_mut(|li| li.sequence_number += AUDIT_RETENTION_LEDGERS - 100);
+    assert_eq!(tender_entry_ttl(&env, &client, &winner_key), 100);
+
+    client.bump_tender(&tender_id);
+
+    for key in [
+        DataKey::Tender(tender_id),
+        DataKey::TenderBidders(tender_id),
+        DataKey::Bid(tender_id, bidder),
+        winner_key,
+    ] {
+        assert_eq!(tender_entry_ttl(&env, &client, &key), AUDIT_RETENTION_LEDGERS);
+    }
+}
+
+#[test]
+#[should_panic(expected = "has been archived")]
+fn test_tender_expires_without_bump() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let kept_id = create_test_tender(&env, &client, &creator);
+    let expired_id = create_test_tender(&env, &client, &creator);
+
+    let ttl = tender_entry_ttl(&env, &client, &DataKey::Tender(expired_id));
+    env.ledger().with_mut(|li| li.sequence_number += ttl - 10);
+    client.bump_tender(&kept_id);
+    env.ledger().with_mut(|li| li.sequence_number += 20);
+
+    assert_eq!(client.get_tender(&kept_id).id, kept_id);
+
+    // The unbumped tender has been archived
+    client.get_tender(&expired_id);
+}
+
+// Key committee: shares are dealt off-chain, only their hashes are registered
+
+fn share(env: &Env, index: u8) -> Bytes {
+    Bytes::from_array(env, &[index; 33])
+}
+
+/// Register a committee of `size` members holding `share(i + 1)`
+fn setup_committee(
+    env: &Env,
+    client: &OpenTenderContractClient,
+    tender_id: u64,
+    creator: &Address,
+    size: u32,
+    threshold: u32,
+) -> Vec<Address> {
+    let mut members = Vec::new(env);
+    let mut share_hashes = Vec::new(env);
+    for i in 0..size {
+        members.push_back(Address::generate(env));
+        share_hashes.push_back(env.crypto().sha256(&share(env, i as u8 + 1)).to_bytes());
+    }
+    client.set_committee(
         &tender_id,
-        &bidder1,
-        &150000i128,
-        &String::from_str(&env, "secret1"),
+        creator,
+        &Committee {
+            members: members.clone(),
+            share_hashes,
+            threshold,
+        },
     );
-    
-    client.reveal_bid(
-        &tender_id,
-        &bidder2,
-        &120000i128,
-        &String::from_str(&env, "secret2"),
+    members
+}
+
+#[test]
+fn test_set_committee_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let member = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let committee = |members: &[Address], hashes: u32, threshold: u32| {
+        let mut share_hashes = Vec::new(&env);
+        for _ in 0..hashes {
+            share_hashes.push_back(hash.clone());
+        }
+        Committee {
+            members: Vec::from_slice(&env, members),
+            share_hashes,
+            threshold,
+        }
+    };
+
+    for invalid in [
+        committee(core::slice::from_ref(&member), 1, 0),
+        committee(core::slice::from_ref(&member), 1, 2),
+        committee(core::slice::from_ref(&member), 2, 1),
+        committee(&[member.clone(), member.clone()], 2, 1),
+    ] {
+        assert_eq!(
+            client.try_set_committee(&tender_id, &creator, &invalid),
+            Err(Ok(TenderError::InvalidCommittee))
+        );
+    }
+
+    let valid = committee(core::slice::from_ref(&member), 1, 1);
+    assert_eq!(
+        client.try_set_committee(&tender_id, &Address::generate(&env), &valid),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    client.set_committee(&tender_id, &creator, &valid);
+    assert_eq!(client.get_committee(&tender_id), Some(valid.clone()));
+
+    advance_ledger_time(&env, 1000);
+    assert_eq!(
+        client.try_set_committee(&tender_id, &creator, &valid),
+        Err(Ok(TenderError::DeadlinePassed))
     );
-    
-    client.reveal_bid(
-        &tender_id,
-        &bidder3,
-        &180000i128,
-        &String::from_str(&env, "secret3"),
-    );
-    
-    // Step 5: Verify all bids are valid
-    let bid1 = client.get_bid(&tender_id, &bidder1);
-    let bid2 = client.get_bid(&tender_id, &bidder2);
-    let bid3 = client.get_bid(&tender_id, &bidder3);
-    
-    assert_eq!(bid1.is_valid, true);
-    assert_eq!(bid2.is_valid, true);
-    assert_eq!(bid3.is_valid, true);
-    
-    // Step 6: Advance time past reveal deadline
-    advance_ledger_time(&env, 1000);
-    
-    // Step 7: Close tender
-    client.close_tender(&tender_id, &admin);
-    
-    // Step 8: Verify tender is closed
-    let tender = client.get_tender(&tender_id);
-    assert_eq!(tender.is_closed, true);
-    
-    // In a real implementation, the winner would be bidder2 with 120000 (lowest bid)
 }
 
 #[test]
-fn test_get_tender_count_increments() {
+fn test_key_shares_after_reveal_deadline() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    assert_eq!(client.get_tender_count(), 0);
-    
-    client.create_tender(
-        &creator,
-        &String::from_str(&env, "Project 1"),
-        &String::from_str(&env, "Hash1"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let members = setup_committee(&env, &client, tender_id, &creator, 3, 2);
+
+    // Shares stay private until the reveal phase is over
+    advance_ledger_time(&env, 1500);
+    assert_eq!(
+        client.try_submit_key_share(&tender_id, &members.get(0).unwrap(), &share(&env, 1)),
+        Err(Ok(TenderError::RevealNotEnded))
     );
-    
-    assert_eq!(client.get_tender_count(), 1);
-    
-    client.create_tender(
-        &creator,
-        &String::from_str(&env, "Project 2"),
-        &String::from_str(&env, "Hash2"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+
+    advance_ledger_time(&env, 500);
+    let first = members.get(0).unwrap();
+    client.submit_key_share(&tender_id, &first, &share(&env, 1));
+    assert_eq!(
+        client.try_submit_key_share(&tender_id, &first, &share(&env, 1)),
+        Err(Ok(TenderError::ShareAlreadySubmitted))
     );
-    
-    assert_eq!(client.get_tender_count(), 2);
-    
-    client.create_tender(
-        &creator,
-        &String::from_str(&env, "Project 3"),
-        &String::from_str(&env, "Hash3"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    // Member 2 cannot hand in member 3's share
+    assert_eq!(
+        client.try_submit_key_share(&tender_id, &members.get(1).unwrap(), &share(&env, 3)),
+        Err(Ok(TenderError::ShareMismatch))
     );
-    
-    assert_eq!(client.get_tender_count(), 3);
+    assert_eq!(
+        client.try_submit_key_share(&tender_id, &Address::generate(&env), &share(&env, 1)),
+        Err(Ok(TenderError::Unauthorized))
+    );
+
+    client.submit_key_share(&tender_id, &members.get(2).unwrap(), &share(&env, 3));
+
+    let shares = client.get_key_shares(&tender_id);
+    assert_eq!(shares.len(), 2);
+    assert_eq!(shares.get(0).unwrap().member, first);
+    assert_eq!(shares.get(1).unwrap().member, members.get(2).unwrap());
+    assert_eq!(shares.get(1).unwrap().share, share(&env, 3));
 }
 
 #[test]
-#[should_panic(expected = "Tender not found")]
-fn test_get_nonexistent_tender() {
+fn test_committee_tender_closes_after_quorum() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
-    
-    // Try to get tender that doesn't exist
-    client.get_tender(&999);
+    let creator = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let members = setup_committee(&env, &client, tender_id, &creator, 3, 2);
+
+    advance_ledger_time(&env, 2000);
+    client.submit_key_share(&tender_id, &members.get(0).unwrap(), &share(&env, 1));
+    assert_eq!(
+        client.try_close_tender(&tender_id, &creator),
+        Err(Ok(TenderError::RevealNotEnded))
+    );
+
+    client.submit_key_share(&tender_id, &members.get(1).unwrap(), &share(&env, 2));
+    client.close_tender(&tender_id, &creator);
+    assert!(client.get_tender(&tender_id).is_closed);
 }
 
 #[test]
-#[should_panic(expected = "Bid not found")]
-fn test_get_nonexistent_bid() {
+fn test_key_share_requires_committee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    advance_ledger_time(&env, 2000);
+    assert_eq!(
+        client.try_submit_key_share(&tender_id, &creator, &share(&env, 1)),
+        Err(Ok(TenderError::CommitteeNotFound))
+    );
+    assert!(client.get_key_shares(&tender_id).is_empty());
+}
+
+// Time-lock opening: the creator opens every bid with the published key
+
+fn opening(bidder: &Address, amount: i128, salt: BytesN<32>) -> BidOpening {
+    BidOpening {
+        bidder: bidder.clone(),
+        amount,
+        salt,
+    }
+}
+
+#[test]
+fn test_open_bids_without_bidder_reveals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_tender_with_mode(&env, &client, &creator, OpeningMode::TimeLock);
+
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let bidder3 = Address::generate(&env);
+    let salt1 = submit_sealed_bid(&env, &client, tender_id, &bidder1, 150000, 1);
+    let salt2 = submit_sealed_bid(&env, &client, tender_id, &bidder2, 120000, 2);
+    let salt3 = submit_sealed_bid(&env, &client, tender_id, &bidder3, 90000, 3);
+
+    advance_ledger_time(&env, 1000);
+    let secret = BytesN::from_array(&env, &[9u8; 32]);
+    client.publish_opening_key(&tender_id, &creator, &secret);
+    assert_eq!(client.get_opening_key(&tender_id), Some(secret));
+
+    // Bidder 1 reveals on their own; the batch leaves that bid alone
+    client.reveal_bid(&tender_id, &bidder1, &150000, &salt1);
+    let mut openings = Vec::new(&env);
+    openings.push_back(opening(&bidder1, 1, salt1));
+    openings.push_back(opening(&bidder2, 120000, salt2));
+    openings.push_back(opening(&bidder3, 90000, salt3));
+    client.open_bids(&tender_id, &creator, &openings);
+
+    assert_eq!(client.get_bid(&tender_id, &bidder1).unwrap().revealed_amount, Some(150000));
+    assert!(client.get_bid(&tender_id, &bidder2).unwrap().is_valid);
+    let below_minimum = client.get_bid(&tender_id, &bidder3).unwrap();
+    assert_eq!(below_minimum.revealed_amount, Some(90000));
+    assert!(!below_minimum.is_valid);
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+    assert_eq!(client.get_winner(&tender_id).unwrap().bidder, bidder2);
+}
+
+#[test]
+fn test_open_bids_checks_commitments() {
     let env = Env::default();
     env.mock_all_auths();
-    
+
     let (client, _admin) = create_test_contract(&env);
     let creator = Address::generate(&env);
+    let tender_id = create_tender_with_mode(&env, &client, &creator, OpeningMode::TimeLock);
     let bidder = Address::generate(&env);
-    
-    let current_time = env.ledger().timestamp();
-    let deadline = current_time + 1000;
-    let reveal_deadline = deadline + 1000;
-    
-    let tender_id = client.create_tender(
-        &creator,
-        &String::from_str(&env, "Test"),
-        &String::from_str(&env, "Hash"),
-        &deadline,
-        &reveal_deadline,
-        &100000i128,
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    let mut openings = Vec::new(&env);
+    openings.push_back(opening(&bidder, 100001, salt.clone()));
+
+    // Nothing can be opened before the deadline or without the key
+    assert_eq!(
+        client.try_open_bids(&tender_id, &creator, &openings),
+        Err(Ok(TenderError::RevealNotStarted))
+    );
+    advance_ledger_time(&env, 1000);
+    assert_eq!(
+        client.try_open_bids(&tender_id, &creator, &openings),
+        Err(Ok(TenderError::OpeningKeyNotPublished))
+    );
+
+    // Only the creator publishes the key
+    let key = BytesN::from_array(&env, &[9u8; 32]);
+    assert_eq!(
+        client.try_publish_opening_key(&tender_id, &Address::generate(&env), &key),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    client.publish_opening_key(&tender_id, &creator, &key);
+    // No one can substitute a different amount
+    assert_eq!(
+        client.try_open_bids(&tender_id, &creator, &openings),
+        Err(Ok(TenderError::CommitmentMismatch))
     );
-    
-    // Try to get bid that doesn't exist
-    client.get_bid(&tender_id, &bidder);
-}
\ No newline at end of file
+    assert_eq!(client.get_bid(&tender_id, &bidder).unwrap().revealed_amount, None);
+
+    let mut openings = Vec::new(&env);
+    openings.push_back(opening(&bidder, 150000, salt));
+    advance_ledger_time(&env, 1000);
+    assert_eq!(
+        client.try_open_bids(&tender_id, &creator, &openings),
+        Err(Ok(TenderError::RevealDeadlinePassed))
+    );
+}
+
+#[test]
+fn test_open_bids_requires_time_lock_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+
+    advance_ledger_time(&env, 1000);
+    assert_eq!(
+        client.try_publish_opening_key(&tender_id, &creator, &BytesN::from_array(&env, &[9u8; 32])),
+        Err(Ok(TenderError::WrongOpeningMode))
+    );
+    assert_eq!(
+        client.try_open_bids(&tender_id, &creator, &Vec::new(&env)),
+        Err(Ok(TenderError::WrongOpeningMode))
+    );
+}
+
+// Bid bonds, escrowed in a Stellar Asset Contract token
+
+const BOND: i128 = 500;
+
+fn create_bonded_tender(
+    env: &Env,
+    client: &OpenTenderContractClient,
+    creator: &Address,
+    bond: BondPolicy,
+) -> Result<u64, TenderError> {
+    grant_role(client, creator, Role::ProcuringEntity);
+    let current_time = env.ledger().timestamp();
+    client
+        .try_create_tender(
+            creator,
+            &String::from_str(env, "Bonded Project"),
+            &String::from_str(env, "Description"),
+            &String::from_str(env, "QmHash"),
+            &(current_time + 1000),
+            &(current_time + 2000),
+            &100000i128,
+            &authority_key(env),
+            &TenderRules { bond, ..default_rules(env) },
+        )
+        .map(|id| id.unwrap())
+        .map_err(|e| e.unwrap())
+}
+
+/// Register a test token and fund each holder with `balance`
+fn test_token(env: &Env, holders: &[&Address], balance: i128) -> (Address, token::Client<'static>) {
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let admin = token::StellarAssetClient::new(env, &asset.address());
+    for holder in holders {
+        admin.mint(holder, &balance);
+    }
+    (asset.address(), token::Client::new(env, &asset.address()))
+}
+
+#[test]
+fn test_bid_bonds_refunded_or_forfeited_at_close() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let no_show = Address::generate(&env);
+    let (token_id, token) = test_token(&env, &[&bidder1, &bidder2, &no_show], 1000);
+
+    let bond = BidBond {
+        token: token_id,
+        amount: BOND,
+        beneficiary: treasury.clone(),
+    };
+    let tender_id = create_bonded_tender(&env, &client, &creator, BondPolicy::Required(bond)).unwrap();
+
+    let salt1 = submit_sealed_bid(&env, &client, tender_id, &bidder1, 150000, 1);
+    let salt2 = submit_sealed_bid(&env, &client, tender_id, &bidder2, 120000, 2);
+    submit_sealed_bid(&env, &client, tender_id, &no_show, 110000, 3);
+
+    assert_eq!(token.balance(&client.address), 3 * BOND);
+    assert_eq!(token.balance(&bidder1), 1000 - BOND);
+    assert_eq!(client.get_bid(&tender_id, &bidder1).unwrap().bond, BOND);
+
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &bidder1, &150000, &salt1);
+    client.reveal_bid(&tender_id, &bidder2, &120000, &salt2);
+
+    // Nothing moves until the tender is closed
+    assert_eq!(token.balance(&client.address), 3 * BOND);
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    assert_eq!(token.balance(&bidder1), 1000);
+    assert_eq!(token.balance(&bidder2), 1000);
+    assert_eq!(token.balance(&no_show), 1000 - BOND);
+    assert_eq!(token.balance(&treasury), BOND);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.get_bid(&tender_id, &no_show).unwrap().bond, 0);
+}
+
+#[test]
+fn test_bid_bond_must_be_paid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let (token_id, token) = test_token(&env, &[&bidder], BOND - 1);
+
+    let bond = BidBond {
+        token: token_id,
+        amount: BOND,
+        beneficiary: creator.clone(),
+    };
+    let tender_id = create_bonded_tender(&env, &client, &creator, BondPolicy::Required(bond)).unwrap();
+
+    let (commitment, _salt) = seal(&env, tender_id, &bidder, 150000, 1);
+    let result = client.try_submit_bid(
+        &tender_id,
+        &bidder,
+        &String::from_str(&env, "encrypted"),
+        &commitment,
+    );
+    assert!(result.is_err());
+    assert!(client.get_bid(&tender_id, &bidder).is_none());
+    assert_eq!(token.balance(&bidder), BOND - 1);
+}
+
+#[test]
+fn test_create_tender_rejects_invalid_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let (token_id, _token) = test_token(&env, &[], 0);
+
+    let bond = BidBond {
+        token: token_id,
+        amount: 0,
+        beneficiary: creator.clone(),
+    };
+    assert_eq!(
+        create_bonded_tender(&env, &client, &creator, BondPolicy::Required(bond)),
+        Err(TenderError::InvalidBond)
+    );
+}
+
+const PAYMENT: i128 = 1000;
+
+/// Close a tender with a single valid bid; returns the tender and its winner
+fn awarded_tender(env: &Env, client: &OpenTenderContractClient, creator: &Address) -> (u64, Address) {
+    let tender_id = create_test_tender(env, client, creator);
+    let winner = Address::generate(env);
+    let salt = submit_sealed_bid(env, client, tender_id, &winner, 150000, 1);
+    advance_ledger_time(env, 1000);
+    client.reveal_bid(&tender_id, &winner, &150000, &salt);
+    advance_ledger_time(env, 1000);
+    client.close_tender(&tender_id, creator);
+    (tender_id, winner)
+}
+
+fn milestones(env: &Env, percentages: &[u32]) -> Vec<MilestoneTerms> {
+    let due_date = env.ledger().timestamp() + 1000;
+    let mut terms = Vec::new(env);
+    for (i, percentage) in percentages.iter().enumerate() {
+        terms.push_back(MilestoneTerms {
+            description: String::from_str(env, "Milestone"),
+            percentage: *percentage,
+            due_date: due_date + i as u64 * 1000,
+        });
+    }
+    terms
+}
+
+#[test]
+fn test_escrow_milestones_released_to_winner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let inspector = Address::generate(&env);
+    let (tender_id, winner) = awarded_tender(&env, &client, &creator);
+    let (token_id, token) = test_token(&env, &[&creator], PAYMENT);
+
+    client.fund_escrow(
+        &tender_id,
+        &creator,
+        &token_id,
+        &PAYMENT,
+        &Some(inspector.clone()),
+        &milestones(&env, &[33, 33, 34]),
+    );
+    assert_eq!(token.balance(&client.address), PAYMENT);
+
+    // Rounding remainder goes to the last milestone
+    let escrow = client.get_escrow(&tender_id).unwrap();
+    for (milestone, amount) in escrow.milestones.iter().zip([330, 330, 340]) {
+        assert_eq!(milestone.amount, amount);
+    }
+
+    client.release_milestone(&tender_id, &creator, &0);
+    client.release_milestone(&tender_id, &inspector, &2);
+    assert_eq!(token.balance(&winner), 670);
+
+    let escrow = client.get_escrow(&tender_id).unwrap();
+    assert_eq!(escrow.milestones.get(0).unwrap().status, MilestoneStatus::Released);
+    assert_eq!(escrow.milestones.get(1).unwrap().status, MilestoneStatus::Pending);
+
+    assert_eq!(
+        client.try_release_milestone(&tender_id, &creator, &0),
+        Err(Ok(TenderError::MilestoneSettled))
+    );
+    assert_eq!(
+        client.try_release_milestone(&tender_id, &winner, &1),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_release_milestone(&tender_id, &creator, &3),
+        Err(Ok(TenderError::MilestoneNotFound))
+    );
+}
+
+#[test]
+fn test_refund_milestone_after_due_date() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let (tender_id, winner) = awarded_tender(&env, &client, &creator);
+    let (token_id, token) = test_token(&env, &[&creator], PAYMENT);
+
+    client.fund_escrow(&tender_id, &creator, &token_id, &PAYMENT, &None, &milestones(&env, &[50, 50]));
+
+    assert_eq!(
+        client.try_refund_milestone(&tender_id, &creator, &0),
+        Err(Ok(TenderError::MilestoneNotDue))
+    );
+
+    advance_ledger_time(&env, 1001);
+    client.refund_milestone(&tender_id, &creator, &0);
+    assert_eq!(token.balance(&creator), 500);
+    assert_eq!(
+        client.get_escrow(&tender_id).unwrap().milestones.get(0).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+    assert_eq!(
+        client.try_release_milestone(&tender_id, &creator, &0),
+        Err(Ok(TenderError::MilestoneSettled))
+    );
+
+    // The second milestone is not due yet and can still be paid out
+    client.release_milestone(&tender_id, &creator, &1);
+    assert_eq!(token.balance(&winner), 500);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_fund_escrow_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let (token_id, _token) = test_token(&env, &[&creator], 2 * PAYMENT);
+
+    // No winner before the tender is closed
+    let open_tender = create_test_tender(&env, &client, &creator);
+    assert_eq!(
+        client.try_fund_escrow(&open_tender, &creator, &token_id, &PAYMENT, &None, &milestones(&env, &[100])),
+        Err(Ok(TenderError::NoWinner))
+    );
+
+    let (tender_id, winner) = awarded_tender(&env, &client, &creator);
+    assert_eq!(
+        client.try_fund_escrow(&tender_id, &winner, &token_id, &PAYMENT, &None, &milestones(&env, &[100])),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    let invalid = [
+        (PAYMENT, &[60, 30][..]),
+        (PAYMENT, &[100, 0][..]),
+        (PAYMENT, &[][..]),
+        (0, &[100][..]),
+        // Percentages that only sum to 100 by wrapping around
+        (PAYMENT, &[u32::MAX, 101][..]),
+        // Too large to split without overflowing
+        (i128::MAX, &[50, 50][..]),
+    ];
+    for (amount, percentages) in invalid {
+        assert_eq!(
+            client.try_fund_escrow(&tender_id, &creator, &token_id, &amount, &None, &milestones(&env, percentages)),
+            Err(Ok(TenderError::InvalidEscrow))
+        );
+    }
+    assert_eq!(
+        client.try_release_milestone(&tender_id, &creator, &0),
+        Err(Ok(TenderError::EscrowNotFound))
+    );
+
+    client.fund_escrow(&tender_id, &creator, &token_id, &PAYMENT, &None, &milestones(&env, &[100]));
+    assert_eq!(
+        client.try_fund_escrow(&tender_id, &creator, &token_id, &PAYMENT, &None, &milestones(&env, &[100])),
+        Err(Ok(TenderError::EscrowAlreadyFunded))
+    );
+}
+
+/// Price weighs 40%, one evaluator-scored "Quality" criterion 60%
+fn weighted_rules(env: &Env, evaluator: &Address) -> TenderRules {
+    let mut criteria = Vec::new(env);
+    criteria.push_back(Criterion {
+        name: String::from_str(env, "Quality"),
+        weight: 60,
+    });
+    TenderRules {
+        evaluation: Evaluation::Weighted(WeightedCriteria {
+            price_weight: 40,
+            criteria,
+            evaluator: evaluator.clone(),
+        }),
+        ..default_rules(env)
+    }
+}
+
+fn scores(env: &Env, values: &[u32]) -> Vec<u32> {
+    Vec::from_slice(env, values)
+}
+
+#[test]
+fn test_weighted_evaluation_picks_highest_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let evaluator = Address::generate(&env);
+    grant_role(&client, &evaluator, Role::Evaluator);
+    let tender_id = create_tender_with_rules(&env, &client, &creator, &weighted_rules(&env, &evaluator));
+
+    let expensive = Address::generate(&env);
+    let cheap = Address::generate(&env);
+    let salt1 = submit_sealed_bid(&env, &client, tender_id, &expensive, 150000, 1);
+    let salt2 = submit_sealed_bid(&env, &client, tender_id, &cheap, 120000, 2);
+
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &expensive, &150000, &salt1);
+    client.reveal_bid(&tender_id, &cheap, &120000, &salt2);
+    client.submit_scores(&tender_id, &evaluator, &expensive, &scores(&env, &[100]));
+    client.submit_scores(&tender_id, &evaluator, &cheap, &scores(&env, &[40]));
+    // Scores can be revised before closing
+    client.submit_scores(&tender_id, &evaluator, &cheap, &scores(&env, &[50]));
+    assert_eq!(client.get_scores(&tender_id, &cheap), Some(scores(&env, &[50])));
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    // 0.4 * 120/150 + 0.6 * 1.0 = 0.92 beats 0.4 * 1.0 + 0.6 * 0.5 = 0.70
+    let winner = client.get_winner(&tender_id).unwrap();
+    assert_eq!(winner.bidder, expensive);
+    assert_eq!(winner.amount, 150000);
+
+    let cards = client.get_scorecards(&tender_id);
+    assert_eq!(cards.len(), 2);
+    let first = cards.get(0).unwrap();
+    assert_eq!(first.bidder, expensive);
+    assert_eq!(first.price_score, 800_000);
+    assert_eq!(first.criteria_scores, Vec::from_slice(&env, &[1_000_000i128]));
+    assert_eq!(first.total, 920_000);
+    assert_eq!(cards.get(1).unwrap().total, 700_000);
+}
+
+#[test]
+fn test_submit_scores_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let evaluator = Address::generate(&env);
+    grant_role(&client, &evaluator, Role::Evaluator);
+    let bidder = Address::generate(&env);
+    let tender_id = create_tender_with_rules(&env, &client, &creator, &weighted_rules(&env, &evaluator));
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    assert_eq!(
+        client.try_submit_scores(&tender_id, &evaluator, &bidder, &scores(&env, &[80])),
+        Err(Ok(TenderError::BidNotRevealed))
+    );
+
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &bidder, &150000, &salt);
+
+    assert_eq!(
+        client.try_submit_scores(&tender_id, &creator, &bidder, &scores(&env, &[80])),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    for bad in [&[][..], &[80, 80][..], &[101][..]] {
+        assert_eq!(
+            client.try_submit_scores(&tender_id, &evaluator, &bidder, &scores(&env, bad)),
+            Err(Ok(TenderError::InvalidScores))
+        );
+    }
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+    assert_eq!(
+        client.try_submit_scores(&tender_id, &evaluator, &bidder, &scores(&env, &[80])),
+        Err(Ok(TenderError::TenderClosed))
+    );
+    // Unscored bids count as 0 on every criterion
+    assert_eq!(client.get_scorecards(&tender_id).get(0).unwrap().total, 400_000);
+
+    let plain = create_test_tender(&env, &client, &creator);
+    assert_eq!(
+        client.try_submit_scores(&plain, &evaluator, &bidder, &scores(&env, &[80])),
+        Err(Ok(TenderError::WrongEvaluationMethod))
+    );
+}
+
+#[test]
+fn test_create_tender_rejects_invalid_criteria() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    grant_role(&client, &creator, Role::ProcuringEntity);
+    let evaluator = Address::generate(&env);
+    grant_role(&client, &evaluator, Role::Evaluator);
+
+    let mut rules = weighted_rules(&env, &evaluator);
+    let Evaluation::Weighted(weighted) = &mut rules.evaluation else {
+        unreachable!()
+    };
+    weighted.price_weight = 50;
+
+    let current_time = env.ledger().timestamp();
+    let result = client.try_create_tender(
+        &creator,
+        &String::from_str(&env, "Weighted Project"),
+        &String::from_str(&env, "Description"),
+        &String::from_str(&env, "QmHash"),
+        &(current_time + 1000),
+        &(current_time + 2000),
+        &100000i128,
+        &authority_key(&env),
+        &rules,
+    );
+    assert_eq!(result, Err(Ok(TenderError::InvalidCriteria)));
+
+    // Weights that only sum to 100 by wrapping around
+    let Evaluation::Weighted(weighted) = &mut rules.evaluation else {
+        unreachable!()
+    };
+    weighted.criteria.set(0, Criterion { name: String::from_str(&env, "Quality"), weight: u32::MAX });
+    weighted.criteria.push_back(Criterion { name: String::from_str(&env, "Safety"), weight: 51 });
+    let result = client.try_create_tender(
+        &creator,
+        &String::from_str(&env, "Weighted Project"),
+        &String::from_str(&env, "Description"),
+        &String::from_str(&env, "QmHash"),
+        &(current_time + 1000),
+        &(current_time + 2000),
+        &100000i128,
+        &authority_key(&env),
+        &rules,
+    );
+    assert_eq!(result, Err(Ok(TenderError::InvalidCriteria)));
+}
+
+fn award_rules(env: &Env, evaluation: Evaluation) -> TenderRules {
+    TenderRules {
+        evaluation,
+        ..default_rules(env)
+    }
+}
+
+#[test]
+fn test_highest_price_award_with_reserve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_tender_with_rules(&env, &client, &creator, &award_rules(&env, Evaluation::HighestPrice));
+
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let lowball = Address::generate(&env);
+    let salt1 = submit_sealed_bid(&env, &client, tender_id, &bidder1, 150000, 1);
+    let salt2 = submit_sealed_bid(&env, &client, tender_id, &bidder2, 120000, 2);
+    let salt3 = submit_sealed_bid(&env, &client, tender_id, &lowball, 90000, 3);
+
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &bidder1, &150000, &salt1);
+    client.reveal_bid(&tender_id, &bidder2, &120000, &salt2);
+    // min_bid is the reserve price
+    client.reveal_bid(&tender_id, &lowball, &90000, &salt3);
+    assert!(!client.get_bid(&tender_id, &lowball).unwrap().is_valid);
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    let winner = client.get_winner(&tender_id).unwrap();
+    assert_eq!(winner.bidder, bidder1);
+    assert_eq!(winner.amount, 150000);
+    assert_eq!(winner.payable, 150000);
+}
+
+#[test]
+fn test_vickrey_winner_pays_second_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let contested = create_tender_with_rules(&env, &client, &creator, &award_rules(&env, Evaluation::Vickrey));
+    let unopposed = create_tender_with_rules(&env, &client, &creator, &award_rules(&env, Evaluation::Vickrey));
+
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let bidder3 = Address::generate(&env);
+    let salt1 = submit_sealed_bid(&env, &client, contested, &bidder1, 130000, 1);
+    let salt2 = submit_sealed_bid(&env, &client, contested, &bidder2, 150000, 2);
+    let salt3 = submit_sealed_bid(&env, &client, contested, &bidder3, 120000, 3);
+    let salt4 = submit_sealed_bid(&env, &client, unopposed, &bidder1, 140000, 4);
+
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&contested, &bidder1, &130000, &salt1);
+    client.reveal_bid(&contested, &bidder2, &150000, &salt2);
+    client.reveal_bid(&contested, &bidder3, &120000, &salt3);
+    client.reveal_bid(&unopposed, &bidder1, &140000, &salt4);
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&contested, &creator);
+    client.close_tender(&unopposed, &creator);
+
+    let winner = client.get_winner(&contested).unwrap();
+    assert_eq!(winner.bidder, bidder2);
+    assert_eq!(winner.amount, 150000);
+    assert_eq!(winner.payable, 130000);
+
+    // Without a runner-up the winner pays the reserve price
+    let winner = client.get_winner(&unopposed).unwrap();
+    assert_eq!(winner.amount, 140000);
+    assert_eq!(winner.payable, 100000);
+}
+
+/// Close a tender where two bidders tie at 120000 and a third bids more
+fn tied_tender(env: &Env, client: &OpenTenderContractClient, creator: &Address, tie_break: TieBreak) -> (u64, Address, Address) {
+    let tender_id = create_tender_with_rules(env, client, creator, &TenderRules { tie_break, ..default_rules(env) });
+    let first = Address::generate(env);
+    let second = Address::generate(env);
+    let other = Address::generate(env);
+
+    let salt1 = submit_sealed_bid(env, client, tender_id, &first, 120000, 1);
+    advance_ledger_time(env, 10);
+    let salt2 = submit_sealed_bid(env, client, tender_id, &second, 120000, 2);
+    let salt3 = submit_sealed_bid(env, client, tender_id, &other, 130000, 3);
+
+    advance_ledger_time(env, 1000);
+    client.reveal_bid(&tender_id, &first, &120000, &salt1);
+    client.reveal_bid(&tender_id, &second, &120000, &salt2);
+    client.reveal_bid(&tender_id, &other, &130000, &salt3);
+    advance_ledger_time(env, 1000);
+    client.close_tender(&tender_id, creator);
+    (tender_id, first, second)
+}
+
+/// Data of the last event published under `topics`
+fn last_event<T: TryFromVal<Env, Val>>(env: &Env, topics: (Symbol, Symbol)) -> Option<T> {
+    let topics: Vec<Val> = topics.into_val(env);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, t, _)| *t == topics)
+        .last()
+        .map(|(_, _, data)| T::try_from_val(env, &data).unwrap())
+}
+
+type WinnerEvent = (u64, Address, i128, i128, Vec<Address>);
+
+#[test]
+fn test_tie_break_earliest_commit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let (tender_id, first, second) = tied_tender(&env, &client, &creator, TieBreak::EarliestCommit);
+
+    assert_eq!(client.get_winner(&tender_id).unwrap().bidder, first);
+    let (_, winner, amount, _, tied): WinnerEvent =
+        last_event(&env, (symbol_short!("winner"), symbol_short!("selected"))).unwrap();
+    assert_eq!((winner, amount), (first.clone(), 120000));
+    assert_eq!(tied, Vec::from_array(&env, [first, second]));
+}
+
+#[test]
+fn test_tie_break_lottery_publishes_seed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let (tender_id, first, second) = tied_tender(&env, &client, &creator, TieBreak::Lottery);
+
+    let winner = client.get_winner(&tender_id).unwrap().bidder;
+    assert!(winner == first || winner == second);
+
+    let (id, _seed, tied, drawn): (u64, BytesN<32>, Vec<Address>, Address) =
+        last_event(&env, (symbol_short!("tie"), symbol_short!("lottery"))).unwrap();
+    assert_eq!(id, tender_id);
+    assert_eq!(tied, Vec::from_array(&env, [first, second]));
+    assert_eq!(drawn, winner);
+}
+
+#[test]
+fn test_tie_break_manual_award() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let (tender_id, first, second) = tied_tender(&env, &client, &creator, TieBreak::Manual);
+
+    // Closed without a winner until someone picks one
+    assert!(client.get_tender(&tender_id).is_closed);
+    assert!(client.get_winner(&tender_id).is_none());
+    assert_eq!(client.get_tied_bidders(&tender_id), Vec::from_array(&env, [first.clone(), second.clone()]));
+
+    assert_eq!(
+        client.try_award_tender(&tender_id, &outsider, &second),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_award_tender(&tender_id, &creator, &outsider),
+        Err(Ok(TenderError::NotTied))
+    );
+
+    client.award_tender(&tender_id, &creator, &second);
+    let winner = client.get_winner(&tender_id).unwrap();
+    assert_eq!((winner.bidder, winner.amount), (second.clone(), 120000));
+    let (_, _, _, _, tied): WinnerEvent =
+        last_event(&env, (symbol_short!("winner"), symbol_short!("selected"))).unwrap();
+    assert_eq!(tied.len(), 2);
+
+    assert!(client.get_tied_bidders(&tender_id).is_empty());
+    assert_eq!(
+        client.try_award_tender(&tender_id, &creator, &first),
+        Err(Ok(TenderError::NoPendingAward))
+    );
+}
+
+fn lot_rules(env: &Env, min_bids: &[i128], max_lots_per_bidder: u32) -> TenderRules {
+    let mut lots = Vec::new(env);
+    for (i, min_bid) in min_bids.iter().enumerate() {
+        lots.push_back(Lot {
+            title: String::from_str(env, ["Materials", "Labour", "Equipment"][i]),
+            min_bid: *min_bid,
+        });
+    }
+    TenderRules {
+        lots,
+        max_lots_per_bidder,
+        ..default_rules(env)
+    }
+}
+
+fn submit_sealed_lot_bid(
+    env: &Env,
+    client: &OpenTenderContractClient,
+    tender_id: u64,
+    lot: u32,
+    bidder: &Address,
+    amount: i128,
+    seed: u8,
+) -> BytesN<32> {
+    let (commitment, salt) = seal(env, tender_id, bidder, amount, seed);
+    client.submit_lot_bid(
+        &tender_id,
+        &lot,
+        bidder,
+        &String::from_str(env, "encrypted"),
+        &commitment,
+    );
+    salt
+}
+
+#[test]
+fn test_multi_lot_tender_awards_each_lot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    let tender_id = create_tender_with_rules(&env, &client, &creator, &lot_rules(&env, &[1000, 500], 0));
+
+    let salt1 = submit_sealed_lot_bid(&env, &client, tender_id, 0, &first, 1500, 1);
+    let salt2 = submit_sealed_lot_bid(&env, &client, tender_id, 0, &second, 1200, 2);
+    let salt3 = submit_sealed_lot_bid(&env, &client, tender_id, 1, &first, 800, 3);
+    assert_eq!(client.get_tender_bidders(&tender_id), Vec::from_array(&env, [first.clone(), second.clone()]));
+    assert_eq!(client.get_lot_bidders(&tender_id, &0), Vec::from_array(&env, [first.clone(), second.clone()]));
+
+    advance_ledger_time(&env, 1001);
+    client.reveal_lot_bid(&tender_id, &0, &first, &1500, &salt1);
+    client.reveal_lot_bid(&tender_id, &0, &second, &1200, &salt2);
+    client.reveal_lot_bid(&tender_id, &1, &first, &800, &salt3);
+    assert_eq!(client.get_lot_bid(&tender_id, &1, &first).unwrap().revealed_amount, Some(800));
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    let winners = client.get_lot_winners(&tender_id);
+    assert_eq!(winners.len(), 2);
+    let lot0 = winners.get(0).unwrap();
+    assert_eq!((lot0.lot, lot0.bidder, lot0.amount), (Some(0), second, 1200));
+    let lot1 = winners.get(1).unwrap();
+    assert_eq!((lot1.lot, lot1.bidder, lot1.amount), (Some(1), first, 800));
+    assert!(client.get_winner(&tender_id).is_none());
+}
+
+#[test]
+fn test_max_lots_per_bidder() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let cheapest = Address::generate(&env);
+    let runner_up = Address::generate(&env);
+
+    let tender_id = create_tender_with_rules(&env, &client, &creator, &lot_rules(&env, &[100, 100, 100], 1));
+
+    let salt1 = submit_sealed_lot_bid(&env, &client, tender_id, 0, &cheapest, 200, 1);
+    let salt2 = submit_sealed_lot_bid(&env, &client, tender_id, 1, &cheapest, 200, 2);
+    let salt3 = submit_sealed_lot_bid(&env, &client, tender_id, 1, &runner_up, 300, 3);
+    let salt4 = submit_sealed_lot_bid(&env, &client, tender_id, 2, &cheapest, 200, 4);
+
+    advance_ledger_time(&env, 1001);
+    client.reveal_lot_bid(&tender_id, &0, &cheapest, &200, &salt1);
+    client.reveal_lot_bid(&tender_id, &1, &cheapest, &200, &salt2);
+    client.reveal_lot_bid(&tender_id, &1, &runner_up, &300, &salt3);
+    client.reveal_lot_bid(&tender_id, &2, &cheapest, &200, &salt4);
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    // Lot 1 passes to the runner-up; lot 2 has no other bidder and stays unawarded
+    let winners = client.get_lot_winners(&tender_id);
+    assert_eq!(winners.len(), 2);
+    assert_eq!(winners.get(0).unwrap().bidder, cheapest);
+    let lot1 = winners.get(1).unwrap();
+    assert_eq!((lot1.lot, lot1.bidder, lot1.amount), (Some(1), runner_up, 300));
+}
+
+#[test]
+fn test_lot_bid_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    grant_role(&client, &creator, Role::ProcuringEntity);
+    let bidder = Address::generate(&env);
+
+    let single = create_test_tender(&env, &client, &creator);
+    let multi = create_tender_with_rules(&env, &client, &creator, &lot_rules(&env, &[1000, 500], 0));
+    let encrypted = String::from_str(&env, "encrypted");
+    let (commitment, salt) = seal(&env, multi, &bidder, 400, 1);
+
+    assert_eq!(
+        client.try_submit_bid(&multi, &bidder, &encrypted, &commitment),
+        Err(Ok(TenderError::LotRequired))
+    );
+    assert_eq!(
+        client.try_submit_lot_bid(&multi, &2, &bidder, &encrypted, &commitment),
+        Err(Ok(TenderError::LotNotFound))
+    );
+    assert_eq!(
+        client.try_submit_lot_bid(&single, &0, &bidder, &encrypted, &commitment),
+        Err(Ok(TenderError::LotNotFound))
+    );
+
+    client.submit_lot_bid(&multi, &1, &bidder, &encrypted, &commitment);
+    assert_eq!(
+        client.try_submit_lot_bid(&multi, &1, &bidder, &encrypted, &commitment),
+        Err(Ok(TenderError::BidAlreadySubmitted))
+    );
+
+    // Each lot has its own minimum
+    advance_ledger_time(&env, 1001);
+    client.reveal_lot_bid(&multi, &1, &bidder, &400, &salt);
+    assert!(!client.get_lot_bid(&multi, &1, &bidder).unwrap().is_valid);
+
+    let invalid = [
+        TenderRules { tie_break: TieBreak::Manual, ..lot_rules(&env, &[100, 100], 0) },
+        TenderRules { opening_mode: OpeningMode::TimeLock, ..lot_rules(&env, &[100, 100], 0) },
+        lot_rules(&env, &[], 1),
+    ];
+    for rules in invalid.iter() {
+        let now = env.ledger().timestamp();
+        let result = client.try_create_tender(
+            &creator,
+            &String::from_str(&env, "Lots"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "QmHash"),
+            &(now + 1000),
+            &(now + 2000),
+            &100i128,
+            &authority_key(&env),
+            rules,
+        );
+        assert_eq!(result, Err(Ok(TenderError::InvalidLots)));
+    }
+}
+
+#[test]
+fn test_cancel_tender_refunds_bonds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let (token_id, token) = test_token(&env, &[&bidder1, &bidder2], 1000);
+
+    let bond = BidBond {
+        token: token_id,
+        amount: BOND,
+        beneficiary: treasury.clone(),
+    };
+    let tender_id = create_bonded_tender(&env, &client, &creator, BondPolicy::Required(bond)).unwrap();
+    let salt1 = submit_sealed_bid(&env, &client, tender_id, &bidder1, 150000, 1);
+    submit_sealed_bid(&env, &client, tender_id, &bidder2, 120000, 2);
+
+    // Cancelling is allowed after the deadline too, as long as the tender is open
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &bidder1, &150000, &salt1);
+
+    let reason = String::from_str(&env, "Published with the wrong specification");
+    client.cancel_tender(&tender_id, &creator, &reason);
+
+    let event: (u64, Address, String) = last_event(&env, (symbol_short!("tender"), symbol_short!("cancelled"))).unwrap();
+    assert_eq!(event, (tender_id, creator.clone(), reason.clone()));
+
+    // Unrevealed bids get their bond back as well
+    assert_eq!(token.balance(&bidder1), 1000);
+    assert_eq!(token.balance(&bidder2), 1000);
+    assert_eq!(token.balance(&treasury), 0);
+    assert_eq!(token.balance(&client.address), 0);
+
+    let tender = client.get_tender(&tender_id);
+    assert!(tender.is_closed && tender.is_cancelled);
+    let cancellation = client.get_cancellation(&tender_id).unwrap();
+    assert_eq!((cancellation.cancelled_by, cancellation.reason), (creator.clone(), reason));
+
+    advance_ledger_time(&env, 1000);
+    assert_eq!(client.try_close_tender(&tender_id, &creator), Err(Ok(TenderError::TenderClosed)));
+    assert!(client.get_winner(&tender_id).is_none());
+}
+
+#[test]
+fn test_cancel_tender_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let reason = String::from_str(&env, "Duplicate");
+
+    let tender_id = create_test_tender(&env, &client, &creator);
+    assert_eq!(
+        client.try_cancel_tender(&tender_id, &stranger, &reason),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_cancel_tender(&tender_id, &creator, &String::from_str(&env, "")),
+        Err(Ok(TenderError::ReasonRequired))
+    );
+
+    client.cancel_tender(&tender_id, &admin, &reason);
+    assert_eq!(client.get_cancellation(&tender_id).unwrap().cancelled_by, admin);
+    assert_eq!(
+        client.try_cancel_tender(&tender_id, &creator, &reason),
+        Err(Ok(TenderError::TenderClosed))
+    );
+
+    let bidder = Address::generate(&env);
+    let (commitment, _salt) = seal(&env, tender_id, &bidder, 150000, 1);
+    assert_eq!(
+        client.try_submit_bid(&tender_id, &bidder, &String::from_str(&env, "encrypted"), &commitment),
+        Err(Ok(TenderError::TenderClosed))
+    );
+
+    // A closed tender can no longer be cancelled
+    let closed = create_test_tender(&env, &client, &creator);
+    advance_ledger_time(&env, 2000);
+    client.close_tender(&closed, &creator);
+    assert_eq!(
+        client.try_cancel_tender(&closed, &creator, &reason),
+        Err(Ok(TenderError::TenderClosed))
+    );
+}
+
+#[test]
+fn test_withdraw_bid_before_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    let (token_id, token) = test_token(&env, &[&bidder1, &bidder2], 1000);
+
+    let bond = BidBond {
+        token: token_id,
+        amount: BOND,
+        beneficiary: creator.clone(),
+    };
+    let tender_id = create_bonded_tender(&env, &client, &creator, BondPolicy::Required(bond)).unwrap();
+    submit_sealed_bid(&env, &client, tender_id, &bidder1, 150000, 1);
+    submit_sealed_bid(&env, &client, tender_id, &bidder2, 120000, 2);
+
+    client.withdraw_bid(&tender_id, &bidder1);
+    assert!(client.get_bid(&tender_id, &bidder1).is_none());
+    assert_eq!(client.get_tender_bidders(&tender_id), Vec::from_array(&env, [bidder2.clone()]));
+    assert_eq!(token.balance(&bidder1), 1000);
+    assert_eq!(
+        client.try_withdraw_bid(&tender_id, &bidder1),
+        Err(Ok(TenderError::BidNotFound))
+    );
+
+    // A withdrawn bidder may bid again, at the end of the bidding order
+    submit_sealed_bid(&env, &client, tender_id, &bidder1, 140000, 3);
+    assert_eq!(
+        client.get_tender_bidders(&tender_id),
+        Vec::from_array(&env, [bidder2.clone(), bidder1.clone()])
+    );
+
+    advance_ledger_time(&env, 1000);
+    assert_eq!(
+        client.try_withdraw_bid(&tender_id, &bidder2),
+        Err(Ok(TenderError::DeadlinePassed))
+    );
+}
+
+#[test]
+fn test_replace_bid_before_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let encrypted = String::from_str(&env, "resealed");
+
+    let old_salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+    let (commitment, new_salt) = seal(&env, tender_id, &bidder, 130000, 2);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_replace_bid(&tender_id, &stranger, &encrypted, &commitment),
+        Err(Ok(TenderError::BidNotFound))
+    );
+
+    advance_ledger_time(&env, 10);
+    client.replace_bid(&tender_id, &bidder, &encrypted, &commitment);
+    let bid = client.get_bid(&tender_id, &bidder).unwrap();
+    assert_eq!(bid.encrypted_amount, encrypted);
+    assert_eq!(bid.commitment, commitment);
+    assert_eq!(bid.timestamp, env.ledger().timestamp());
+    assert_eq!(client.get_tender_bidders(&tender_id).len(), 1);
+
+    advance_ledger_time(&env, 990);
+    assert_eq!(
+        client.try_replace_bid(&tender_id, &bidder, &encrypted, &commitment),
+        Err(Ok(TenderError::DeadlinePassed))
+    );
+
+    // Only the replacement opens
+    assert_eq!(
+        client.try_reveal_bid(&tender_id, &bidder, &150000, &old_salt),
+        Err(Ok(TenderError::CommitmentMismatch))
+    );
+    client.reveal_bid(&tender_id, &bidder, &130000, &new_salt);
+}
+
+#[test]
+fn test_withdraw_and_replace_lot_bids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let other = Address::generate(&env);
+    let tender_id = create_tender_with_rules(&env, &client, &creator, &lot_rules(&env, &[100, 100], 0));
+    let encrypted = String::from_str(&env, "resealed");
+
+    submit_sealed_lot_bid(&env, &client, tender_id, 0, &bidder, 200, 1);
+    submit_sealed_lot_bid(&env, &client, tender_id, 1, &bidder, 300, 2);
+    submit_sealed_lot_bid(&env, &client, tender_id, 1, &other, 250, 3);
+
+    let (commitment, _salt) = seal(&env, tender_id, &bidder, 280, 4);
+    assert_eq!(
+        client.try_replace_bid(&tender_id, &bidder, &encrypted, &commitment),
+        Err(Ok(TenderError::LotRequired))
+    );
+    client.replace_lot_bid(&tender_id, &1, &bidder, &encrypted, &commitment);
+    assert_eq!(client.get_lot_bid(&tender_id, &1, &bidder).unwrap().commitment, commitment);
+
+    // Withdrawing covers every lot
+    client.withdraw_bid(&tender_id, &bidder);
+    assert!(client.get_lot_bid(&tender_id, &0, &bidder).is_none());
+    assert!(client.get_lot_bid(&tender_id, &1, &bidder).is_none());
+    assert!(client.get_lot_bidders(&tender_id, &0).is_empty());
+    assert_eq!(client.get_lot_bidders(&tender_id, &1), Vec::from_array(&env, [other.clone()]));
+    assert_eq!(client.get_tender_bidders(&tender_id), Vec::from_array(&env, [other]));
+}
+
+#[test]
+fn test_amend_tender_extends_timeline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let original = client.get_tender(&tender_id);
+
+    advance_ledger_time(&env, 500);
+    let description = String::from_str(&env, "Description, revision 2");
+    let ipfs_hash = String::from_str(&env, "QmRevised");
+    client.amend_tender(
+        &tender_id,
+        &creator,
+        &description,
+        &ipfs_hash,
+        &(original.deadline + 500),
+        &(original.reveal_deadline + 500),
+    );
+
+    let tender = client.get_tender(&tender_id);
+    assert_eq!(tender.description, description);
+    assert_eq!(tender.ipfs_hash, ipfs_hash);
+    assert_eq!(tender.deadline, original.deadline + 500);
+    assert_eq!(tender.reveal_deadline, original.reveal_deadline + 500);
+
+    let amendments = client.get_amendments(&tender_id);
+    assert_eq!(amendments.len(), 1);
+    let amendment = amendments.get(0).unwrap();
+    assert_eq!(amendment.amended_at, env.ledger().timestamp());
+    assert_eq!(amendment.deadline, tender.deadline);
+
+    let event: (u64, Amendment) = last_event(&env, (symbol_short!("tender"), symbol_short!("amended"))).unwrap();
+    assert_eq!(event, (tender_id, amendment));
+
+    // Bidding stays open past the original deadline
+    advance_ledger_time(&env, 700);
+    submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    // A second amendment may keep the timeline as is
+    let revised = String::from_str(&env, "QmRevised3");
+    client.amend_tender(&tender_id, &creator, &description, &revised, &tender.deadline, &tender.reveal_deadline);
+    assert_eq!(client.get_amendments(&tender_id).len(), 2);
+    assert_eq!(client.get_tender(&tender_id).ipfs_hash, revised);
+}
+
+#[test]
+fn test_amend_tender_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let tender = client.get_tender(&tender_id);
+    let (description, ipfs_hash) = (tender.description.clone(), tender.ipfs_hash.clone());
+
+    let amend = |caller: &Address, deadline: u64, reveal_deadline: u64| {
+        client.try_amend_tender(&tender_id, caller, &description, &ipfs_hash, &deadline, &reveal_deadline)
+    };
+
+    assert_eq!(
+        amend(&stranger, tender.deadline, tender.reveal_deadline),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    assert_eq!(
+        amend(&creator, tender.deadline - 1, tender.reveal_deadline),
+        Err(Ok(TenderError::DeadlineShortened))
+    );
+    assert_eq!(
+        amend(&creator, tender.deadline, tender.reveal_deadline - 1),
+        Err(Ok(TenderError::DeadlineShortened))
+    );
+    assert_eq!(
+        amend(&creator, tender.reveal_deadline, tender.reveal_deadline),
+        Err(Ok(TenderError::InvalidRevealDeadline))
+    );
+
+    advance_ledger_time(&env, 1000);
+    assert_eq!(
+        amend(&creator, tender.deadline + 100, tender.reveal_deadline + 100),
+        Err(Ok(TenderError::DeadlinePassed))
+    );
+    assert!(client.get_amendments(&tender_id).is_empty());
+}
+
+// Roles
+
+#[test]
+fn test_grant_and_revoke_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let officer = Address::generate(&env);
+    assert!(client.has_role(&admin, &Role::SuperAdmin));
+    assert!(!client.has_role(&officer, &Role::ProcuringEntity));
+
+    client.grant_role(&admin, &officer, &Role::ProcuringEntity);
+    assert!(client.has_role(&officer, &Role::ProcuringEntity));
+    assert!(!client.has_role(&officer, &Role::Evaluator));
+    assert_eq!(
+        last_event::<(Address, Role, Address)>(&env, (symbol_short!("role"), symbol_short!("granted"))),
+        Some((officer.clone(), Role::ProcuringEntity, admin.clone()))
+    );
+
+    // Only a SuperAdmin manages roles
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_grant_role(&officer, &other, &Role::Auditor),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_revoke_role(&officer, &officer, &Role::ProcuringEntity),
+        Err(Ok(TenderError::Unauthorized))
+    );
+
+    client.revoke_role(&admin, &officer, &Role::ProcuringEntity);
+    assert!(!client.has_role(&officer, &Role::ProcuringEntity));
+    assert_eq!(
+        last_event::<(Address, Role, Address)>(&env, (symbol_short!("role"), symbol_short!("revoked"))),
+        Some((officer.clone(), Role::ProcuringEntity, admin.clone()))
+    );
+    assert_eq!(
+        client.try_revoke_role(&admin, &officer, &Role::ProcuringEntity),
+        Err(Ok(TenderError::RoleNotHeld))
+    );
+
+    // A second SuperAdmin can be appointed and removed, but none can
+    // remove themselves
+    client.grant_role(&admin, &other, &Role::SuperAdmin);
+    assert_eq!(
+        client.try_revoke_role(&admin, &admin, &Role::SuperAdmin),
+        Err(Ok(TenderError::SelfRevoke))
+    );
+    client.revoke_role(&other, &admin, &Role::SuperAdmin);
+    assert!(!client.has_role(&admin, &Role::SuperAdmin));
+}
+
+#[test]
+fn test_roles_enforced() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let current_time = env.ledger().timestamp();
+
+    // Creating a tender takes the ProcuringEntity role
+    assert_eq!(
+        client.try_create_tender(
+            &creator,
+            &String::from_str(&env, "Unauthorized Project"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "QmHash"),
+            &(current_time + 1000),
+            &(current_time + 2000),
+            &100000i128,
+            &authority_key(&env),
+            &default_rules(&env),
+        ),
+        Err(Ok(TenderError::Unauthorized))
+    );
+
+    // A creator whose role was revoked can no longer close its tenders;
+    // a SuperAdmin still can
+    let tender_id = create_test_tender(&env, &client, &creator);
+    client.revoke_role(&admin, &creator, &Role::ProcuringEntity);
+    advance_ledger_time(&env, 2000);
+    assert_eq!(
+        client.try_close_tender(&tender_id, &creator),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    client.close_tender(&tender_id, &admin);
+    assert!(client.is_tender_closed(&tender_id));
+
+    // Scoring takes the Evaluator role as well as being the named evaluator
+    let evaluator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let weighted = create_tender_with_rules(&env, &client, &creator, &weighted_rules(&env, &evaluator));
+    let salt = submit_sealed_bid(&env, &client, weighted, &bidder, 150000, 1);
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&weighted, &bidder, &150000, &salt);
+    assert_eq!(
+        client.try_submit_scores(&weighted, &evaluator, &bidder, &scores(&env, &[80])),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    grant_role(&client, &evaluator, Role::Evaluator);
+    client.submit_scores(&weighted, &evaluator, &bidder, &scores(&env, &[80]));
+}
+
+// Multi-signature approvals
+
+fn approval_policy(env: &Env, client: &OpenTenderContractClient, entity: &Address, threshold: u32) -> Vec<Address> {
+    let mut approvers = Vec::new(env);
+    for _ in 0..3 {
+        approvers.push_back(Address::generate(env));
+    }
+    let policy = ApprovalPolicy { approvers: approvers.clone(), threshold };
+    client.set_approval_policy(&client.get_admin(), entity, &policy);
+    approvers
+}
+
+#[test]
+fn test_set_approval_policy_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let entity = Address::generate(&env);
+    let approver = Address::generate(&env);
+    let policy = |approvers: &[&Address], threshold: u32| {
+        let mut list = Vec::new(&env);
+        for a in approvers {
+            list.push_back((*a).clone());
+        }
+        ApprovalPolicy { approvers: list, threshold }
+    };
+
+    assert_eq!(
+        client.try_set_approval_policy(&entity, &entity, &policy(&[&approver], 1)),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    for bad in [policy(&[&approver], 0), policy(&[&approver], 2), policy(&[&approver, &approver], 2)] {
+        assert_eq!(
+            client.try_set_approval_policy(&admin, &entity, &bad),
+            Err(Ok(TenderError::InvalidApprovalPolicy))
+        );
+    }
+
+    client.set_approval_policy(&admin, &entity, &policy(&[&approver], 1));
+    assert_eq!(client.get_approval_policy(&entity), Some(policy(&[&approver], 1)));
+
+    // An empty approver list lifts the policy
+    client.set_approval_policy(&admin, &entity, &policy(&[], 0));
+    assert_eq!(client.get_approval_policy(&entity), None);
+}
+
+#[test]
+fn test_publication_requires_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let approvers = approval_policy(&env, &client, &creator, 2);
+
+    let tender_id = create_test_tender(&env, &client, &creator);
+    assert!(client.get_tender(&tender_id).is_draft);
+    let (commitment, _salt) = seal(&env, tender_id, &bidder, 150000, 1);
+    assert_eq!(
+        client.try_submit_bid(&tender_id, &bidder, &String::from_str(&env, "encrypted"), &commitment),
+        Err(Ok(TenderError::TenderNotPublished))
+    );
+
+    assert_eq!(
+        client.try_approve_tender(&tender_id, &bidder),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    client.approve_tender(&tender_id, &approvers.get(0).unwrap());
+    assert_eq!(
+        client.try_approve_tender(&tender_id, &approvers.get(0).unwrap()),
+        Err(Ok(TenderError::AlreadyApproved))
+    );
+    assert!(client.get_tender(&tender_id).is_draft);
+
+    advance_ledger_time(&env, 50);
+    client.approve_tender(&tender_id, &approvers.get(2).unwrap());
+    assert!(!client.get_tender(&tender_id).is_draft);
+    assert_eq!(
+        last_event::<u64>(&env, (symbol_short!("tender"), symbol_short!("published"))),
+        Some(tender_id)
+    );
+
+    let approvals = client.get_approvals(&tender_id, &ApprovalStage::Publication);
+    assert_eq!(approvals.len(), 2);
+    assert_eq!(approvals.get(1).unwrap(), Approval { approver: approvers.get(2).unwrap(), approved_at: 50 });
+    assert_eq!(
+        client.try_approve_tender(&tender_id, &approvers.get(1).unwrap()),
+        Err(Ok(TenderError::NotPendingApproval))
+    );
+    submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    // A draft whose deadline passed awaiting approval must be amended first
+    let late = create_test_tender(&env, &client, &creator);
+    client.approve_tender(&late, &approvers.get(0).unwrap());
+    advance_ledger_time(&env, 1000);
+    assert_eq!(
+        client.try_approve_tender(&late, &approvers.get(1).unwrap()),
+        Err(Ok(TenderError::DeadlinePassed))
+    );
+    let now = env.ledger().timestamp();
+    client.amend_tender(
+        &late,
+        &creator,
+        &String::from_str(&env, "Description"),
+        &String::from_str(&env, "QmHash"),
+        &(now + 1000),
+        &(now + 2000),
+    );
+    client.approve_tender(&late, &approvers.get(1).unwrap());
+    assert!(!client.get_tender(&late).is_draft);
+}
+
+#[test]
+fn test_award_requires_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let approvers = approval_policy(&env, &client, &creator, 1);
+
+    let tender_id = create_test_tender(&env, &client, &creator);
+    client.approve_tender(&tender_id, &approvers.get(0).unwrap());
+
+    // Later policy changes do not apply to existing tenders
+    approval_policy(&env, &client, &creator, 3);
+    client.set_approval_policy(&admin, &creator, &ApprovalPolicy { approvers: Vec::new(&env), threshold: 0 });
+
+    let salt = submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &bidder, &150000, &salt);
+    assert_eq!(
+        client.try_approve_award(&tender_id, &approvers.get(0).unwrap()),
+        Err(Ok(TenderError::NotPendingApproval))
+    );
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    // The winner is provisional until approved
+    assert!(client.get_tender(&tender_id).award_pending);
+    assert_eq!(client.get_winner(&tender_id).unwrap().bidder, bidder);
+    let (token_id, _token) = test_token(&env, &[&creator], PAYMENT);
+    assert_eq!(
+        client.try_fund_escrow(&tender_id, &creator, &token_id, &PAYMENT, &None, &milestones(&env, &[100])),
+        Err(Ok(TenderError::AwardNotFinal))
+    );
+
+    client.approve_award(&tender_id, &approvers.get(1).unwrap());
+    assert!(!client.get_tender(&tender_id).award_pending);
+    assert_eq!(last_event::<u64>(&env, (symbol_short!("award"), symbol_short!("final"))), Some(tender_id));
+    assert_eq!(client.get_approvals(&tender_id, &ApprovalStage::Award).len(), 1);
+    client.fund_escrow(&tender_id, &creator, &token_id, &PAYMENT, &None, &milestones(&env, &[100]));
+
+    // Without a policy, drafts and provisional awards never arise
+    let plain_creator = Address::generate(&env);
+    let plain = create_test_tender(&env, &client, &plain_creator);
+    assert!(!client.get_tender(&plain).is_draft);
+    advance_ledger_time(&env, 2000);
+    client.close_tender(&plain, &plain_creator);
+    assert!(!client.get_tender(&plain).award_pending);
+}
+
+// Bidder registry and eligibility
+
+#[test]
+fn test_bidder_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let verifier = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let credentials = String::from_str(&env, "QmCredentials");
+
+    assert_eq!(
+        client.try_approve_bidder(&verifier, &bidder, &1000),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    grant_role(&client, &verifier, Role::Verifier);
+    assert_eq!(
+        client.try_approve_bidder(&verifier, &bidder, &1000),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+
+    client.register_bidder(&bidder, &credentials);
+    let registration = client.get_registration(&bidder).unwrap();
+    assert_eq!(registration.status, RegistrationStatus::Pending);
+    assert_eq!(registration.credentials_hash, credentials);
+    assert!(!client.is_registered(&bidder));
+
+    advance_ledger_time(&env, 100);
+    assert_eq!(
+        client.try_approve_bidder(&verifier, &bidder, &100),
+        Err(Ok(TenderError::InvalidDeadline))
+    );
+    assert!(!client.has_role(&bidder, &Role::Bidder));
+    client.approve_bidder(&verifier, &bidder, &1000);
+    assert!(client.is_registered(&bidder));
+    assert!(client.has_role(&bidder, &Role::Bidder));
+    assert_eq!(client.get_registration(&bidder).unwrap().verified_by, Some(verifier.clone()));
+    assert_eq!(
+        last_event::<(Address, Address, u64)>(&env, (symbol_short!("bidder"), symbol_short!("approved"))),
+        Some((bidder.clone(), verifier.clone(), 1000))
+    );
+
+    // Registrations lapse at their expiry
+    advance_ledger_time(&env, 900);
+    assert!(!client.is_registered(&bidder));
+
+    // Suspended bidders stay out until a Verifier reinstates them
+    client.suspend_bidder(&verifier, &bidder);
+    assert_eq!(client.get_registration(&bidder).unwrap().status, RegistrationStatus::Suspended);
+    assert!(!client.has_role(&bidder, &Role::Bidder));
+    assert_eq!(
+        last_event::<(Address, Role, Address)>(&env, (symbol_short!("role"), symbol_short!("revoked"))),
+        Some((bidder.clone(), Role::Bidder, verifier.clone()))
+    );
+    assert_eq!(
+        client.try_register_bidder(&bidder, &credentials),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+    client.approve_bidder(&verifier, &bidder, &5000);
+    assert!(client.is_registered(&bidder));
+
+    // Re-registering sends the new credentials back for review
+    client.register_bidder(&bidder, &String::from_str(&env, "QmUpdated"));
+    assert_eq!(client.get_registration(&bidder).unwrap().status, RegistrationStatus::Pending);
+    assert!(!client.has_role(&bidder, &Role::Bidder));
+    assert_eq!(
+        last_event::<(Address, Role, Address)>(&env, (symbol_short!("role"), symbol_short!("revoked"))),
+        Some((bidder.clone(), Role::Bidder, bidder.clone()))
+    );
+}
+
+#[test]
+fn test_eligibility_enforced() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let registered = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    grant_role(&client, &verifier, Role::Verifier);
+    client.register_bidder(&registered, &String::from_str(&env, "QmCredentials"));
+    client.approve_bidder(&verifier, &registered, &10_000);
+    client.register_bidder(&outsider, &String::from_str(&env, "QmPending"));
+
+    let encrypted = String::from_str(&env, "encrypted");
+    let restricted = create_tender_with_rules(
+        &env,
+        &client,
+        &creator,
+        &TenderRules { eligibility: Eligibility::Registered, ..default_rules(&env) },
+    );
+    let (commitment, _salt) = seal(&env, restricted, &outsider, 150000, 1);
+    assert_eq!(
+        client.try_submit_bid(&restricted, &outsider, &encrypted, &commitment),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+    submit_sealed_bid(&env, &client, restricted, &registered, 150000, 2);
+
+    // A SuperAdmin can withdraw the Bidder role from a registered bidder
+    let revoked = Address::generate(&env);
+    client.register_bidder(&revoked, &String::from_str(&env, "QmRevoked"));
+    client.approve_bidder(&verifier, &revoked, &10_000);
+    client.revoke_role(&admin, &revoked, &Role::Bidder);
+    let (commitment, _salt) = seal(&env, restricted, &revoked, 150000, 6);
+    assert_eq!(
+        client.try_submit_bid(&restricted, &revoked, &encrypted, &commitment),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+
+    let invited = Vec::from_array(&env, [outsider.clone()]);
+    let invite_only = create_tender_with_rules(
+        &env,
+        &client,
+        &creator,
+        &TenderRules { eligibility: Eligibility::InviteOnly(invited), ..default_rules(&env) },
+    );
+    let (commitment, _salt) = seal(&env, invite_only, &registered, 150000, 3);
+    assert_eq!(
+        client.try_submit_bid(&invite_only, &registered, &encrypted, &commitment),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+    submit_sealed_bid(&env, &client, invite_only, &outsider, 150000, 4);
+
+    // Lot bids are held to the same rule
+    let lots = create_tender_with_rules(
+        &env,
+        &client,
+        &creator,
+        &TenderRules { eligibility: Eligibility::Registered, ..lot_rules(&env, &[100, 100], 0) },
+    );
+    let (commitment, _salt) = seal(&env, lots, &outsider, 150, 5);
+    assert_eq!(
+        client.try_submit_lot_bid(&lots, &0, &outsider, &encrypted, &commitment),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+}
+
+// Debarment
+
+#[test]
+fn test_debarment_blocks_bids() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let reason = String::from_str(&env, "Bid rigging");
+
+    assert_eq!(
+        client.try_debar_bidder(&creator, &bidder, &reason, &0, &1000),
+        Err(Ok(TenderError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_debar_bidder(&admin, &bidder, &String::from_str(&env, ""), &0, &1000),
+        Err(Ok(TenderError::ReasonRequired))
+    );
+    assert_eq!(
+        client.try_debar_bidder(&admin, &bidder, &reason, &1000, &1000),
+        Err(Ok(TenderError::InvalidDeadline))
+    );
+
+    // A debarment starting later does not block bids yet
+    client.debar_bidder(&admin, &bidder, &reason, &500, &1500);
+    assert!(!client.is_debarred(&bidder));
+    let tender_id = create_test_tender(&env, &client, &creator);
+    submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, 1);
+
+    advance_ledger_time(&env, 500);
+    assert!(client.is_debarred(&bidder));
+    let other = create_test_tender(&env, &client, &creator);
+    let (commitment, _salt) = seal(&env, other, &bidder, 150000, 2);
+    assert_eq!(
+        client.try_submit_bid(&other, &bidder, &String::from_str(&env, "encrypted"), &commitment),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+
+    let debarments = client.get_debarments(&0, &10);
+    assert_eq!(debarments.len(), 1);
+    assert_eq!(debarments.get(0).unwrap().reason, reason);
+    assert_eq!(client.get_debarment(&bidder).unwrap().debarred_by, admin);
+
+    // Lifted debarments stay on the public record
+    client.lift_debarment(&admin, &bidder);
+    assert!(!client.is_debarred(&bidder));
+    let debarments = client.get_debarments(&0, &10);
+    assert_eq!(debarments.len(), 1);
+    assert_eq!(debarments.get(0).unwrap().lifted_at, Some(env.ledger().timestamp()));
+    submit_sealed_bid(&env, &client, other, &bidder, 150000, 2);
+
+    // Debarments lapse at their end
+    client.debar_bidder(&admin, &bidder, &reason, &0, &1000);
+    assert!(client.is_debarred(&bidder));
+    advance_ledger_time(&env, 500);
+    assert!(!client.is_debarred(&bidder));
+    assert_eq!(client.get_debarments(&0, &10).len(), 2);
+    assert_eq!(client.get_debarment(&bidder).unwrap().lifted_at, None);
+}
+
+#[test]
+fn test_redebarment_keeps_earlier_record() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let bidder = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.debar_bidder(&admin, &bidder, &String::from_str(&env, "Bid rigging"), &0, &1000);
+    client.debar_bidder(&admin, &other, &String::from_str(&env, "Forged documents"), &0, &1000);
+    advance_ledger_time(&env, 100);
+    client.debar_bidder(&admin, &bidder, &String::from_str(&env, "Collusion"), &0, &5000);
+
+    let debarments = client.get_debarments(&0, &10);
+    assert_eq!(debarments.len(), 3);
+    assert_eq!(debarments.get(0).unwrap().lifted_at, Some(100));
+    assert_eq!(debarments.get(1).unwrap().lifted_at, None);
+    assert_eq!(debarments.get(2).unwrap().reason, String::from_str(&env, "Collusion"));
+    assert_eq!(client.get_debarment(&bidder).unwrap().end, 5000);
+    assert_eq!(client.get_debarments(&1, &1).get(0).unwrap().bidder, other);
+
+    // Lifting a debarment that already ran out changes nothing
+    advance_ledger_time(&env, 1000);
+    client.lift_debarment(&admin, &other);
+    assert_eq!(client.get_debarments(&1, &1).get(0).unwrap().lifted_at, None);
+}
+
+#[test]
+fn test_debarred_bidder_passed_over_at_close() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let cheap = Address::generate(&env);
+    let honest = Address::generate(&env);
+
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let cheap_salt = submit_sealed_bid(&env, &client, tender_id, &cheap, 120000, 1);
+    let honest_salt = submit_sealed_bid(&env, &client, tender_id, &honest, 150000, 2);
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &cheap, &120000, &cheap_salt);
+    client.reveal_bid(&tender_id, &honest, &150000, &honest_salt);
+
+    // Caught mid-tender, after revealing the lowest bid
+    let now = env.ledger().timestamp();
+    client.debar_bidder(&admin, &cheap, &String::from_str(&env, "Forged documents"), &now, &(now + 10_000));
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    let winner = client.get_winner(&tender_id).unwrap();
+    assert_eq!(winner.bidder, honest);
+    assert_eq!(winner.amount, 150000);
+}
+
+#[test]
+fn test_close_strikes_unrevealed_bidders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let honest = Address::generate(&env);
+    let silent = Address::generate(&env);
+
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let salt = submit_sealed_bid(&env, &client, tender_id, &honest, 150000, 1);
+    submit_sealed_bid(&env, &client, tender_id, &silent, 120000, 2);
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &honest, &150000, &salt);
+
+    assert!(client.get_reveal_outcomes(&tender_id).is_empty());
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    let outcomes = client.get_reveal_outcomes(&tender_id);
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes.get(0).unwrap(), BidderOutcome { bidder: honest.clone(), outcome: RevealOutcome::Revealed });
+    assert_eq!(outcomes.get(1).unwrap(), BidderOutcome { bidder: silent.clone(), outcome: RevealOutcome::Unrevealed });
+
+    assert_eq!(client.get_strikes(&honest), None);
+    let strikes = client.get_strikes(&silent).unwrap();
+    assert_eq!(strikes.count, 1);
+    assert_eq!(strikes.last_tender, tender_id);
+    assert_eq!(strikes.last_struck_at, env.ledger().timestamp());
+    assert_eq!(
+        last_event::<(Address, u64, u32)>(&env, (symbol_short!("strike"), symbol_short!("issued"))),
+        Some((silent, tender_id, 1))
+    );
+}
+
+#[test]
+fn test_time_lock_bidders_are_not_struck() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let tender_id = create_tender_with_mode(&env, &client, &creator, OpeningMode::TimeLock);
+
+    let low = Address::generate(&env);
+    let other = Address::generate(&env);
+    let low_salt = submit_sealed_bid(&env, &client, tender_id, &low, 90000, 1);
+    let other_salt = submit_sealed_bid(&env, &client, tender_id, &other, 120000, 2);
+
+    // The authority may open the bids over several batches
+    advance_ledger_time(&env, 1000);
+    client.publish_opening_key(&tender_id, &creator, &BytesN::from_array(&env, &[9u8; 32]));
+    for (bidder, amount, salt) in [(&low, 90000, low_salt), (&other, 120000, other_salt)] {
+        let mut openings = Vec::new(&env);
+        openings.push_back(opening(bidder, amount, salt));
+        client.open_bids(&tender_id, &creator, &openings);
+    }
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    let outcomes = client.get_reveal_outcomes(&tender_id);
+    assert_eq!(outcomes.get(0).unwrap().outcome, RevealOutcome::Invalid);
+    assert_eq!(outcomes.get(1).unwrap().outcome, RevealOutcome::Revealed);
+    assert_eq!(client.get_strikes(&low), None);
+}
+
+#[test]
+fn test_time_lock_unopenable_bid_does_not_block_award() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let honest = Address::generate(&env);
+    let griefer = Address::generate(&env);
+    let (token_id, token) = test_token(&env, &[&honest, &griefer], 1000);
+
+    let bond = BidBond {
+        token: token_id,
+        amount: BOND,
+        beneficiary: treasury.clone(),
+    };
+    let rules = TenderRules {
+        opening_mode: OpeningMode::TimeLock,
+        bond: BondPolicy::Required(bond),
+        ..default_rules(&env)
+    };
+    let tender_id = create_tender_with_rules(&env, &client, &creator, &rules);
+    let salt = submit_sealed_bid(&env, &client, tender_id, &honest, 150000, 1);
+    // A commitment its ciphertext does not open to
+    submit_sealed_bid(&env, &client, tender_id, &griefer, 120000, 2);
+
+    // Anyone holding the published key may open the bids
+    advance_ledger_time(&env, 1000);
+    client.publish_opening_key(&tender_id, &creator, &BytesN::from_array(&env, &[9u8; 32]));
+    let mut openings = Vec::new(&env);
+    openings.push_back(opening(&honest, 150000, salt));
+    client.open_bids(&tender_id, &Address::generate(&env), &openings);
+
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    assert_eq!(client.get_winner(&tender_id).unwrap().bidder, honest);
+    let outcomes = client.get_reveal_outcomes(&tender_id);
+    assert_eq!(outcomes.get(1).unwrap(), BidderOutcome { bidder: griefer.clone(), outcome: RevealOutcome::Unrevealed });
+    assert_eq!(token.balance(&honest), 1000);
+    assert_eq!(token.balance(&griefer), 1000 - BOND);
+    assert_eq!(token.balance(&treasury), BOND);
+}
+
+#[test]
+fn test_strike_threshold_excludes_bidders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+
+    // Two tenders bid on and never revealed
+    for seed in 1..=2 {
+        let tender_id = create_test_tender(&env, &client, &creator);
+        submit_sealed_bid(&env, &client, tender_id, &bidder, 150000, seed);
+        advance_ledger_time(&env, 2000);
+        client.close_tender(&tender_id, &creator);
+    }
+    assert_eq!(client.get_strikes(&bidder).unwrap().count, 2);
+
+    // A tender tolerating two strikes still takes the bid; one tolerating one does not
+    let lenient = create_tender_with_rules(&env, &client, &creator, &TenderRules { max_strikes: 2, ..default_rules(&env) });
+    let strict = create_tender_with_rules(&env, &client, &creator, &TenderRules { max_strikes: 1, ..default_rules(&env) });
+    let (commitment, _) = seal(&env, lenient, &bidder, 150000, 3);
+    client.submit_bid(&lenient, &bidder, &String::from_str(&env, "encrypted"), &commitment);
+
+    let (commitment, _) = seal(&env, strict, &bidder, 150000, 4);
+    assert_eq!(
+        client.try_submit_bid(&strict, &bidder, &String::from_str(&env, "encrypted"), &commitment),
+        Err(Ok(TenderError::BidderNotEligible))
+    );
+}
+
+#[test]
+fn test_below_minimum_reveal_is_invalid_not_struck() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = create_test_contract(&env);
+    let creator = Address::generate(&env);
+    let lowball = Address::generate(&env);
+
+    let tender_id = create_test_tender(&env, &client, &creator);
+    let salt = submit_sealed_bid(&env, &client, tender_id, &lowball, 50000, 1);
+    advance_ledger_time(&env, 1000);
+    client.reveal_bid(&tender_id, &lowball, &50000, &salt);
+    advance_ledger_time(&env, 1000);
+    client.close_tender(&tender_id, &creator);
+
+    let outcomes = client.get_reveal_outcomes(&tender_id);
+    assert_eq!(outcomes.get(0).unwrap(), BidderOutcome { bidder: lowball.clone(), outcome: RevealOutcome::Invalid });
+    assert_eq!(client.get_strikes(&lowball), None);
+}